@@ -0,0 +1,55 @@
+//! Gravity kernel benchmarks.
+//!
+//! Run with `cargo bench --bench gravity`.
+
+use bevy::math::DVec3;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use planetarium::physics::gravity;
+use planetarium::physics::kernel::{self, SoaBodies};
+use planetarium::physics::octree::Octree;
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+use std::hint::black_box;
+
+fn random_bodies(count: usize) -> (Vec<DVec3>, Vec<f64>) {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    (0..count)
+        .map(|_| {
+            let position = DVec3::new(
+                rng.random_range(-1.0..1.0),
+                rng.random_range(-1.0..1.0),
+                rng.random_range(-1.0..1.0),
+            );
+            (position, rng.random_range(0.5..2.0))
+        })
+        .unzip()
+}
+
+fn bench_gravity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gravity");
+
+    for count in [256, 1024, 4096] {
+        let (positions, masses) = random_bodies(count);
+        let soa = SoaBodies::from_slices(&positions, &masses);
+        let mut out = vec![DVec3::ZERO; count];
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("serial", count), &count, |b, _| {
+            b.iter(|| gravity::accelerations(black_box(&positions), &masses, 1.0, &mut out));
+        });
+        group.bench_with_input(BenchmarkId::new("soa_parallel", count), &count, |b, _| {
+            b.iter(|| kernel::accelerations(black_box(&soa), 1.0, &mut out));
+        });
+        group.bench_with_input(BenchmarkId::new("barnes_hut", count), &count, |b, _| {
+            b.iter(|| {
+                Octree::build(black_box(&positions), &masses).accelerations(
+                    &positions, &masses, 1.0, 0.5, &mut out,
+                );
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gravity);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use fluent_bundle::bundle::FluentBundle;
 // We need to re-export Localization for boot/mod.rs if it uses crate::boot::localization::Localization
-use localization::utils::{load_ftl_into_bundle, parse_language_id};
+use localization::utils::{load_text_dir_into_bundle, parse_language_id};
 pub use localization::{LanguageChanged, Localization, LocalizedStrings};
 
 use crate::boot::AppPaths;
@@ -25,8 +25,8 @@ pub fn setup_localization(
             fallback_lang.clone(),
         ]);
 
-    // Load common strings (menu.ftl) into fallback bundle
-    load_ftl_into_bundle(&mut fallback_bundle, &paths.assets_dir, "en-US", "menu.ftl");
+    // Load common strings (every text/*.ftl) into fallback bundle
+    load_text_dir_into_bundle(&mut fallback_bundle, &paths.assets_dir, "en-US");
 
     let mut localization = Localization::new(
         primary_lang.clone(),
@@ -57,12 +57,7 @@ pub fn setup_localization(
 
     // Only attempt to load locale files if the assets locales directory exists.
     if paths.assets_dir.join("locales").exists() {
-        localization::utils::load_ftl_into_bundle(
-            localization.main_bundle_mut(),
-            &paths.assets_dir,
-            &resolved,
-            "menu.ftl",
-        );
+        load_text_dir_into_bundle(localization.main_bundle_mut(), &paths.assets_dir, &resolved);
     } else {
         let mut warn_args = fluent_bundle::FluentArgs::new();
         warn_args.set("path", paths.assets_dir.display().to_string());
@@ -107,7 +102,7 @@ pub fn apply_language_change_system(
                 fallback_lang.clone(),
             ]);
 
-        load_ftl_into_bundle(&mut fallback_bundle, &paths.assets_dir, "en-US", "menu.ftl");
+        load_text_dir_into_bundle(&mut fallback_bundle, &paths.assets_dir, "en-US");
 
         let requested_locale = settings.language.clone();
         let resolved =
@@ -121,7 +116,7 @@ pub fn apply_language_change_system(
 
         // Only attempt to load locale files if the assets locales directory exists.
         if paths.assets_dir.join("locales").exists() {
-            load_ftl_into_bundle(&mut main_bundle, &paths.assets_dir, &resolved, "menu.ftl");
+            load_text_dir_into_bundle(&mut main_bundle, &paths.assets_dir, &resolved);
         } else {
             warn!(
                 "[Localization] Locales directory not present under assets ({}); skipping per-locale load and using fallback en-US",
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 
 /// Current version of the settings schema, used for migrations.
-pub const SETTINGS_VERSION: u32 = 4;
+pub const SETTINGS_VERSION: u32 = 5;
 
 /// Quality presets for graphics settings.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, Reflect)]
@@ -51,6 +51,8 @@ pub struct UserSettings {
     pub audio: AudioSettings,
     #[serde(default)]
     pub graphics: GraphicsSettings,
+    #[serde(default)]
+    pub controls: ControlsSettings,
     /// Allows launching multiple game instances simultaneously.
     #[serde(default)]
     pub allow_multiple_instances: bool,
@@ -75,6 +77,19 @@ pub struct AudioSettings {
     pub sfx_volume: f32,
 }
 
+/// Mouse and camera control settings.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct ControlsSettings {
+    /// Multiplier on mouse rotate and pan speeds.
+    pub mouse_sensitivity: f32,
+    /// Dragging up lowers the camera instead of raising it.
+    pub invert_y: bool,
+    /// Scrolling up zooms out instead of in.
+    pub invert_wheel: bool,
+}
+
 impl Default for DisplaySettings {
     fn default() -> Self {
         Self {
@@ -96,6 +111,23 @@ impl Default for AudioSettings {
     }
 }
 
+impl Default for ControlsSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 1.0,
+            invert_y: false,
+            invert_wheel: false,
+        }
+    }
+}
+
+impl ControlsSettings {
+    /// Lowest accepted `mouse_sensitivity`.
+    pub const MIN_MOUSE_SENSITIVITY: f32 = 0.1;
+    /// Highest accepted `mouse_sensitivity`.
+    pub const MAX_MOUSE_SENSITIVITY: f32 = 10.0;
+}
+
 impl Default for UserSettings {
     fn default() -> Self {
         Self {
@@ -105,6 +137,7 @@ impl Default for UserSettings {
             display: DisplaySettings::default(),
             audio: AudioSettings::default(),
             graphics: GraphicsSettings::default(),
+            controls: ControlsSettings::default(),
             allow_multiple_instances: false,
         }
     }
@@ -172,6 +205,9 @@ pub fn migrate_settings(mut old: UserSettings, _paths: &AppPaths) -> UserSetting
     // Simple version bump for now, as Serde's #[serde(default)] handles
     // addition of new fields during deserialization.
     // For more complex migrations (e.g. data renames), add logic here.
+    //
+    // v4 -> v5: added `controls`; older files get `ControlsSettings::default()`
+    // through `#[serde(default)]`, so there is nothing to convert.
     old.version = SETTINGS_VERSION;
     old
 }
@@ -231,6 +267,14 @@ mod tests {
         assert!(!s.allow_multiple_instances);
     }
 
+    #[test]
+    fn missing_controls_use_defaults() {
+        let parsed: UserSettings =
+            ron::from_str("UserSettings(version: 4)").expect("minimal settings should deserialize");
+        assert_eq!(parsed.controls, ControlsSettings::default());
+        assert!(!parsed.controls.invert_y);
+    }
+
     #[test]
     fn missing_allow_multiple_instances_defaults_to_false() {
         let parsed: UserSettings =
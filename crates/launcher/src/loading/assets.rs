@@ -16,6 +16,9 @@ pub struct AssetManifest {
     pub fonts: HashMap<String, String>,
     pub audio: HashMap<String, String>,
     pub branding: HashMap<String, String>,
+    /// Data catalogs (e.g. star lists) keyed by name. Optional in `assets.ron`.
+    #[serde(default)]
+    pub catalogs: HashMap<String, String>,
 }
 
 impl AssetManifest {
@@ -33,6 +36,11 @@ impl AssetManifest {
     pub fn branding(&self, key: &str) -> Option<&String> {
         self.branding.get(key)
     }
+
+    /// Helper to get a catalog path by key.
+    pub fn catalog(&self, key: &str) -> Option<&String> {
+        self.catalogs.get(key)
+    }
 }
 
 /// Runtime cache for frequently used asset handles.
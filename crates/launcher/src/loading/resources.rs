@@ -40,4 +40,7 @@ pub struct AssetLoadingState {
     pub loaded_count: usize,
     /// Total number of assets tracked (cached from `required_assets.len()`).
     pub total_count: usize,
+    /// Set when the content being loaded cannot be played; loading then
+    /// never completes and the state is left to whoever reported the failure.
+    pub failed: bool,
 }
@@ -147,6 +147,9 @@ pub fn update_loading_progress(
     mut fade: ResMut<ScreenFade<AppState>>,
     localization: Res<Localization>,
 ) {
+    if loading_state.failed {
+        return;
+    }
     let total = loading_state.required_assets.len();
 
     if total == 0 {
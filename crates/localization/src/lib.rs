@@ -138,6 +138,11 @@ impl Localization {
         key.to_string()
     }
 
+    /// Whether `key` exists in the current language or the fallback.
+    pub fn has(&self, key: &str) -> bool {
+        self.main_bundle.has_message(key) || self.fallback_bundle.has_message(key)
+    }
+
     /// Internal translation helper to avoid recursion when logging missing keys.
     fn t_silent(&self, key: &str, args: Option<&FluentArgs>, log_errors: bool) -> String {
         if let Some(translated) =
@@ -77,6 +77,43 @@ pub fn load_ftl_into_bundle(
     }
 }
 
+/// Load every Fluent file in a locale's `text` directory into a bundle.
+///
+/// `menu.ftl` is loaded first and the remaining files in name order, so
+/// feature-specific files (e.g. `sky.ftl`) can be added without touching
+/// the loaders. If the directory is missing, `menu.ftl` is still attempted
+/// so the usual warning is logged.
+pub fn load_text_dir_into_bundle(
+    bundle: &mut FluentBundleType,
+    assets_dir: &std::path::Path,
+    locale: &str,
+) {
+    for file in text_files(assets_dir, locale) {
+        load_ftl_into_bundle(bundle, assets_dir, locale, &file);
+    }
+}
+
+/// Names of the `.ftl` files for `locale`, with `menu.ftl` first.
+fn text_files(assets_dir: &std::path::Path, locale: &str) -> Vec<String> {
+    let resolved_locale = resolve_locale_dir(assets_dir, locale);
+    let dir = assets_dir.join(format!("locales/{}/text", resolved_locale));
+
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .filter(|name| name.ends_with(".ftl"))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !files.iter().any(|name| name == "menu.ftl") {
+        files.push("menu.ftl".to_string());
+    }
+    files.sort_by(|a, b| (a != "menu.ftl", a).cmp(&(b != "menu.ftl", b)));
+    files
+}
+
 /// Attempt to map a requested locale string to an available locale directory.
 pub fn resolve_locale_dir(assets_dir: &std::path::Path, locale: &str) -> String {
     let locales_dir = assets_dir.join("locales");
@@ -161,4 +198,23 @@ mod tests {
         // Clean up
         let _ = fs::remove_dir_all(&base);
     }
+
+    #[test]
+    fn text_files_lists_menu_first() {
+        let base = std::env::temp_dir().join("planetarium_loc_files_test");
+        let _ = fs::remove_dir_all(&base);
+        let text = base.join("locales/en-US/text");
+        fs::create_dir_all(&text).unwrap();
+        for file in ["sky.ftl", "menu.ftl", "about.ftl", "notes.txt"] {
+            fs::write(text.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            text_files(&base, "en-US"),
+            vec!["menu.ftl", "about.ftl", "sky.ftl"]
+        );
+        assert_eq!(text_files(&base, "de-DE"), vec!["menu.ftl"]);
+
+        let _ = fs::remove_dir_all(&base);
+    }
 }
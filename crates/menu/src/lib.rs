@@ -3,6 +3,7 @@ use bevy::prelude::*;
 pub mod modal;
 // Redundant modal code removed. Using modal module instead.
 
+pub mod error;
 pub mod events;
 pub mod main_menu;
 // pub mod modal; // Removed duplicate
@@ -26,6 +27,7 @@ impl Plugin for MenuPlugin {
                 pause::PausePlugin,
                 settings::SettingsPlugin,
                 modal::ModalPlugin,
+                error::ErrorPlugin,
             ))
             .init_resource::<settings::SettingsOpen>()
             .init_resource::<settings::ActiveSettingsTab>()
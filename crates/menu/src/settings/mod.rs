@@ -31,7 +31,7 @@ pub use validation::*;
 pub use launcher::config::AppPaths;
 pub use launcher::config::settings::save_settings;
 pub use launcher::config::settings::{
-    AudioSettings, DisplaySettings, GraphicsSettings, Quality, UserSettings,
+    AudioSettings, ControlsSettings, DisplaySettings, GraphicsSettings, Quality, UserSettings,
 };
 
 /// Resource tracking visibility.
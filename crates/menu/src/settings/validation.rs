@@ -2,7 +2,7 @@
 //!
 //! Centralized validation for user settings sub-structures.
 
-use launcher::config::settings::{AudioSettings, DisplaySettings};
+use launcher::config::settings::{AudioSettings, ControlsSettings, DisplaySettings};
 
 /// Trait for validating settings structures.
 pub trait Validate {
@@ -67,3 +67,28 @@ impl Validate for AudioSettings {
         }
     }
 }
+
+impl Validate for ControlsSettings {
+    type Error = Vec<String>;
+
+    fn validate(&self) -> Result<(), Self::Error> {
+        let mut errors = Vec::new();
+
+        let range =
+            ControlsSettings::MIN_MOUSE_SENSITIVITY..=ControlsSettings::MAX_MOUSE_SENSITIVITY;
+        if !range.contains(&self.mouse_sensitivity) {
+            errors.push(format!(
+                "mouse_sensitivity {} out of range [{:.1}, {:.1}]",
+                self.mouse_sensitivity,
+                range.start(),
+                range.end()
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
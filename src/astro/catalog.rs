@@ -0,0 +1,341 @@
+//! # Star Catalogs
+//!
+//! Parses bright-star CSV catalogs into equatorial positions, magnitudes and
+//! colours. Two layouts are recognised from the header row:
+//!
+//! - **HYG** (`ra` in hours, `dec` in degrees, `mag`, optional `ci`, `hip`,
+//!   `proper` and `dist`; the Sun is skipped).
+//! - **Yale Bright Star** (`RAh`/`RAm`/`RAs`, `DE-`/`DEd`/`DEm`/`DEs`, `Vmag`,
+//!   optional `B-V` and `Name`; blank placeholder rows are skipped). This
+//!   layout carries no Hipparcos numbers, so sky-culture figures cannot use it.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use thiserror::Error;
+
+use super::frames::Spherical;
+
+/// Brightest apparent magnitude the size and intensity scales are fitted to.
+pub const BRIGHTEST_MAGNITUDE: f32 = -1.5;
+
+/// Failure to read a star catalog.
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("Failed to read star catalog {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Malformed star catalog: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Unrecognised star catalog header; expected HYG or Yale Bright Star columns")]
+    UnknownFormat,
+}
+
+/// One catalog entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Star {
+    pub name: Option<String>,
+    /// Hipparcos number, which sky-culture figures refer to.
+    pub hip: Option<u32>,
+    /// Right ascension in radians.
+    pub right_ascension: f64,
+    /// Declination in radians.
+    pub declination: f64,
+    /// Apparent visual magnitude.
+    pub magnitude: f32,
+    /// B − V colour index, if known.
+    pub color_index: Option<f32>,
+}
+
+impl Star {
+    /// Unit vector in the equatorial frame (+Z to the celestial north pole,
+    /// +X to the March equinox).
+    pub fn direction(&self) -> DVec3 {
+        Spherical::new(self.right_ascension, self.declination).to_vector()
+    }
+}
+
+/// The stars loaded from the manifest's `stars` catalog, brightest first.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct StarCatalog {
+    pub stars: Vec<Star>,
+}
+
+impl StarCatalog {
+    pub fn load(path: &Path) -> Result<Self, CatalogError> {
+        let file = std::fs::File::open(path).map_err(|source| CatalogError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(file)
+    }
+
+    /// Parses a HYG or Yale Bright Star CSV.
+    pub fn parse(reader: impl Read) -> Result<Self, CatalogError> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+
+        let layout = if let (Some(ra), Some(dec), Some(mag)) =
+            (column("ra"), column("dec"), column("mag"))
+        {
+            Layout::Hyg {
+                ra,
+                dec,
+                mag,
+                ci: column("ci"),
+                hip: column("hip"),
+                name: column("proper"),
+                dist: column("dist"),
+            }
+        } else if let (Some(ra), Some(dec_sign), Some(mag)) =
+            (column("RAh"), column("DE-"), column("Vmag"))
+        {
+            Layout::Ybs {
+                ra,
+                dec_sign,
+                mag,
+                bv: column("B-V"),
+                name: column("Name"),
+            }
+        } else {
+            return Err(CatalogError::UnknownFormat);
+        };
+
+        let mut stars = Vec::new();
+        for record in reader.records() {
+            if let Some(star) = layout.star(&record?) {
+                stars.push(star);
+            }
+        }
+        stars.sort_by(|a, b| a.magnitude.total_cmp(&b.magnitude));
+        Ok(Self { stars })
+    }
+
+    /// Stars at or brighter than `limiting_magnitude`.
+    pub fn visible(&self, limiting_magnitude: f32) -> impl Iterator<Item = &Star> {
+        self.stars
+            .iter()
+            .take_while(move |star| star.magnitude <= limiting_magnitude)
+    }
+}
+
+/// Column indices of a recognised catalog layout.
+enum Layout {
+    Hyg {
+        ra: usize,
+        dec: usize,
+        mag: usize,
+        ci: Option<usize>,
+        hip: Option<usize>,
+        name: Option<usize>,
+        dist: Option<usize>,
+    },
+    Ybs {
+        /// `RAh`, followed by `RAm` and `RAs`.
+        ra: usize,
+        /// `DE-`, followed by `DEd`, `DEm` and `DEs`.
+        dec_sign: usize,
+        mag: usize,
+        bv: Option<usize>,
+        name: Option<usize>,
+    },
+}
+
+impl Layout {
+    fn star(&self, record: &csv::StringRecord) -> Option<Star> {
+        let text = |index: usize| record.get(index).map(str::trim).filter(|s| !s.is_empty());
+        let number = |index: usize| text(index)?.parse::<f64>().ok();
+        let name = |index: Option<usize>| text(index?).map(str::to_string);
+
+        match *self {
+            Layout::Hyg {
+                ra,
+                dec,
+                mag,
+                ci,
+                hip,
+                name: proper,
+                dist,
+            } => {
+                // Distance 0 marks the Sun.
+                if dist.and_then(number) == Some(0.0) {
+                    return None;
+                }
+                Some(Star {
+                    name: name(proper),
+                    hip: hip.and_then(|index| text(index)?.parse().ok()),
+                    right_ascension: (number(ra)? * 15.0).to_radians(),
+                    declination: number(dec)?.to_radians(),
+                    magnitude: number(mag)? as f32,
+                    color_index: ci.and_then(number).map(|ci| ci as f32),
+                })
+            }
+            Layout::Ybs {
+                ra,
+                dec_sign,
+                mag,
+                bv,
+                name: label,
+            } => {
+                let hours = number(ra)? + number(ra + 1)? / 60.0 + number(ra + 2)? / 3600.0;
+                let degrees = number(dec_sign + 1)?
+                    + number(dec_sign + 2)? / 60.0
+                    + number(dec_sign + 3)? / 3600.0;
+                let sign = if text(dec_sign)? == "-" { -1.0 } else { 1.0 };
+                Some(Star {
+                    name: name(label),
+                    hip: None,
+                    right_ascension: (hours * 15.0).to_radians(),
+                    declination: (sign * degrees).to_radians(),
+                    magnitude: number(mag)? as f32,
+                    color_index: bv.and_then(number).map(|bv| bv as f32),
+                })
+            }
+        }
+    }
+}
+
+/// Angular diameter in radians for a star of `magnitude`.
+///
+/// Stars at the limit get `min_size`; each 2.5 magnitudes brighter scales
+/// the diameter by 10^¼, so flux maps to roughly the square of the area.
+pub fn star_angular_size(magnitude: f32, limiting_magnitude: f32, min_size: f32) -> f32 {
+    let relative_flux = 10f32.powf(0.4 * (limiting_magnitude - magnitude).max(0.0));
+    min_size * relative_flux.powf(0.25)
+}
+
+/// Display intensity in `[0.25, 1]`, linear in magnitude from the limit up to
+/// `BRIGHTEST_MAGNITUDE`.
+pub fn star_intensity(magnitude: f32, limiting_magnitude: f32) -> f32 {
+    let span = (limiting_magnitude - BRIGHTEST_MAGNITUDE).max(f32::EPSILON);
+    let t = ((limiting_magnitude - magnitude) / span).clamp(0.0, 1.0);
+    0.25 + 0.75 * t
+}
+
+/// Approximate sRGB colour of a star with B − V colour index `bv`.
+///
+/// Uses Ballesteros' B − V → temperature relation and a Planckian-locus fit.
+pub fn color_from_bv(bv: f32) -> [f32; 3] {
+    let bv = bv.clamp(-0.4, 2.0);
+    let temperature = 4600.0 * (1.0 / (0.92 * bv + 1.7) + 1.0 / (0.92 * bv + 0.62));
+
+    // Tanner Helland's blackbody fit, in hundreds of kelvin.
+    let t = temperature / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.7 * (t - 60.0).powf(-0.1332)
+    };
+    let green = if t <= 66.0 {
+        99.47 * t.ln() - 161.12
+    } else {
+        288.12 * (t - 60.0).powf(-0.0755)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.52 * (t - 10.0).ln() - 305.04
+    };
+    [red, green, blue].map(|channel| (channel / 255.0).clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HYG: &str = include_str!("../../tests/fixtures/hyg_sample.csv");
+    const YBS: &str = include_str!("../../tests/fixtures/ybs_sample.csv");
+
+    fn find<'a>(catalog: &'a StarCatalog, name: &str) -> &'a Star {
+        catalog
+            .stars
+            .iter()
+            .find(|star| star.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("{name} missing"))
+    }
+
+    #[test]
+    fn parses_hyg_fixture() {
+        let catalog = StarCatalog::parse(HYG.as_bytes()).expect("fixture should parse");
+        assert_eq!(catalog.stars.len(), 4, "the Sun is skipped");
+        assert_eq!(catalog.stars[0].name.as_deref(), Some("Sirius"));
+
+        let vega = find(&catalog, "Vega");
+        assert!((vega.right_ascension.to_degrees() - 279.2347).abs() < 1e-3);
+        assert!((vega.declination.to_degrees() - 38.7837).abs() < 1e-3);
+        assert_eq!(vega.magnitude, 0.03);
+        assert_eq!(vega.hip, Some(91262));
+
+        let faint = catalog.stars.last().unwrap();
+        assert_eq!(faint.name, None);
+        assert_eq!(faint.color_index, None);
+    }
+
+    #[test]
+    fn parses_ybs_fixture() {
+        let catalog = StarCatalog::parse(YBS.as_bytes()).expect("fixture should parse");
+        assert_eq!(catalog.stars.len(), 3, "the blank HR 92 row is skipped");
+
+        let sirius = find(&catalog, "Alp CMa");
+        assert!((sirius.declination.to_degrees() + 16.7161).abs() < 1e-3);
+        assert!((sirius.right_ascension.to_degrees() - 101.2871).abs() < 1e-3);
+
+        let polaris = find(&catalog, "Alp UMi");
+        assert!(polaris.direction().z > 0.9998);
+    }
+
+    #[test]
+    fn both_layouts_agree() {
+        let hyg = StarCatalog::parse(HYG.as_bytes()).unwrap();
+        let ybs = StarCatalog::parse(YBS.as_bytes()).unwrap();
+        let angle = find(&hyg, "Vega")
+            .direction()
+            .angle_between(find(&ybs, "Alp Lyr").direction());
+        assert!(angle.to_degrees() < 0.01);
+    }
+
+    #[test]
+    fn limiting_magnitude_filters() {
+        let catalog = StarCatalog::parse(HYG.as_bytes()).unwrap();
+        assert_eq!(catalog.visible(6.5).count(), 3);
+        assert_eq!(catalog.visible(0.0).count(), 1);
+    }
+
+    #[test]
+    fn default_limit_covers_the_bundled_catalog() {
+        let bundled = include_str!("../../assets/catalogs/bright_stars.csv");
+        let catalog = StarCatalog::parse(bundled.as_bytes()).unwrap();
+        let limit = crate::config::gameplay::StarConfig::default().limiting_magnitude;
+        assert_eq!(catalog.visible(limit).count(), catalog.stars.len());
+    }
+
+    #[test]
+    fn rejects_unknown_layout() {
+        let result = StarCatalog::parse("a,b,c\n1,2,3\n".as_bytes());
+        assert!(matches!(result, Err(CatalogError::UnknownFormat)));
+    }
+
+    #[test]
+    fn brighter_stars_are_larger_and_brighter() {
+        assert_eq!(star_angular_size(6.5, 6.5, 1.0), 1.0);
+        assert!((star_angular_size(-3.5, 6.5, 1.0) - 10.0).abs() < 1e-4);
+        assert!(star_intensity(0.0, 6.5) > star_intensity(5.0, 6.5));
+        assert_eq!(star_intensity(7.0, 6.5), 0.25);
+    }
+
+    #[test]
+    fn hot_stars_are_blue_and_cool_stars_red() {
+        let [r, _, b] = color_from_bv(-0.3);
+        assert!(b >= r);
+        let [r, _, b] = color_from_bv(1.8);
+        assert!(r > b);
+    }
+}
@@ -0,0 +1,399 @@
+//! # Reference Frames
+//!
+//! Rotations between the celestial frames the planetarium works in:
+//!
+//! - **ICRS**: the catalog frame, treated as the mean equator and equinox of
+//!   J2000.0 (the ~20 mas frame bias is ignored).
+//! - **Equatorial of date**: mean equator and equinox of date (IAU 1976
+//!   precession, no nutation).
+//! - **Ecliptic** of J2000.0 or of date, +Z to the ecliptic pole.
+//! - **Galactic** (IAU 1958, as realised for Hipparcos).
+//! - **Horizontal** for an observer: east, north and zenith axes.
+//!
+//! Every frame is right-handed and related to ICRS by a rotation, so
+//! [`convert`] composes two matrices and the inverse is a transpose.
+//! Sidereal time is the mean (GMST) value; UT1 is taken as UTC.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use bevy::math::{DMat3, DVec3};
+
+use super::JulianDate;
+use crate::constants::OBLIQUITY_J2000;
+
+const ARCSEC: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// ICRS to galactic rotation (rows are the galactic axes in ICRS).
+const GALACTIC_ROWS: [[f64; 3]; 3] = [
+    [
+        -0.054_875_560_416_215_4,
+        -0.873_437_090_234_885,
+        -0.483_835_015_548_713_2,
+    ],
+    [
+        0.494_109_427_875_583_7,
+        -0.444_829_629_960_011_2,
+        0.746_982_244_497_219,
+    ],
+    [
+        -0.867_666_149_019_004_7,
+        -0.198_076_373_431_201_5,
+        0.455_983_776_175_066_9,
+    ],
+];
+
+/// Longitude/latitude pair in radians: (α, δ), (λ, β) or (l, b) depending
+/// on the frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spherical {
+    /// Longitude in `[0, 2π)`.
+    pub lon: f64,
+    /// Latitude in `[-π/2, π/2]`.
+    pub lat: f64,
+}
+
+impl Spherical {
+    pub fn new(lon: f64, lat: f64) -> Self {
+        Self { lon, lat }
+    }
+
+    /// Unit vector with +X at zero longitude and +Z at the pole.
+    pub fn to_vector(self) -> DVec3 {
+        let (sin_lon, cos_lon) = self.lon.sin_cos();
+        let (sin_lat, cos_lat) = self.lat.sin_cos();
+        DVec3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat)
+    }
+
+    pub fn from_vector(v: DVec3) -> Self {
+        let v = v.normalize();
+        Self {
+            lon: v.y.atan2(v.x).rem_euclid(TAU),
+            lat: v.z.clamp(-1.0, 1.0).asin(),
+        }
+    }
+}
+
+/// Azimuth and altitude in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Horizontal {
+    /// Azimuth from north through east, in `[0, 2π)`.
+    pub azimuth: f64,
+    /// Altitude above the geometric horizon.
+    pub altitude: f64,
+}
+
+impl Horizontal {
+    /// Unit vector in the horizontal frame (+X east, +Y north, +Z zenith).
+    pub fn to_vector(self) -> DVec3 {
+        let (sin_az, cos_az) = self.azimuth.sin_cos();
+        let (sin_alt, cos_alt) = self.altitude.sin_cos();
+        DVec3::new(cos_alt * sin_az, cos_alt * cos_az, sin_alt)
+    }
+
+    pub fn from_vector(v: DVec3) -> Self {
+        let v = v.normalize();
+        Self {
+            azimuth: v.x.atan2(v.y).rem_euclid(TAU),
+            altitude: v.z.clamp(-1.0, 1.0).asin(),
+        }
+    }
+}
+
+/// A place on a body's surface, in radians.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Observer {
+    /// Geodetic latitude, north positive.
+    pub latitude: f64,
+    /// Longitude, east positive.
+    pub longitude: f64,
+}
+
+impl Observer {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+/// A celestial reference frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frame {
+    Icrs,
+    /// Mean equator and equinox of the given TT date.
+    EquatorialOfDate(JulianDate),
+    EclipticJ2000,
+    /// Mean ecliptic and equinox of the given TT date.
+    EclipticOfDate(JulianDate),
+    Galactic,
+    /// Horizon of `observer` at the given UT date.
+    Horizontal {
+        observer: Observer,
+        date: JulianDate,
+    },
+}
+
+impl Frame {
+    /// Rotation taking ICRS vectors into this frame.
+    pub fn from_icrs(&self) -> DMat3 {
+        match *self {
+            Frame::Icrs => DMat3::IDENTITY,
+            Frame::EquatorialOfDate(date) => precession(date),
+            Frame::EclipticJ2000 => rot_x(OBLIQUITY_J2000),
+            Frame::EclipticOfDate(date) => rot_x(mean_obliquity(date)) * precession(date),
+            Frame::Galactic => DMat3::from_cols_array_2d(&GALACTIC_ROWS).transpose(),
+            Frame::Horizontal { observer, date } => {
+                let lst = local_sidereal_time(date, observer.longitude);
+                // Equator of date -> south/east/zenith -> east/north/zenith.
+                let to_south_east_up = rot_y(FRAC_PI_2 - observer.latitude) * rot_z(lst);
+                let to_east_north_up = DMat3::from_cols(DVec3::NEG_Y, DVec3::X, DVec3::Z);
+                to_east_north_up * to_south_east_up * precession(date)
+            }
+        }
+    }
+
+    /// Rotation taking vectors in this frame into ICRS.
+    pub fn to_icrs(&self) -> DMat3 {
+        self.from_icrs().transpose()
+    }
+}
+
+/// Rotates `v` from frame `from` into frame `to`.
+pub fn convert(v: DVec3, from: &Frame, to: &Frame) -> DVec3 {
+    to.from_icrs() * (from.to_icrs() * v)
+}
+
+/// Mean obliquity of the ecliptic (IAU 1976), in radians.
+pub fn mean_obliquity(date: JulianDate) -> f64 {
+    let t = date.centuries_since_j2000();
+    OBLIQUITY_J2000 + (-46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t) * ARCSEC
+}
+
+/// IAU 1976 precession from J2000.0 to the mean equator of `date` (TT).
+pub fn precession(date: JulianDate) -> DMat3 {
+    let t = date.centuries_since_j2000();
+    let zeta = (2306.2181 * t + 0.30188 * t * t + 0.017998 * t * t * t) * ARCSEC;
+    let z = (2306.2181 * t + 1.09468 * t * t + 0.018203 * t * t * t) * ARCSEC;
+    let theta = (2004.3109 * t - 0.42665 * t * t - 0.041833 * t * t * t) * ARCSEC;
+    rot_z(-z) * rot_y(theta) * rot_z(-zeta)
+}
+
+/// Greenwich mean sidereal time in radians for a UT1 date (Meeus 12.4).
+pub fn greenwich_sidereal_time(date: JulianDate) -> f64 {
+    let d = date.days_since_j2000();
+    let t = date.centuries_since_j2000();
+    let degrees =
+        280.460_618_37 + 360.985_647_366_29 * d + 0.000_387_933 * t * t - t * t * t / 38_710_000.0;
+    degrees.to_radians().rem_euclid(TAU)
+}
+
+/// Local mean sidereal time in radians at east `longitude` (radians).
+pub fn local_sidereal_time(date: JulianDate, longitude: f64) -> f64 {
+    (greenwich_sidereal_time(date) + longitude).rem_euclid(TAU)
+}
+
+/// Hour angle of a right ascension at an observer's longitude, in `(-π, π]`.
+pub fn hour_angle(date: JulianDate, longitude: f64, right_ascension: f64) -> f64 {
+    let h = (local_sidereal_time(date, longitude) - right_ascension).rem_euclid(TAU);
+    if h > std::f64::consts::PI { h - TAU } else { h }
+}
+
+/// Passive rotation of the axes about X by `angle`.
+fn rot_x(angle: f64) -> DMat3 {
+    DMat3::from_rotation_x(-angle)
+}
+
+/// Passive rotation of the axes about Y by `angle`.
+fn rot_y(angle: f64) -> DMat3 {
+    DMat3::from_rotation_y(-angle)
+}
+
+/// Passive rotation of the axes about Z by `angle`.
+fn rot_z(angle: f64) -> DMat3 {
+    DMat3::from_rotation_z(-angle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::CalendarDate;
+    use crate::astro::time::{Calendar, J2000};
+
+    fn hms(h: f64, m: f64, s: f64) -> f64 {
+        ((h + m / 60.0 + s / 3600.0) * 15.0).to_radians()
+    }
+
+    fn dms(sign: f64, d: f64, m: f64, s: f64) -> f64 {
+        (sign * (d + m / 60.0 + s / 3600.0)).to_radians()
+    }
+
+    fn date(text: &str) -> JulianDate {
+        JulianDate::from_calendar(&text.parse::<CalendarDate>().unwrap(), Calendar::Reform).unwrap()
+    }
+
+    fn assert_degrees(actual: f64, expected: f64, tolerance: f64) {
+        let diff = (actual.to_degrees() - expected + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(
+            diff.abs() < tolerance,
+            "{} != {expected} (±{tolerance})",
+            actual.to_degrees()
+        );
+    }
+
+    #[test]
+    fn greenwich_sidereal_time_matches_meeus() {
+        // Meeus, examples 12.a and 12.b.
+        assert_degrees(
+            greenwich_sidereal_time(date("1987-04-10T00:00:00")),
+            hms(13.0, 10.0, 46.3668).to_degrees(),
+            1e-5,
+        );
+        assert_degrees(
+            greenwich_sidereal_time(date("1987-04-10T19:21:00")),
+            hms(8.0, 34.0, 57.0896).to_degrees(),
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn equatorial_to_ecliptic_matches_meeus() {
+        // Meeus, example 13.a: Pollux.
+        let pollux = Spherical::new(hms(7.0, 45.0, 18.946), dms(1.0, 28.0, 1.0, 34.26));
+        let ecliptic = Spherical::from_vector(convert(
+            pollux.to_vector(),
+            &Frame::Icrs,
+            &Frame::EclipticJ2000,
+        ));
+        assert_degrees(ecliptic.lon, 113.215_630, 1e-4);
+        assert_degrees(ecliptic.lat, 6.684_170, 1e-4);
+    }
+
+    #[test]
+    fn horizontal_matches_meeus() {
+        // Meeus, example 13.b: Venus from Washington. Meeus uses apparent
+        // sidereal time and measures azimuth from the south.
+        let when = date("1987-04-10T19:21:00");
+        let venus = Spherical::new(hms(23.0, 9.0, 16.641), dms(-1.0, 6.0, 43.0, 11.61));
+        let observer = Observer::new(dms(1.0, 38.0, 55.0, 17.0), dms(-1.0, 77.0, 3.0, 56.0));
+
+        assert_degrees(
+            hour_angle(when, observer.longitude, venus.lon),
+            64.352_133,
+            0.005,
+        );
+        let horizontal = Horizontal::from_vector(convert(
+            venus.to_vector(),
+            &Frame::EquatorialOfDate(when),
+            &Frame::Horizontal {
+                observer,
+                date: when,
+            },
+        ));
+        assert_degrees(horizontal.azimuth, 68.0337 + 180.0, 0.005);
+        assert_degrees(horizontal.altitude, 15.1249, 0.005);
+    }
+
+    #[test]
+    fn horizontal_axes() {
+        let when = date("2024-03-20T00:00:00");
+        let observer = Observer::new(0.8, 0.3);
+        let frame = Frame::Horizontal {
+            observer,
+            date: when,
+        };
+        let to_horizontal = |v: DVec3| convert(v, &Frame::EquatorialOfDate(when), &frame);
+
+        // The celestial pole sits due north at the observer's latitude.
+        let pole = Horizontal::from_vector(to_horizontal(DVec3::Z));
+        assert_degrees(pole.azimuth, 0.0, 1e-9);
+        assert_degrees(pole.altitude, observer.latitude.to_degrees(), 1e-9);
+
+        // The meridian on the equator culminates due south.
+        let lst = local_sidereal_time(when, observer.longitude);
+        let meridian = Horizontal::from_vector(to_horizontal(Spherical::new(lst, 0.0).to_vector()));
+        assert_degrees(meridian.azimuth, 180.0, 1e-9);
+        assert_degrees(
+            meridian.altitude,
+            90.0 - observer.latitude.to_degrees(),
+            1e-9,
+        );
+
+        let v = Horizontal {
+            azimuth: 1.0,
+            altitude: 0.2,
+        };
+        let back = Horizontal::from_vector(v.to_vector());
+        assert!((back.azimuth - 1.0).abs() < 1e-12 && (back.altitude - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn precession_matches_meeus() {
+        // Meeus, example 21.b: θ Persei to 2028 Nov 13.19 TD.
+        let start = Spherical::new(41.054_063_f64.to_radians(), 49.227_750_f64.to_radians());
+        let when = JulianDate(2_462_088.69);
+        let precessed = Spherical::from_vector(convert(
+            start.to_vector(),
+            &Frame::Icrs,
+            &Frame::EquatorialOfDate(when),
+        ));
+        assert_degrees(precessed.lon, 41.547_214, 1e-5);
+        assert_degrees(precessed.lat, 49.348_483, 1e-5);
+    }
+
+    #[test]
+    fn ecliptic_of_date_drifts_with_general_precession() {
+        // Fixed stars gain ~50.29″ of ecliptic longitude per year.
+        let star = Spherical::new(1.0, 0.3).to_vector();
+        let when = JulianDate(J2000.0 + 36_525.0);
+        let j2000 = Spherical::from_vector(convert(star, &Frame::Icrs, &Frame::EclipticJ2000));
+        let of_date =
+            Spherical::from_vector(convert(star, &Frame::Icrs, &Frame::EclipticOfDate(when)));
+        let drift = (of_date.lon - j2000.lon) / ARCSEC;
+        assert!((drift - 5029.0).abs() < 20.0, "drift {drift}″");
+        assert!(
+            (mean_obliquity(when).to_degrees() - 23.426_0).abs() < 1e-3,
+            "obliquity of 2100"
+        );
+    }
+
+    #[test]
+    fn galactic_reference_points() {
+        let to_galactic = |ra: f64, dec: f64| {
+            Spherical::from_vector(convert(
+                Spherical::new(ra.to_radians(), dec.to_radians()).to_vector(),
+                &Frame::Icrs,
+                &Frame::Galactic,
+            ))
+        };
+        let centre = to_galactic(266.404_99, -28.936_17);
+        assert_degrees(centre.lon, 0.0, 1e-3);
+        assert_degrees(centre.lat, 0.0, 1e-3);
+        let pole = to_galactic(192.859_48, 27.128_25);
+        assert_degrees(pole.lat, 90.0, 1e-3);
+    }
+
+    #[test]
+    fn round_trips_through_every_frame() {
+        let when = JulianDate(J2000.0 + 9_000.25);
+        let frames = [
+            Frame::Icrs,
+            Frame::EquatorialOfDate(when),
+            Frame::EclipticJ2000,
+            Frame::EclipticOfDate(when),
+            Frame::Galactic,
+            Frame::Horizontal {
+                observer: Observer::new(-0.6, 2.5),
+                date: when,
+            },
+        ];
+        let v = Spherical::new(4.0, -0.7).to_vector();
+        for from in &frames {
+            for to in &frames {
+                let there = convert(v, from, to);
+                assert!((there.length() - 1.0).abs() < 1e-12);
+                assert!((convert(there, to, from) - v).length() < 1e-12);
+            }
+        }
+    }
+}
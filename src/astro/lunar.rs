@@ -0,0 +1,567 @@
+//! # Lunar Theory
+//!
+//! Geocentric position of the Moon from the abridged ELP-2000/82 series of
+//! Meeus, *Astronomical Algorithms*, chapter 47: the main periodic terms in
+//! longitude, latitude and distance as sums over the Delaunay arguments `D`,
+//! `M`, `M'` and `F`, good to about 10″ in longitude and 4″ in latitude.
+//! Also the phase (chapter 48) against a low-precision Sun (chapter 25) and
+//! the optical libration (chapter 53). TT is used for TDB.
+
+use std::f64::consts::TAU;
+
+use bevy::math::DVec3;
+use serde::{Deserialize, Serialize};
+
+use super::JulianDate;
+use super::frames::{Frame, Spherical, convert};
+use crate::constants::ASTRONOMICAL_UNIT;
+
+/// Mean Earth–Moon distance the distance terms are added to (m).
+const MEAN_DISTANCE: f64 = 385_000_560.0;
+
+/// Inclination of the mean lunar equator to the ecliptic (Meeus ch. 53).
+const EQUATOR_INCLINATION: f64 = 1.542_42;
+
+/// Half-width of the central difference used for velocities (s).
+const VELOCITY_STEP: f64 = 600.0;
+
+/// Elongation either side of a new, quarter or full moon still given that
+/// name: half a day of the Moon's mean synodic motion (radians).
+const PRINCIPAL_PHASE_HALF_WIDTH: f64 = 0.106;
+
+/// Multiples of `D`, `M`, `M'`, `F` with the sine coefficient of longitude
+/// (10⁻⁶ degree) and the cosine coefficient of distance (m). Table 47.A.
+const LONGITUDE_DISTANCE_TERMS: [([i8; 4], i32, i32); 60] = [
+    ([0, 0, 1, 0], 6288774, -20905355),
+    ([2, 0, -1, 0], 1274027, -3699111),
+    ([2, 0, 0, 0], 658314, -2955968),
+    ([0, 0, 2, 0], 213618, -569925),
+    ([0, 1, 0, 0], -185116, 48888),
+    ([0, 0, 0, 2], -114332, -3149),
+    ([2, 0, -2, 0], 58793, 246158),
+    ([2, -1, -1, 0], 57066, -152138),
+    ([2, 0, 1, 0], 53322, -170733),
+    ([2, -1, 0, 0], 45758, -204586),
+    ([0, 1, -1, 0], -40923, -129620),
+    ([1, 0, 0, 0], -34720, 108743),
+    ([0, 1, 1, 0], -30383, 104755),
+    ([2, 0, 0, -2], 15327, 10321),
+    ([0, 0, 1, 2], -12528, 0),
+    ([0, 0, 1, -2], 10980, 79661),
+    ([4, 0, -1, 0], 10675, -34782),
+    ([0, 0, 3, 0], 10034, -23210),
+    ([4, 0, -2, 0], 8548, -21636),
+    ([2, 1, -1, 0], -7888, 24208),
+    ([2, 1, 0, 0], -6766, 30824),
+    ([1, 0, -1, 0], -5163, -8379),
+    ([1, 1, 0, 0], 4987, -16675),
+    ([2, -1, 1, 0], 4036, -12831),
+    ([2, 0, 2, 0], 3994, -10445),
+    ([4, 0, 0, 0], 3861, -11650),
+    ([2, 0, -3, 0], 3665, 14403),
+    ([0, 1, -2, 0], -2689, -7003),
+    ([2, 0, -1, 2], -2602, 0),
+    ([2, -1, -2, 0], 2390, 10056),
+    ([1, 0, 1, 0], -2348, 6322),
+    ([2, -2, 0, 0], 2236, -9884),
+    ([0, 1, 2, 0], -2120, 5751),
+    ([0, 2, 0, 0], -2069, 0),
+    ([2, -2, -1, 0], 2048, -4950),
+    ([2, 0, 1, -2], -1773, 4130),
+    ([2, 0, 0, 2], -1595, 0),
+    ([4, -1, -1, 0], 1215, -3958),
+    ([0, 0, 2, 2], -1110, 0),
+    ([3, 0, -1, 0], -892, 3258),
+    ([2, 1, 1, 0], -810, 2616),
+    ([4, -1, -2, 0], 759, -1897),
+    ([0, 2, -1, 0], -713, -2117),
+    ([2, 2, -1, 0], -700, 2354),
+    ([2, 1, -2, 0], 691, 0),
+    ([2, -1, 0, -2], 596, 0),
+    ([4, 0, 1, 0], 549, -1423),
+    ([0, 0, 4, 0], 537, -1117),
+    ([4, -1, 0, 0], 520, -1571),
+    ([1, 0, -2, 0], -487, -1739),
+    ([2, 1, 0, -2], -399, 0),
+    ([0, 0, 2, -2], -381, -4421),
+    ([1, 1, 1, 0], 351, 0),
+    ([3, 0, -2, 0], -340, 0),
+    ([4, 0, -3, 0], 330, 0),
+    ([2, -1, 2, 0], 327, 0),
+    ([0, 2, 1, 0], -323, 1165),
+    ([1, 1, -1, 0], 299, 0),
+    ([2, 0, 3, 0], 294, 0),
+    ([2, 0, -1, -2], 0, 8752),
+];
+
+/// Multiples of `D`, `M`, `M'`, `F` with the sine coefficient of latitude
+/// (10⁻⁶ degree). Table 47.B.
+const LATITUDE_TERMS: [([i8; 4], i32); 60] = [
+    ([0, 0, 0, 1], 5128122),
+    ([0, 0, 1, 1], 280602),
+    ([0, 0, 1, -1], 277693),
+    ([2, 0, 0, -1], 173237),
+    ([2, 0, -1, 1], 55413),
+    ([2, 0, -1, -1], 46271),
+    ([2, 0, 0, 1], 32573),
+    ([0, 0, 2, 1], 17198),
+    ([2, 0, 1, -1], 9266),
+    ([0, 0, 2, -1], 8822),
+    ([2, -1, 0, -1], 8216),
+    ([2, 0, -2, -1], 4324),
+    ([2, 0, 1, 1], 4200),
+    ([2, 1, 0, -1], -3359),
+    ([2, -1, -1, 1], 2463),
+    ([2, -1, 0, 1], 2211),
+    ([2, -1, -1, -1], 2065),
+    ([0, 1, -1, -1], -1870),
+    ([4, 0, -1, -1], 1828),
+    ([0, 1, 0, 1], -1794),
+    ([0, 0, 0, 3], -1749),
+    ([0, 1, -1, 1], -1565),
+    ([1, 0, 0, 1], -1491),
+    ([0, 1, 1, 1], -1475),
+    ([0, 1, 1, -1], -1410),
+    ([0, 1, 0, -1], -1344),
+    ([1, 0, 0, -1], -1335),
+    ([0, 0, 3, 1], 1107),
+    ([4, 0, 0, -1], 1021),
+    ([4, 0, -1, 1], 833),
+    ([0, 0, 1, -3], 777),
+    ([4, 0, -2, 1], 671),
+    ([2, 0, 0, -3], 607),
+    ([2, 0, 2, -1], 596),
+    ([2, -1, 1, -1], 491),
+    ([2, 0, -2, 1], -451),
+    ([0, 0, 3, -1], 439),
+    ([2, 0, 2, 1], 422),
+    ([2, 0, -3, -1], 421),
+    ([2, 1, -1, 1], -366),
+    ([2, 1, 0, 1], -351),
+    ([4, 0, 0, 1], 331),
+    ([2, -1, 1, 1], 315),
+    ([2, -2, 0, -1], 302),
+    ([0, 0, 1, 3], -283),
+    ([2, 1, 1, -1], -229),
+    ([1, 1, 0, -1], 223),
+    ([1, 1, 0, 1], 223),
+    ([0, 1, -2, -1], -220),
+    ([2, 1, -1, -1], -220),
+    ([1, 0, 1, 1], -185),
+    ([2, -1, -2, -1], 181),
+    ([0, 1, 2, 1], -177),
+    ([4, 0, -2, -1], 176),
+    ([4, -1, -1, -1], 166),
+    ([1, 0, 1, -1], -164),
+    ([4, 0, 1, -1], 132),
+    ([1, 0, -1, -1], -119),
+    ([4, -1, 0, -1], 115),
+    ([2, -2, 0, 1], 107),
+];
+
+/// Fundamental arguments of the theory at one date, in radians.
+struct Arguments {
+    /// Mean longitude of the Moon `L'`.
+    mean_longitude: f64,
+    /// Multiples-table arguments `D`, `M`, `M'`, `F`.
+    delaunay: [f64; 4],
+    /// Eccentricity factor `E` of the Earth's orbit.
+    eccentricity: f64,
+    /// Planetary arguments `A1` (Venus), `A2` (Jupiter) and `A3`.
+    planetary: [f64; 3],
+    /// Mean longitude of the ascending node `Ω`.
+    node: f64,
+}
+
+impl Arguments {
+    fn at(date: JulianDate) -> Self {
+        let t = date.centuries_since_j2000();
+        let angle = |coefficients: [f64; 5]| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |acc, c| acc * t + c)
+                .to_radians()
+        };
+        Self {
+            mean_longitude: angle([
+                218.316_447_7,
+                481_267.881_234_21,
+                -0.001_578_6,
+                1.0 / 538_841.0,
+                -1.0 / 65_194_000.0,
+            ]),
+            delaunay: [
+                angle([
+                    297.850_192_1,
+                    445_267.111_403_4,
+                    -0.001_881_9,
+                    1.0 / 545_868.0,
+                    -1.0 / 113_065_000.0,
+                ]),
+                angle([
+                    357.529_109_2,
+                    35_999.050_290_9,
+                    -0.000_153_6,
+                    1.0 / 24_490_000.0,
+                    0.0,
+                ]),
+                angle([
+                    134.963_396_4,
+                    477_198.867_505_5,
+                    0.008_741_4,
+                    1.0 / 69_699.0,
+                    -1.0 / 14_712_000.0,
+                ]),
+                angle([
+                    93.272_095_0,
+                    483_202.017_523_3,
+                    -0.003_653_9,
+                    -1.0 / 3_526_000.0,
+                    1.0 / 863_310_000.0,
+                ]),
+            ],
+            eccentricity: 1.0 - 0.002_516 * t - 0.000_007_4 * t * t,
+            planetary: [
+                angle([119.75, 131.849, 0.0, 0.0, 0.0]),
+                angle([53.09, 479_264.290, 0.0, 0.0, 0.0]),
+                angle([313.45, 481_266.484, 0.0, 0.0, 0.0]),
+            ],
+            node: angle([
+                125.044_547_9,
+                -1_934.136_289_1,
+                0.002_075_4,
+                1.0 / 467_441.0,
+                -1.0 / 60_616_000.0,
+            ]),
+        }
+    }
+
+    /// Argument of a table row and its `E^|M|` damping factor.
+    fn term(&self, multiples: [i8; 4]) -> (f64, f64) {
+        let argument = multiples
+            .iter()
+            .zip(self.delaunay)
+            .map(|(&n, angle)| f64::from(n) * angle)
+            .sum();
+        (
+            argument,
+            self.eccentricity.powi(i32::from(multiples[1].abs())),
+        )
+    }
+}
+
+/// Geocentric ecliptic longitude and latitude, and distance in metres,
+/// referred to the mean ecliptic and equinox of `date` (TT).
+pub fn spherical(date: JulianDate) -> (Spherical, f64) {
+    let args = Arguments::at(date);
+    let [a1, a2, a3] = args.planetary;
+    let l = args.mean_longitude;
+    let f = args.delaunay[3];
+    let m_prime = args.delaunay[2];
+
+    let (mut longitude, mut distance) = (0.0, 0.0);
+    for &(multiples, sine, cosine) in &LONGITUDE_DISTANCE_TERMS {
+        let (argument, factor) = args.term(multiples);
+        longitude += factor * f64::from(sine) * argument.sin();
+        distance += factor * f64::from(cosine) * argument.cos();
+    }
+    longitude += 3958.0 * a1.sin() + 1962.0 * (l - f).sin() + 318.0 * a2.sin();
+
+    let mut latitude = 0.0;
+    for &(multiples, sine) in &LATITUDE_TERMS {
+        let (argument, factor) = args.term(multiples);
+        latitude += factor * f64::from(sine) * argument.sin();
+    }
+    latitude += -2235.0 * l.sin()
+        + 382.0 * a3.sin()
+        + 175.0 * (a1 - f).sin()
+        + 175.0 * (a1 + f).sin()
+        + 127.0 * (l - m_prime).sin()
+        - 115.0 * (l + m_prime).sin();
+
+    (
+        Spherical::new(
+            (l + (longitude * 1e-6).to_radians()).rem_euclid(TAU),
+            (latitude * 1e-6).to_radians(),
+        ),
+        MEAN_DISTANCE + distance,
+    )
+}
+
+/// Geocentric position (m) in the ecliptic J2000 (scene) frame.
+pub fn position(date: JulianDate) -> DVec3 {
+    let (direction, distance) = spherical(date);
+    convert(
+        direction.to_vector() * distance,
+        &Frame::EclipticOfDate(date),
+        &Frame::EclipticJ2000,
+    )
+}
+
+/// Geocentric position (m) and velocity (m/s) at `epoch`, TT seconds since
+/// J2000.0 as kept by `SimClock`. The velocity is a central difference.
+pub fn state(epoch: f64) -> (DVec3, DVec3) {
+    let at = |seconds: f64| position(JulianDate::from_j2000_seconds(seconds));
+    let velocity = (at(epoch + VELOCITY_STEP) - at(epoch - VELOCITY_STEP)) / (2.0 * VELOCITY_STEP);
+    (at(epoch), velocity)
+}
+
+/// Geocentric position (m) of the Sun in the ecliptic J2000 frame, from the
+/// low-precision solar theory of Meeus chapter 25 (about 0.01°).
+pub fn sun_position(date: JulianDate) -> DVec3 {
+    let t = date.centuries_since_j2000();
+    let mean_longitude = 280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t * t;
+    let mean_anomaly = (357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t * t).to_radians();
+    let eccentricity = 0.016_708_634 - 0.000_042_037 * t - 0.000_000_126_7 * t * t;
+    let centre = (1.914_602 - 0.004_817 * t - 0.000_014 * t * t) * mean_anomaly.sin()
+        + (0.019_993 - 0.000_101 * t) * (2.0 * mean_anomaly).sin()
+        + 0.000_289 * (3.0 * mean_anomaly).sin();
+
+    let true_anomaly = mean_anomaly + centre.to_radians();
+    let distance = 1.000_001_018 * (1.0 - eccentricity * eccentricity)
+        / (1.0 + eccentricity * true_anomaly.cos());
+    let direction = Spherical::new((mean_longitude + centre).to_radians().rem_euclid(TAU), 0.0);
+    convert(
+        direction.to_vector() * distance * ASTRONOMICAL_UNIT,
+        &Frame::EclipticOfDate(date),
+        &Frame::EclipticJ2000,
+    )
+}
+
+/// Optical libration: the selenographic longitude and latitude (radians) of
+/// the point at the centre of the Moon's disc as seen from the Earth.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Libration {
+    /// Positive when the western limb (Mare Crisium side) is tipped towards
+    /// the Earth.
+    pub longitude: f64,
+    /// Positive when the northern limb is tipped towards the Earth.
+    pub latitude: f64,
+}
+
+/// Optical libration in longitude and latitude at `date` (Meeus ch. 53).
+pub fn libration(date: JulianDate) -> Libration {
+    let args = Arguments::at(date);
+    let (moon, _) = spherical(date);
+    let (sin_i, cos_i) = EQUATOR_INCLINATION.to_radians().sin_cos();
+    let (sin_w, cos_w) = (moon.lon - args.node).sin_cos();
+    let (sin_b, cos_b) = moon.lat.sin_cos();
+
+    let a = (sin_w * cos_b * cos_i - sin_b * sin_i).atan2(cos_w * cos_b);
+    let longitude = (a - args.delaunay[3] + TAU / 2.0).rem_euclid(TAU) - TAU / 2.0;
+    let latitude = (-sin_w * cos_b * sin_i - sin_b * cos_i).asin();
+    Libration {
+        longitude,
+        latitude,
+    }
+}
+
+/// One of the eight named phases of the lunar month.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseName {
+    #[default]
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl PhaseName {
+    /// Every phase in order of increasing elongation.
+    pub const ALL: [Self; 8] = [
+        Self::New,
+        Self::WaxingCrescent,
+        Self::FirstQuarter,
+        Self::WaxingGibbous,
+        Self::Full,
+        Self::WaningGibbous,
+        Self::LastQuarter,
+        Self::WaningCrescent,
+    ];
+
+    /// Phase at `elongation`, the Moon's ecliptic longitude minus the Sun's
+    /// (radians). New, quarter and full moons span the day around their
+    /// instant; the crescents and gibbous phases fill the rest.
+    pub fn from_elongation(elongation: f64) -> Self {
+        let quarters = elongation.rem_euclid(TAU) / (TAU / 4.0);
+        let nearest = quarters.round();
+        let octant = if (quarters - nearest).abs() * (TAU / 4.0) < PRINCIPAL_PHASE_HALF_WIDTH {
+            2 * nearest as usize
+        } else {
+            2 * quarters.floor() as usize + 1
+        };
+        Self::ALL[octant % 8]
+    }
+
+    /// Fluent key of the phase's display name.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::New => "moon-phase-new",
+            Self::WaxingCrescent => "moon-phase-waxing-crescent",
+            Self::FirstQuarter => "moon-phase-first-quarter",
+            Self::WaxingGibbous => "moon-phase-waxing-gibbous",
+            Self::Full => "moon-phase-full",
+            Self::WaningGibbous => "moon-phase-waning-gibbous",
+            Self::LastQuarter => "moon-phase-last-quarter",
+            Self::WaningCrescent => "moon-phase-waning-crescent",
+        }
+    }
+}
+
+/// Illumination of the Moon as seen from the Earth.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Phase {
+    /// Sun–Moon–Earth angle `i` (radians): 0 at full, π at new.
+    pub phase_angle: f64,
+    /// Illuminated fraction `k = (1 + cos i) / 2` of the disc.
+    pub illuminated_fraction: f64,
+    /// Moon's ecliptic longitude minus the Sun's, in `[0, 2π)`.
+    pub elongation: f64,
+}
+
+impl Phase {
+    /// Phase from geocentric positions of the Moon and Sun in the same
+    /// ecliptic frame.
+    pub fn from_positions(moon: DVec3, sun: DVec3) -> Self {
+        let phase_angle = (sun - moon).angle_between(-moon);
+        let elongation =
+            (Spherical::from_vector(moon).lon - Spherical::from_vector(sun).lon).rem_euclid(TAU);
+        Self {
+            phase_angle,
+            illuminated_fraction: (1.0 + phase_angle.cos()) / 2.0,
+            elongation,
+        }
+    }
+
+    /// Whether the illuminated fraction is growing.
+    pub fn is_waxing(&self) -> bool {
+        self.elongation < TAU / 2.0
+    }
+
+    pub fn name(&self) -> PhaseName {
+        PhaseName::from_elongation(self.elongation)
+    }
+}
+
+/// Phase of the Moon at `date`.
+pub fn phase(date: JulianDate) -> Phase {
+    Phase::from_positions(position(date), sun_position(date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::CalendarDate;
+    use crate::astro::time::Calendar;
+
+    /// Meeus examples 47.a, 48.a and 53.a: 1992-04-12 0h TD.
+    const EXAMPLE: JulianDate = JulianDate(2_448_724.5);
+
+    fn date(text: &str) -> JulianDate {
+        JulianDate::from_calendar(&text.parse::<CalendarDate>().unwrap(), Calendar::Reform).unwrap()
+    }
+
+    fn assert_degrees(actual: f64, expected: f64, tolerance: f64) {
+        let diff = (actual.to_degrees() - expected + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(
+            diff.abs() < tolerance,
+            "{} != {expected} (±{tolerance})",
+            actual.to_degrees()
+        );
+    }
+
+    #[test]
+    fn position_matches_meeus_example_47a() {
+        // λ = 133.162655°, β = -3.229126°, Δ = 368409.7 km.
+        let (moon, distance) = spherical(EXAMPLE);
+        assert_degrees(moon.lon, 133.162_655, 1e-6);
+        assert_degrees(moon.lat, -3.229_126, 1e-6);
+        assert!((distance - 368_409_700.0).abs() < 100.0, "{distance} m");
+    }
+
+    #[test]
+    fn phase_matches_meeus_example_48a() {
+        // k = 0.6786 with i = 69.0756°; the low-precision Sun adds ~0.01°.
+        let phase = phase(EXAMPLE);
+        assert_degrees(phase.phase_angle, 69.0756, 0.02);
+        assert!((phase.illuminated_fraction - 0.6786).abs() < 2e-4);
+        assert!(phase.is_waxing());
+        assert_eq!(phase.name(), PhaseName::WaxingGibbous);
+    }
+
+    #[test]
+    fn libration_matches_meeus_example_53a() {
+        // l' = -1.206°, b' = +4.194°.
+        let libration = libration(EXAMPLE);
+        assert_degrees(libration.longitude, -1.206, 0.002);
+        assert_degrees(libration.latitude, 4.194, 0.002);
+    }
+
+    #[test]
+    fn phases_match_almanac_dates() {
+        // Almanac instants in UT, shifted to TT by ΔT (64 s in 2000, 69 s
+        // in 2022-2024). Elongations must be within 0.05° of the nominal
+        // 0°, 90°, 180° and 270°.
+        let cases = [
+            ("2000-01-06T18:15:04", PhaseName::New, 0.0),
+            ("2000-01-14T13:35:04", PhaseName::FirstQuarter, 90.0),
+            ("2000-01-21T04:41:04", PhaseName::Full, 180.0),
+            ("2022-11-08T11:03:09", PhaseName::Full, 180.0),
+            ("2024-04-02T03:16:09", PhaseName::LastQuarter, 270.0),
+            ("2024-04-08T18:22:09", PhaseName::New, 0.0),
+        ];
+        for (text, name, elongation) in cases {
+            let phase = phase(date(text));
+            assert_eq!(phase.name(), name, "{text}");
+            assert_degrees(phase.elongation, elongation, 0.05);
+            let expected = (1.0 - elongation.to_radians().cos()) / 2.0;
+            assert!(
+                (phase.illuminated_fraction - expected).abs() < 0.01,
+                "{text}: k = {}",
+                phase.illuminated_fraction
+            );
+        }
+    }
+
+    #[test]
+    fn eclipse_new_moon_covers_the_sun() {
+        // Total solar eclipse of 2024-04-08: geocentric separation at the
+        // almanac new moon is well under a degree.
+        let when = date("2024-04-08T18:22:09");
+        let separation = position(when).angle_between(sun_position(when));
+        assert!(
+            separation.to_degrees() < 0.5,
+            "{}°",
+            separation.to_degrees()
+        );
+    }
+
+    #[test]
+    fn state_velocity_is_an_orbital_speed() {
+        let (position, velocity) = state(0.0);
+        let distance = position.length();
+        assert!((356_000_000.0..407_000_000.0).contains(&distance));
+        // About 1 km/s, mostly perpendicular to the radius.
+        assert!((950.0..1_100.0).contains(&velocity.length()), "{velocity}");
+        assert!(position.normalize().dot(velocity).abs() < 100.0);
+    }
+
+    #[test]
+    fn phase_names_cover_the_month() {
+        for (octant, name) in PhaseName::ALL.into_iter().enumerate() {
+            assert_eq!(PhaseName::from_elongation(octant as f64 * TAU / 8.0), name);
+        }
+        assert_eq!(PhaseName::from_elongation(TAU - 0.01), PhaseName::New);
+        assert_eq!(PhaseName::from_elongation(0.2), PhaseName::WaxingCrescent);
+        assert_eq!(
+            PhaseName::from_elongation(TAU / 4.0 + 0.05),
+            PhaseName::FirstQuarter
+        );
+    }
+}
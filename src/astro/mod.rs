@@ -0,0 +1,22 @@
+//! # Astronomy Core
+//!
+//! Engine-independent astronomical algorithms: time scales, calendars,
+//! reference frames, star catalogs, sky cultures, planetary and lunar theory,
+//! rise/set almanacs and the reference data they need. Like `physics`,
+//! everything here is plain math that can be tested without an `App`.
+
+pub mod catalog;
+pub mod frames;
+pub mod lunar;
+pub mod riseset;
+pub mod skyculture;
+pub mod time;
+pub mod vsop87;
+
+pub use catalog::{Star, StarCatalog};
+pub use frames::{Frame, Horizontal, Observer, Spherical};
+pub use lunar::{Libration, Phase, PhaseName};
+pub use riseset::{Almanac, RiseSetError, RiseTransitSet, Target, Twilight, Visibility};
+pub use skyculture::SkyCulture;
+pub use time::{CalendarDate, JulianDate, LeapSecondTable, ModifiedJulianDate, TimeScale};
+pub use vsop87::{Ephemeris, Planet};
@@ -0,0 +1,519 @@
+//! # Rising, Transit and Setting
+//!
+//! Times at which a body crosses the horizon and the meridian for an
+//! observer, and the Sun's twilights. A rise or set is the instant the
+//! body's centre reaches its standard altitude `h0`: the horizon lowered by
+//! the standard 34′ of refraction and the body's semi-diameter, raised by
+//! its horizontal parallax (Meeus, *Astronomical Algorithms*, chapter 15).
+//! Twilights use the Sun's centre at a fixed depression instead.
+//!
+//! Rather than Meeus's three-day interpolation, the altitude is sampled over
+//! the 24 hours from a starting UT instant and each crossing is refined by
+//! bisection, which copes with the Moon and with high latitudes. Positions
+//! are geometric (no nutation or aberration), good to a few seconds of time.
+
+use std::f64::consts::PI;
+
+use bevy::math::DVec3;
+use thiserror::Error;
+
+use super::frames::{Frame, Spherical, convert, hour_angle};
+use super::{Ephemeris, JulianDate, LeapSecondTable, Observer, Planet, TimeScale, lunar};
+
+/// Standard refraction at the horizon, 34′ (radians).
+pub const STANDARD_REFRACTION: f64 = 34.0 / 60.0 * PI / 180.0;
+
+/// Mean radius of the Sun (m).
+const SUN_RADIUS: f64 = 6.957e8;
+
+/// Mean radius of the Moon (m).
+const MOON_RADIUS: f64 = 1.7374e6;
+
+/// Equatorial radius of the Earth, for horizontal parallax (m).
+const EARTH_RADIUS: f64 = 6.378_137e6;
+
+/// Altitude samples per day; crossings closer together are missed.
+const SAMPLES_PER_DAY: usize = 144;
+
+/// Bisection steps refining a crossing, to well under a second.
+const REFINE_STEPS: usize = 24;
+
+/// A target that cannot rise or set.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RiseSetError {
+    #[error("The Earth does not rise or set for an observer on it")]
+    ObserverPlanet,
+    #[error("No ephemeris theory is loaded for {0:?}")]
+    MissingTheory(Planet),
+}
+
+/// A body whose rising and setting can be computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Sun,
+    Moon,
+    /// A planet from the ephemeris, seen from the Earth.
+    Planet(Planet),
+    /// A fixed star at its ICRS right ascension and declination (radians).
+    Star(Spherical),
+}
+
+/// Sun depressions bounding the twilights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Twilight {
+    Civil,
+    Nautical,
+    Astronomical,
+}
+
+impl Twilight {
+    /// Altitude of the Sun's centre that begins and ends the twilight.
+    pub fn altitude(self) -> f64 {
+        let degrees: f64 = match self {
+            Twilight::Civil => -6.0,
+            Twilight::Nautical => -12.0,
+            Twilight::Astronomical => -18.0,
+        };
+        degrees.to_radians()
+    }
+}
+
+/// Whether a body crosses its standard altitude during the day searched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    /// At least one rise or set happens.
+    RisesAndSets,
+    /// Above the standard altitude all day (midnight Sun, or no true night
+    /// for a twilight).
+    Circumpolar,
+    /// Below the standard altitude all day (polar night, or darker than the
+    /// twilight all day).
+    NeverRises,
+}
+
+/// Events of one 24-hour window. Times are UT Julian dates.
+///
+/// `rise` or `set` can be missing even when the body `RisesAndSets`: the
+/// Moon skips one of each about once a month. For twilights, `rise` is
+/// dawn and `set` is dusk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiseTransitSet {
+    pub visibility: Visibility,
+    pub rise: Option<JulianDate>,
+    /// Upper culmination.
+    pub transit: Option<JulianDate>,
+    pub set: Option<JulianDate>,
+}
+
+/// Computes rise, transit, set and twilight times against the loaded
+/// ephemeris. Planets missing from it are never found above the horizon.
+pub struct Almanac<'a> {
+    ephemeris: &'a Ephemeris,
+    leap_seconds: &'a LeapSecondTable,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn new(ephemeris: &'a Ephemeris, leap_seconds: &'a LeapSecondTable) -> Self {
+        Self {
+            ephemeris,
+            leap_seconds,
+        }
+    }
+
+    /// Geocentric right ascension and declination of the mean equator of
+    /// date, and distance in metres (infinite for stars), at UT `ut`. `None`
+    /// for the Earth itself.
+    pub fn equatorial(&self, target: Target, ut: JulianDate) -> Option<(Spherical, f64)> {
+        let tt = self.leap_seconds.convert(ut, TimeScale::Utc, TimeScale::Tt);
+        let ecliptic = match target {
+            Target::Sun => self.sun(tt),
+            Target::Moon => lunar::position(tt),
+            Target::Planet(Planet::Earth) => return None,
+            Target::Planet(planet) => {
+                let epoch = tt.j2000_seconds();
+                self.ephemeris.heliocentric(planet, epoch)?
+                    - self.ephemeris.heliocentric(Planet::Earth, epoch)?
+            }
+            Target::Star(icrs) => {
+                let direction =
+                    convert(icrs.to_vector(), &Frame::Icrs, &Frame::EquatorialOfDate(tt));
+                return Some((Spherical::from_vector(direction), f64::INFINITY));
+            }
+        };
+        let equatorial = convert(
+            ecliptic,
+            &Frame::EclipticJ2000,
+            &Frame::EquatorialOfDate(tt),
+        );
+        Some((Spherical::from_vector(equatorial), equatorial.length()))
+    }
+
+    /// Altitude of the target's centre above the geocentric horizon
+    /// (radians), and its standard altitude `h0` at that moment.
+    pub fn altitude(
+        &self,
+        target: Target,
+        observer: &Observer,
+        ut: JulianDate,
+    ) -> Option<(f64, f64)> {
+        let (position, distance) = self.equatorial(target, ut)?;
+        let hour_angle = hour_angle(ut, observer.longitude, position.lon);
+        let sin_altitude = observer.latitude.sin() * position.lat.sin()
+            + observer.latitude.cos() * position.lat.cos() * hour_angle.cos();
+        Some((
+            sin_altitude.clamp(-1.0, 1.0).asin(),
+            standard_altitude(target, distance),
+        ))
+    }
+
+    /// Rise, transit and set of `target` in the 24 hours from UT `start`.
+    /// The observer stands on the Earth, so the Earth is rejected, as is a
+    /// planet whose theory, or the Earth's, is not loaded.
+    pub fn rise_transit_set(
+        &self,
+        target: Target,
+        observer: &Observer,
+        start: JulianDate,
+    ) -> Result<RiseTransitSet, RiseSetError> {
+        if target == Target::Planet(Planet::Earth) {
+            return Err(RiseSetError::ObserverPlanet);
+        }
+        if let Target::Planet(planet) = target {
+            for required in [planet, Planet::Earth] {
+                if self.ephemeris.theory(required).is_none() {
+                    return Err(RiseSetError::MissingTheory(required));
+                }
+            }
+        }
+        let height = |ut: JulianDate| {
+            self.altitude(target, observer, ut)
+                .map_or(-PI, |(altitude, h0)| altitude - h0)
+        };
+        let meridian = |ut: JulianDate| {
+            self.equatorial(target, ut).map_or(PI, |(position, _)| {
+                hour_angle(ut, observer.longitude, position.lon)
+            })
+        };
+        Ok(events(height, meridian, start))
+    }
+
+    /// Dawn and dusk of `twilight` in the 24 hours from UT `start`, with the
+    /// Sun's transit.
+    pub fn twilight(
+        &self,
+        twilight: Twilight,
+        observer: &Observer,
+        start: JulianDate,
+    ) -> RiseTransitSet {
+        let height = |ut: JulianDate| {
+            self.altitude(Target::Sun, observer, ut)
+                .map_or(-PI, |(altitude, _)| altitude - twilight.altitude())
+        };
+        let meridian = |ut: JulianDate| {
+            self.equatorial(Target::Sun, ut)
+                .map_or(PI, |(position, _)| {
+                    hour_angle(ut, observer.longitude, position.lon)
+                })
+        };
+        events(height, meridian, start)
+    }
+
+    /// Geocentric Sun in the ecliptic J2000 frame: the reversed VSOP87 Earth
+    /// when loaded, otherwise the low-precision solar theory.
+    fn sun(&self, tt: JulianDate) -> DVec3 {
+        self.ephemeris
+            .heliocentric(Planet::Earth, tt.j2000_seconds())
+            .map_or_else(|| lunar::sun_position(tt), |earth| -earth)
+    }
+}
+
+/// Standard altitude `h0` of a target's centre at `distance` metres.
+pub fn standard_altitude(target: Target, distance: f64) -> f64 {
+    let radius = match target {
+        Target::Sun => SUN_RADIUS,
+        Target::Moon => MOON_RADIUS,
+        Target::Planet(_) | Target::Star(_) => 0.0,
+    };
+    let semi_diameter = (radius / distance).asin();
+    let parallax = (EARTH_RADIUS / distance).asin();
+    parallax - semi_diameter - STANDARD_REFRACTION
+}
+
+/// Finds the crossings of `height` through zero (rise upwards, set
+/// downwards) and of the hour angle `meridian` through zero from east to
+/// west, in the day from `start`.
+fn events(
+    height: impl Fn(JulianDate) -> f64,
+    meridian: impl Fn(JulianDate) -> f64,
+    start: JulianDate,
+) -> RiseTransitSet {
+    let step = 1.0 / SAMPLES_PER_DAY as f64;
+    let at = |i: usize| JulianDate(start.0 + i as f64 * step);
+
+    let mut result = RiseTransitSet {
+        visibility: Visibility::NeverRises,
+        rise: None,
+        transit: None,
+        set: None,
+    };
+    let mut above = height(start) > 0.0;
+    let mut hour_angle = meridian(start);
+    let mut ever_above = above;
+
+    for i in 1..=SAMPLES_PER_DAY {
+        let (from, to) = (at(i - 1), at(i));
+
+        let now_above = height(to) > 0.0;
+        if now_above != above {
+            let crossing = refine(&height, from, to, above);
+            if now_above {
+                result.rise.get_or_insert(crossing);
+            } else {
+                result.set.get_or_insert(crossing);
+            }
+            result.visibility = Visibility::RisesAndSets;
+        }
+        above = now_above;
+        ever_above |= above;
+
+        // A negative-to-positive step that is not the wrap at ±π.
+        let next_hour_angle = meridian(to);
+        if hour_angle <= 0.0 && next_hour_angle > 0.0 && next_hour_angle - hour_angle < PI {
+            result
+                .transit
+                .get_or_insert_with(|| refine(&meridian, from, to, false));
+        }
+        hour_angle = next_hour_angle;
+    }
+
+    if result.visibility != Visibility::RisesAndSets && ever_above {
+        result.visibility = Visibility::Circumpolar;
+    }
+    result
+}
+
+/// Bisects the sign change of `f` between `from` and `to`; `positive_first`
+/// tells which side starts above zero.
+fn refine(
+    f: &impl Fn(JulianDate) -> f64,
+    mut from: JulianDate,
+    mut to: JulianDate,
+    positive_first: bool,
+) -> JulianDate {
+    for _ in 0..REFINE_STEPS {
+        let mid = JulianDate(0.5 * (from.0 + to.0));
+        if (f(mid) > 0.0) == positive_first {
+            from = mid;
+        } else {
+            to = mid;
+        }
+    }
+    JulianDate(0.5 * (from.0 + to.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+
+    use super::*;
+    use crate::astro::CalendarDate;
+    use crate::astro::time::Calendar;
+    use crate::astro::vsop87::{PlanetTheory, Truncation};
+
+    fn ephemeris() -> Ephemeris {
+        let theory = |planet, text| {
+            PlanetTheory::parse(planet, text, &Truncation::default()).expect("bundled file parses")
+        };
+        Ephemeris::from_theories(vec![
+            theory(
+                Planet::Earth,
+                include_str!("../../assets/ephemeris/VSOP87D.ear"),
+            ),
+            theory(
+                Planet::Venus,
+                include_str!("../../assets/ephemeris/VSOP87D.ven"),
+            ),
+        ])
+    }
+
+    fn leap_seconds() -> LeapSecondTable {
+        LeapSecondTable::parse(include_str!("../../assets/data/leap_seconds.ron")).unwrap()
+    }
+
+    fn almanac() -> Almanac<'static> {
+        static DATA: OnceLock<(Ephemeris, LeapSecondTable)> = OnceLock::new();
+        let (ephemeris, leap_seconds) = DATA.get_or_init(|| (ephemeris(), leap_seconds()));
+        Almanac::new(ephemeris, leap_seconds)
+    }
+
+    fn date(text: &str) -> JulianDate {
+        JulianDate::from_calendar(&text.parse::<CalendarDate>().unwrap(), Calendar::Reform).unwrap()
+    }
+
+    fn observer(latitude: f64, longitude: f64) -> Observer {
+        Observer::new(latitude.to_radians(), longitude.to_radians())
+    }
+
+    /// Asserts `actual` is within `minutes` of the UT time `expected`.
+    fn assert_time(actual: Option<JulianDate>, expected: &str, minutes: f64) {
+        let actual = actual.expect("event should happen");
+        let diff = (actual.0 - date(expected).0) * 1440.0;
+        assert!(diff.abs() < minutes, "{expected}: off by {diff:.2} min");
+    }
+
+    const LONDON: (f64, f64) = (51.5074, -0.1278);
+    const TROMSO: (f64, f64) = (69.6492, 18.9553);
+
+    #[test]
+    fn venus_matches_meeus_example_15a() {
+        // Boston, 1988-03-20: rising 12h25m, transit 19h41m, setting 2h55m UT.
+        let almanac = almanac();
+        let boston = observer(42.3333, -71.0833);
+        let events = almanac
+            .rise_transit_set(Target::Planet(Planet::Venus), &boston, date("1988-03-20"))
+            .unwrap();
+        assert_eq!(events.visibility, Visibility::RisesAndSets);
+        assert_time(events.rise, "1988-03-20T12:25", 1.0);
+        assert_time(events.transit, "1988-03-20T19:41", 1.0);
+        assert_time(events.set, "1988-03-20T02:55", 1.0);
+    }
+
+    #[test]
+    fn the_earth_is_rejected() {
+        let london = observer(LONDON.0, LONDON.1);
+        let events =
+            almanac().rise_transit_set(Target::Planet(Planet::Earth), &london, date("2021-01-01"));
+        assert_eq!(events, Err(RiseSetError::ObserverPlanet));
+    }
+
+    #[test]
+    fn planets_without_a_theory_are_rejected() {
+        let london = observer(LONDON.0, LONDON.1);
+        let events =
+            almanac().rise_transit_set(Target::Planet(Planet::Mars), &london, date("2021-01-01"));
+        assert_eq!(events, Err(RiseSetError::MissingTheory(Planet::Mars)));
+    }
+
+    #[test]
+    fn sun_matches_london_solstice_tables() {
+        let almanac = almanac();
+        let london = observer(LONDON.0, LONDON.1);
+
+        let summer = almanac
+            .rise_transit_set(Target::Sun, &london, date("2021-06-21"))
+            .unwrap();
+        assert_time(summer.rise, "2021-06-21T03:43", 1.5);
+        assert_time(summer.set, "2021-06-21T20:21", 1.5);
+        assert_time(summer.transit, "2021-06-21T12:02", 1.5);
+
+        let winter = almanac
+            .rise_transit_set(Target::Sun, &london, date("2021-12-21"))
+            .unwrap();
+        assert_time(winter.rise, "2021-12-21T08:04", 1.5);
+        assert_time(winter.set, "2021-12-21T15:54", 1.5);
+    }
+
+    #[test]
+    fn midnight_sun_and_polar_night() {
+        let almanac = almanac();
+        let tromso = observer(TROMSO.0, TROMSO.1);
+
+        let summer = almanac
+            .rise_transit_set(Target::Sun, &tromso, date("2021-06-21"))
+            .unwrap();
+        assert_eq!(summer.visibility, Visibility::Circumpolar);
+        assert_eq!((summer.rise, summer.set), (None, None));
+        assert!(summer.transit.is_some());
+
+        let winter = almanac
+            .rise_transit_set(Target::Sun, &tromso, date("2021-12-21"))
+            .unwrap();
+        assert_eq!(winter.visibility, Visibility::NeverRises);
+        assert_eq!((winter.rise, winter.set), (None, None));
+
+        // The Sun still climbs to about -3°, so the polar night has a
+        // civil twilight but no nautical daylight.
+        let civil = almanac.twilight(Twilight::Civil, &tromso, date("2021-12-21"));
+        assert_eq!(civil.visibility, Visibility::RisesAndSets);
+        assert!(civil.rise.is_some() && civil.set.is_some());
+    }
+
+    #[test]
+    fn twilights_nest_around_sunrise() {
+        let almanac = almanac();
+        let london = observer(LONDON.0, LONDON.1);
+        let day = date("2021-12-21");
+
+        let sun = almanac.rise_transit_set(Target::Sun, &london, day).unwrap();
+        let civil = almanac.twilight(Twilight::Civil, &london, day);
+        let nautical = almanac.twilight(Twilight::Nautical, &london, day);
+        let astronomical = almanac.twilight(Twilight::Astronomical, &london, day);
+        // Civil dawn 07:23, dusk 16:34 UT.
+        assert_time(civil.rise, "2021-12-21T07:23", 2.0);
+        assert_time(civil.set, "2021-12-21T16:34", 2.0);
+        let dawns = [astronomical.rise, nautical.rise, civil.rise, sun.rise];
+        assert!(
+            dawns
+                .windows(2)
+                .all(|pair| pair[0].unwrap().0 < pair[1].unwrap().0)
+        );
+
+        // No astronomical night in a London midsummer.
+        let midsummer = almanac.twilight(Twilight::Astronomical, &london, date("2021-06-21"));
+        assert_eq!(midsummer.visibility, Visibility::Circumpolar);
+    }
+
+    #[test]
+    fn stars_can_be_circumpolar_or_never_rise() {
+        let almanac = almanac();
+        let london = observer(LONDON.0, LONDON.1);
+        let star = |ra_hours: f64, dec: f64| {
+            Target::Star(Spherical::new(
+                (ra_hours * 15.0).to_radians(),
+                dec.to_radians(),
+            ))
+        };
+        let day = date("2021-01-01");
+
+        let polaris = almanac
+            .rise_transit_set(star(2.530, 89.264), &london, day)
+            .unwrap();
+        assert_eq!(polaris.visibility, Visibility::Circumpolar);
+        assert!(polaris.transit.is_some());
+        let canopus = almanac
+            .rise_transit_set(star(6.399, -52.696), &london, day)
+            .unwrap();
+        assert_eq!(canopus.visibility, Visibility::NeverRises);
+        let sirius = almanac
+            .rise_transit_set(star(6.752, -16.716), &london, day)
+            .unwrap();
+        assert_eq!(sirius.visibility, Visibility::RisesAndSets);
+        // A star returns 3m56s earlier each day.
+        let transit = |day| {
+            let events = almanac
+                .rise_transit_set(star(6.752, -16.716), &london, date(day))
+                .unwrap();
+            events.transit.unwrap().0
+        };
+        let gain = (transit("2021-03-01") + 1.0 - transit("2021-03-02")) * 86_400.0;
+        assert!((gain - 235.9).abs() < 1.0, "{gain} s");
+    }
+
+    #[test]
+    fn moon_rises_at_its_standard_altitude() {
+        let almanac = almanac();
+        let london = observer(LONDON.0, LONDON.1);
+        let events = almanac
+            .rise_transit_set(Target::Moon, &london, date("2024-04-15"))
+            .unwrap();
+        let rise = events.rise.expect("the Moon rises");
+        let (altitude, h0) = almanac.altitude(Target::Moon, &london, rise).unwrap();
+        assert!((altitude - h0).abs() < 1e-5);
+        // Parallax outweighs refraction and semi-diameter: h0 ≈ +0.13°.
+        assert!(
+            (0.05..0.25).contains(&h0.to_degrees()),
+            "{}",
+            h0.to_degrees()
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! # Sky Cultures
+//!
+//! Constellation figures and boundaries in Stellarium's plain-text layout.
+//! A culture is a directory holding:
+//!
+//! - `constellationship.fab`: one figure per line, `ABBR N hip hip …` with
+//!   `N` line segments given as `2N` Hipparcos numbers.
+//! - `constellation_boundaries.dat` (optional): one boundary edge per line,
+//!   `N ra dec … 2 ABBR ABBR` with `N` vertices in J2000 right ascension
+//!   (hours) and declination (degrees), followed by the two constellations
+//!   the edge separates.
+//!
+//! Blank lines and lines starting with `#` are ignored in both files.
+//! Display names are not stored here; they are Fluent keys built from the
+//! culture and abbreviation, see [`Constellation::name_key`].
+
+use std::collections::HashMap;
+use std::f64::consts::{PI, TAU};
+use std::path::{Path, PathBuf};
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use thiserror::Error;
+
+use super::StarCatalog;
+use super::frames::Spherical;
+
+/// File holding the stick figures of a culture.
+pub const FIGURES_FILE: &str = "constellationship.fab";
+
+/// File holding the boundaries of a culture.
+pub const BOUNDARIES_FILE: &str = "constellation_boundaries.dat";
+
+/// Failure to read a sky culture.
+#[derive(Debug, Error)]
+pub enum SkyCultureError {
+    #[error("Failed to read sky culture file {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Malformed sky culture data on line {line}: {reason}")]
+    Malformed { line: usize, reason: String },
+}
+
+/// A constellation figure as pairs of Hipparcos numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constellation {
+    /// Culture-specific identifier, e.g. `Ori`.
+    pub abbreviation: String,
+    pub segments: Vec<[u32; 2]>,
+}
+
+impl Constellation {
+    /// Fluent key of the localized name, e.g. `sky-western-ori`.
+    pub fn name_key(&self, culture: &str) -> String {
+        format!("sky-{}-{}", culture, self.abbreviation.to_lowercase())
+    }
+}
+
+/// One edge of a constellation boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryEdge {
+    /// Vertices as (right ascension, declination) in radians.
+    pub vertices: Vec<(f64, f64)>,
+    /// The two constellations on either side of the edge.
+    pub between: [String; 2],
+}
+
+impl BoundaryEdge {
+    /// The edge as equatorial unit vectors, subdivided so that consecutive
+    /// points are at most `max_step` radians apart in right ascension and
+    /// declination. IAU edges run along hour circles and parallels, which
+    /// straight chords between the vertices would cut across.
+    pub fn points(&self, max_step: f64) -> Vec<DVec3> {
+        let mut points = Vec::new();
+        for pair in self.vertices.windows(2) {
+            let (ra0, dec0) = pair[0];
+            // Take the short way round through 0h.
+            let d_ra = (pair[1].0 - ra0 + PI).rem_euclid(TAU) - PI;
+            let d_dec = pair[1].1 - dec0;
+            let steps = (d_ra.abs().max(d_dec.abs()) / max_step).ceil().max(1.0) as usize;
+            let start = usize::from(!points.is_empty());
+            for step in start..=steps {
+                let t = step as f64 / steps as f64;
+                points.push(Spherical::new(ra0 + d_ra * t, dec0 + d_dec * t).to_vector());
+            }
+        }
+        points
+    }
+}
+
+/// A figure resolved against a star catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstellationFigure {
+    pub abbreviation: String,
+    /// Fluent key of the localized name.
+    pub name_key: String,
+    /// Line segments as equatorial unit vectors.
+    pub lines: Vec<[DVec3; 2]>,
+    /// Where the name is drawn: the mean direction of the figure's stars.
+    pub label_direction: DVec3,
+}
+
+/// The active sky culture.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SkyCulture {
+    /// Directory name of the culture, e.g. `western`.
+    pub id: String,
+    pub constellations: Vec<Constellation>,
+    pub boundaries: Vec<BoundaryEdge>,
+}
+
+impl SkyCulture {
+    /// Loads the culture in `dir`, named after its last path component.
+    ///
+    /// The figures file is required; a missing boundaries file leaves the
+    /// culture without boundaries.
+    pub fn load(dir: &Path) -> Result<Self, SkyCultureError> {
+        let read = |path: PathBuf| {
+            std::fs::read_to_string(&path).map_err(|source| SkyCultureError::Io { path, source })
+        };
+
+        let constellations = parse_figures(&read(dir.join(FIGURES_FILE))?)?;
+        let boundaries_path = dir.join(BOUNDARIES_FILE);
+        let boundaries = if boundaries_path.exists() {
+            parse_boundaries(&read(boundaries_path)?)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            id: dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            constellations,
+            boundaries,
+        })
+    }
+
+    /// Resolves every figure against `catalog`.
+    ///
+    /// Segments naming a star the catalog lacks are dropped, and figures left
+    /// without segments are skipped. Returns the figures and the number of
+    /// dropped segments.
+    pub fn figures(&self, catalog: &StarCatalog) -> (Vec<ConstellationFigure>, usize) {
+        let by_hip: HashMap<u32, DVec3> = catalog
+            .stars
+            .iter()
+            .filter_map(|star| Some((star.hip?, star.direction())))
+            .collect();
+
+        let mut dropped = 0;
+        let mut figures = Vec::new();
+        for constellation in &self.constellations {
+            let mut lines = Vec::new();
+            let mut sum = DVec3::ZERO;
+            for [a, b] in &constellation.segments {
+                match (by_hip.get(a), by_hip.get(b)) {
+                    (Some(&a), Some(&b)) => {
+                        sum += a + b;
+                        lines.push([a, b]);
+                    }
+                    _ => dropped += 1,
+                }
+            }
+            if lines.is_empty() {
+                continue;
+            }
+            figures.push(ConstellationFigure {
+                abbreviation: constellation.abbreviation.clone(),
+                name_key: constellation.name_key(&self.id),
+                lines,
+                label_direction: sum.try_normalize().unwrap_or(DVec3::Z),
+            });
+        }
+        (figures, dropped)
+    }
+}
+
+/// Parses a `constellationship.fab` file.
+pub fn parse_figures(text: &str) -> Result<Vec<Constellation>, SkyCultureError> {
+    data_lines(text)
+        .map(|(line, fields)| {
+            let malformed = |reason: &str| SkyCultureError::Malformed {
+                line,
+                reason: reason.to_string(),
+            };
+            let (abbreviation, rest) = fields
+                .split_first()
+                .ok_or_else(|| malformed("missing abbreviation"))?;
+            let (count, ids) = rest
+                .split_first()
+                .ok_or_else(|| malformed("missing segment count"))?;
+            let count: usize = count
+                .parse()
+                .map_err(|_| malformed("segment count is not a number"))?;
+            if ids.len() != count * 2 {
+                return Err(malformed(&format!(
+                    "expected {} star numbers, found {}",
+                    count * 2,
+                    ids.len()
+                )));
+            }
+            let ids = ids
+                .iter()
+                .map(|id| id.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| malformed("star number is not a Hipparcos number"))?;
+
+            Ok(Constellation {
+                abbreviation: abbreviation.to_string(),
+                segments: ids.chunks_exact(2).map(|pair| [pair[0], pair[1]]).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parses a `constellation_boundaries.dat` file.
+pub fn parse_boundaries(text: &str) -> Result<Vec<BoundaryEdge>, SkyCultureError> {
+    data_lines(text)
+        .map(|(line, fields)| {
+            let malformed = |reason: &str| SkyCultureError::Malformed {
+                line,
+                reason: reason.to_string(),
+            };
+            let count: usize = fields
+                .first()
+                .and_then(|count| count.parse().ok())
+                .ok_or_else(|| malformed("missing vertex count"))?;
+            // Count, 2 numbers per vertex, then "2 ABBR ABBR".
+            if count < 2 {
+                return Err(malformed("an edge needs at least two vertices"));
+            }
+            if fields.len() != 1 + count * 2 + 3 || fields[1 + count * 2] != "2" {
+                return Err(malformed(&format!(
+                    "expected {count} vertices followed by two constellations"
+                )));
+            }
+            let numbers = fields[1..=count * 2]
+                .iter()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| malformed("vertex coordinate is not a number"))?;
+
+            Ok(BoundaryEdge {
+                vertices: numbers
+                    .chunks_exact(2)
+                    .map(|pair| ((pair[0] * 15.0).to_radians(), pair[1].to_radians()))
+                    .collect(),
+                between: [fields[count * 2 + 2], fields[count * 2 + 3]].map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Non-comment lines split on whitespace, with 1-based line numbers.
+fn data_lines(text: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, text)| (line, text.split_whitespace().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HYG: &str = include_str!("../../tests/fixtures/hyg_sample.csv");
+    const WESTERN: &str = include_str!("../../assets/skycultures/western/constellationship.fab");
+    const CHINESE: &str = include_str!("../../assets/skycultures/chinese/constellationship.fab");
+    const BOUNDARIES: &str =
+        include_str!("../../assets/skycultures/western/constellation_boundaries.dat");
+
+    #[test]
+    fn parses_figures() {
+        let figures = parse_figures("# comment\n\nCMa 2 32349 91262  91262 11767\nLyr 0\n")
+            .expect("figures should parse");
+        assert_eq!(figures.len(), 2);
+        assert_eq!(figures[0].abbreviation, "CMa");
+        assert_eq!(figures[0].segments, vec![[32349, 91262], [91262, 11767]]);
+        assert!(figures[1].segments.is_empty());
+        assert_eq!(figures[0].name_key("western"), "sky-western-cma");
+    }
+
+    #[test]
+    fn rejects_malformed_figures() {
+        let result = parse_figures("Ori 2 27989 26727 26727\n");
+        assert!(matches!(
+            result,
+            Err(SkyCultureError::Malformed { line: 1, .. })
+        ));
+        assert!(parse_figures("Ori 1 27989 x\n").is_err());
+        assert!(parse_figures("Ori\n").is_err());
+    }
+
+    #[test]
+    fn parses_boundaries() {
+        let edges = parse_boundaries("3 6.0 0.0 6.0 10.0 5.0 10.0 2 ORI GEM\n").unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].vertices.len(), 3);
+        assert_eq!(edges[0].between, ["ORI".to_string(), "GEM".to_string()]);
+        assert!((edges[0].vertices[0].0 - 90f64.to_radians()).abs() < 1e-12);
+        let points = edges[0].points(1f64.to_radians());
+        assert!((points[0] - DVec3::Y).length() < 1e-12);
+        // 10° up the hour circle, then 15° along the parallel.
+        assert_eq!(points.len(), 1 + 10 + 15);
+
+        let wrap = parse_boundaries("2 23.9 0.0 0.1 0.0 2 PSC PEG\n").unwrap();
+        assert_eq!(wrap[0].points(2f64.to_radians()).len(), 3);
+
+        assert!(parse_boundaries("2 6.0 0.0 6.0 2 ORI GEM\n").is_err());
+        assert!(parse_boundaries("1 6.0 0.0 2 ORI GEM\n").is_err());
+    }
+
+    #[test]
+    fn figures_resolve_against_the_catalog() {
+        let catalog = StarCatalog::parse(HYG.as_bytes()).unwrap();
+        let culture = SkyCulture {
+            id: "western".to_string(),
+            constellations: parse_figures("Test 2 32349 91262 91262 1\nNone 1 1 2\n").unwrap(),
+            boundaries: Vec::new(),
+        };
+
+        let (figures, dropped) = culture.figures(&catalog);
+        assert_eq!(dropped, 2);
+        assert_eq!(figures.len(), 1);
+        assert_eq!(figures[0].name_key, "sky-western-test");
+        assert_eq!(figures[0].lines.len(), 1);
+        assert!(figures[0].label_direction.is_normalized());
+    }
+
+    #[test]
+    fn bundled_cultures_parse_and_are_localized() {
+        let edges = parse_boundaries(BOUNDARIES).unwrap();
+        let constellations: std::collections::HashSet<_> =
+            edges.iter().flat_map(|edge| &edge.between).collect();
+        assert_eq!(constellations.len(), 88);
+        let figures: std::collections::HashSet<_> = parse_figures(WESTERN)
+            .unwrap()
+            .into_iter()
+            .map(|figure| figure.abbreviation.to_uppercase())
+            .collect();
+        assert_eq!(figures, constellations.into_iter().cloned().collect());
+
+        for locale in ["en-US", "ru-RU"] {
+            let path = format!(
+                "{}/assets/locales/{locale}/text/sky.ftl",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let ftl = std::fs::read_to_string(&path).expect("sky.ftl should exist");
+            for (culture, text) in [("western", WESTERN), ("chinese", CHINESE)] {
+                let figures = parse_figures(text).unwrap();
+                assert!(!figures.is_empty());
+                for figure in figures {
+                    let key = figure.name_key(culture);
+                    assert!(
+                        ftl.lines()
+                            .any(|line| line.starts_with(&format!("{key} ="))),
+                        "{locale} is missing {key}"
+                    );
+                }
+                assert!(ftl.contains(&format!("sky-culture-{culture} =")));
+            }
+        }
+    }
+}
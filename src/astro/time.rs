@@ -0,0 +1,575 @@
+//! # Time Scales
+//!
+//! Julian dates, calendar conversion and the UTC/TAI/TT/TDB time scales.
+//!
+//! Calendar conversion follows Meeus, *Astronomical Algorithms*, ch. 7.
+//! TDB − TT uses the series from USNO Circular 179, eq. 2.6 (≈10 µs).
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::constants::SECONDS_PER_DAY;
+
+/// Julian date of J2000.0 (2000-01-01 12:00:00 TT).
+pub const J2000: JulianDate = JulianDate(2_451_545.0);
+
+/// JD − MJD.
+pub const MJD_OFFSET: f64 = 2_400_000.5;
+
+/// Julian date of the Unix epoch, 1970-01-01 00:00:00 UTC.
+pub const UNIX_EPOCH: JulianDate = JulianDate(2_440_587.5);
+
+/// First instant of the Gregorian calendar, 1582-10-15 00:00.
+pub const GREGORIAN_REFORM: JulianDate = JulianDate(2_299_160.5);
+
+/// TT − TAI in seconds.
+pub const TT_MINUS_TAI: f64 = 32.184;
+
+/// Days in a Julian century.
+pub const DAYS_PER_CENTURY: f64 = 36_525.0;
+
+/// Failure to parse a date or read the leap-second table.
+#[derive(Debug, Error)]
+pub enum TimeError {
+    #[error("Invalid date {0:?}, expected YYYY-MM-DD[THH:MM[:SS]]")]
+    InvalidDate(String),
+    #[error("Failed to read leap-second table {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to parse leap-second table: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Leap-second table entries must be in increasing date order")]
+    Unsorted,
+}
+
+/// A continuous day count. The time scale is implied by context.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct JulianDate(pub f64);
+
+/// JD − 2400000.5, so days start at midnight.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct ModifiedJulianDate(pub f64);
+
+impl From<JulianDate> for ModifiedJulianDate {
+    fn from(jd: JulianDate) -> Self {
+        Self(jd.0 - MJD_OFFSET)
+    }
+}
+
+impl From<ModifiedJulianDate> for JulianDate {
+    fn from(mjd: ModifiedJulianDate) -> Self {
+        Self(mjd.0 + MJD_OFFSET)
+    }
+}
+
+/// Calendar used to interpret or produce a `CalendarDate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Calendar {
+    /// Julian before 1582-10-15, Gregorian from then on.
+    #[default]
+    Reform,
+    /// Proleptic Gregorian for all dates.
+    Gregorian,
+    /// Proleptic Julian for all dates.
+    Julian,
+}
+
+impl JulianDate {
+    /// Builds a JD from seconds since J2000.0 in the same time scale.
+    pub fn from_j2000_seconds(seconds: f64) -> Self {
+        Self(J2000.0 + seconds / SECONDS_PER_DAY)
+    }
+
+    /// Seconds since J2000.0 in the same time scale.
+    pub fn j2000_seconds(self) -> f64 {
+        (self.0 - J2000.0) * SECONDS_PER_DAY
+    }
+
+    /// Days since J2000.0.
+    pub fn days_since_j2000(self) -> f64 {
+        self.0 - J2000.0
+    }
+
+    /// Julian centuries since J2000.0, the `T` of most series expansions.
+    pub fn centuries_since_j2000(self) -> f64 {
+        self.days_since_j2000() / DAYS_PER_CENTURY
+    }
+
+    /// Converts a Unix timestamp to a UTC Julian date.
+    pub fn from_unix_seconds(seconds: f64) -> Self {
+        Self(UNIX_EPOCH.0 + seconds / SECONDS_PER_DAY)
+    }
+
+    /// Converts a calendar date to a Julian date. Fails if the day does not
+    /// exist in `calendar`, including 1582-10-05 to 14 under the reform.
+    pub fn from_calendar(date: &CalendarDate, calendar: Calendar) -> Result<Self, TimeError> {
+        let day = (date.year, date.month, date.day);
+        let gregorian = match calendar {
+            Calendar::Gregorian => true,
+            Calendar::Julian => false,
+            Calendar::Reform => day >= (1582, 10, 15),
+        };
+        let skipped = calendar == Calendar::Reform && day > (1582, 10, 4) && !gregorian;
+        if !(1..=12).contains(&date.month)
+            || !(1..=days_in_month(date.year, date.month, gregorian)).contains(&date.day)
+            || skipped
+        {
+            return Err(TimeError::InvalidDate(date.to_string()));
+        }
+
+        let (mut year, mut month) = (f64::from(date.year), f64::from(date.month));
+        if month <= 2.0 {
+            year -= 1.0;
+            month += 12.0;
+        }
+        let b = if gregorian {
+            let a = (year / 100.0).floor();
+            2.0 - a + (a / 4.0).floor()
+        } else {
+            0.0
+        };
+        let day = f64::from(date.day) + date.seconds_of_day() / SECONDS_PER_DAY;
+
+        Ok(Self(
+            (365.25 * (year + 4716.0)).floor() + (30.6001 * (month + 1.0)).floor() + day + b
+                - 1524.5,
+        ))
+    }
+
+    /// Converts to a calendar date. Valid for JD ≥ 0.
+    pub fn to_calendar(self, calendar: Calendar) -> CalendarDate {
+        let shifted = self.0 + 0.5;
+        let z = shifted.floor();
+        let fraction = shifted - z;
+
+        let gregorian = match calendar {
+            Calendar::Gregorian => true,
+            Calendar::Julian => false,
+            Calendar::Reform => z >= GREGORIAN_REFORM.0 + 0.5,
+        };
+        let a = if gregorian {
+            let alpha = ((z - 1_867_216.25) / 36_524.25).floor();
+            z + 1.0 + alpha - (alpha / 4.0).floor()
+        } else {
+            z
+        };
+        let b = a + 1524.0;
+        let c = ((b - 122.1) / 365.25).floor();
+        let d = (365.25 * c).floor();
+        let e = ((b - d) / 30.6001).floor();
+
+        let day = (b - d - (30.6001 * e).floor()) as u32;
+        let month = if e < 14.0 { e - 1.0 } else { e - 13.0 } as u32;
+        let year = if month > 2 { c - 4716.0 } else { c - 4715.0 } as i32;
+
+        let mut seconds = fraction * SECONDS_PER_DAY;
+        let hour = (seconds / 3600.0).floor().min(23.0);
+        seconds -= hour * 3600.0;
+        let minute = (seconds / 60.0).floor().min(59.0);
+        seconds -= minute * 60.0;
+
+        CalendarDate {
+            year,
+            month,
+            day,
+            hour: hour as u32,
+            minute: minute as u32,
+            second: seconds,
+        }
+    }
+}
+
+/// A civil date and time of day. Years use astronomical numbering (1 BC = 0).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: f64,
+}
+
+impl CalendarDate {
+    /// Midnight at the start of the given day.
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0.0,
+        }
+    }
+
+    pub fn with_time(self, hour: u32, minute: u32, second: f64) -> Self {
+        Self {
+            hour,
+            minute,
+            second,
+            ..self
+        }
+    }
+
+    /// Seconds elapsed since midnight.
+    pub fn seconds_of_day(&self) -> f64 {
+        f64::from(self.hour * 3600 + self.minute * 60) + self.second
+    }
+}
+
+impl FromStr for CalendarDate {
+    type Err = TimeError;
+
+    /// Parses `YYYY-MM-DD[THH:MM[:SS]]`; seconds may be fractional and the
+    /// year may be negative.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || TimeError::InvalidDate(text.to_string());
+
+        let (day, time) = text.split_once('T').unwrap_or((text, "00:00"));
+        let (sign, day) = match day.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, day),
+        };
+        let mut parts = day.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let year = sign * next()?.parse::<i32>().map_err(|_| invalid())?;
+        let month = next()?.parse::<u32>().map_err(|_| invalid())?;
+        let day = next()?.parse::<u32>().map_err(|_| invalid())?;
+
+        let mut parts = time.splitn(3, ':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let hour = next()?.parse::<u32>().map_err(|_| invalid())?;
+        let minute = next()?.parse::<u32>().map_err(|_| invalid())?;
+        let second = match parts.next() {
+            Some(s) => s.parse::<f64>().map_err(|_| invalid())?,
+            None => 0.0,
+        };
+
+        // 60 s is allowed for leap seconds.
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || !(0.0..61.0).contains(&second)
+        {
+            return Err(invalid());
+        }
+        Ok(CalendarDate::new(year, month, day).with_time(hour, minute, second))
+    }
+}
+
+/// Days in `month` of `year`, with Gregorian or Julian leap years.
+fn days_in_month(year: i32, month: u32, gregorian: bool) -> u32 {
+    let leap = if gregorian {
+        year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+    } else {
+        year.rem_euclid(4) == 0
+    };
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:06.3}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Astronomical time scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, civil time with leap seconds.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, TAI + 32.184 s.
+    Tt,
+    /// Barycentric Dynamical Time, TT plus a periodic term below 2 ms.
+    Tdb,
+}
+
+/// TDB − TT in seconds at a TT (or TDB) Julian date.
+pub fn tdb_minus_tt(jd: JulianDate) -> f64 {
+    let t = jd.centuries_since_j2000();
+    0.001_657 * (628.3076 * t + 6.2401).sin()
+        + 0.000_022 * (575.3385 * t + 4.2970).sin()
+        + 0.000_014 * (1256.6152 * t + 6.1969).sin()
+        + 0.000_005 * (606.9777 * t + 4.0212).sin()
+        + 0.000_005 * (52.9691 * t + 0.4444).sin()
+        + 0.000_002 * (21.3299 * t + 5.5431).sin()
+        + 0.000_010 * t * (628.3076 * t + 4.2490).sin()
+}
+
+/// One row of the bundled leap-second file.
+#[derive(Deserialize, Debug, Clone)]
+struct LeapSecondEntry {
+    /// UTC date the offset takes effect, `YYYY-MM-DD`.
+    date: String,
+    /// TAI − UTC in seconds from that date on.
+    tai_minus_utc: f64,
+}
+
+/// TAI − UTC history, loaded from `assets/data/leap_seconds.ron`.
+///
+/// Dates before the first entry use its offset; UTC before 1972 had
+/// fractional rates that are not modelled.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LeapSecondTable {
+    /// `(UTC MJD the offset starts, TAI − UTC seconds)`, sorted by date.
+    entries: Vec<(f64, f64)>,
+}
+
+impl LeapSecondTable {
+    /// Parses the RON list of `(date, tai_minus_utc)` entries.
+    pub fn parse(text: &str) -> Result<Self, TimeError> {
+        let rows: Vec<LeapSecondEntry> = ron::from_str(text)?;
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let date = row.date.parse::<CalendarDate>()?;
+            let mjd = ModifiedJulianDate::from(JulianDate::from_calendar(&date, Calendar::Reform)?);
+            entries.push((mjd.0, row.tai_minus_utc));
+        }
+        if entries.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(TimeError::Unsorted);
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, TimeError> {
+        let text = std::fs::read_to_string(path).map_err(|source| TimeError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// TAI − UTC in seconds at a UTC Julian date. Zero for an empty table.
+    pub fn tai_minus_utc(&self, utc: JulianDate) -> f64 {
+        let mjd = ModifiedJulianDate::from(utc).0;
+        let index = self.entries.partition_point(|&(start, _)| start <= mjd);
+        match index {
+            0 => self.entries.first().map_or(0.0, |&(_, offset)| offset),
+            _ => self.entries[index - 1].1,
+        }
+    }
+
+    /// Re-expresses a Julian date from one time scale in another.
+    pub fn convert(&self, jd: JulianDate, from: TimeScale, to: TimeScale) -> JulianDate {
+        if from == to {
+            return jd;
+        }
+        let tai = match from {
+            TimeScale::Utc => jd.0 + self.tai_minus_utc(jd) / SECONDS_PER_DAY,
+            TimeScale::Tai => jd.0,
+            TimeScale::Tt => jd.0 - TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => jd.0 - (TT_MINUS_TAI + tdb_minus_tt(jd)) / SECONDS_PER_DAY,
+        };
+        JulianDate(match to {
+            TimeScale::Utc => {
+                // The offset is tabulated against UTC; one refinement is exact
+                // except inside the leap second itself.
+                let guess = tai - self.tai_minus_utc(JulianDate(tai)) / SECONDS_PER_DAY;
+                tai - self.tai_minus_utc(JulianDate(guess)) / SECONDS_PER_DAY
+            }
+            TimeScale::Tai => tai,
+            TimeScale::Tt => tai + TT_MINUS_TAI / SECONDS_PER_DAY,
+            TimeScale::Tdb => {
+                let tt = JulianDate(tai + TT_MINUS_TAI / SECONDS_PER_DAY);
+                tt.0 + tdb_minus_tt(tt) / SECONDS_PER_DAY
+            }
+        })
+    }
+
+    /// The current system time as a TT Julian date.
+    pub fn now_tt(&self) -> JulianDate {
+        let unix = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+        self.convert(
+            JulianDate::from_unix_seconds(unix),
+            TimeScale::Utc,
+            TimeScale::Tt,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MILLISECOND_DAYS: f64 = 1.0e-3 / SECONDS_PER_DAY;
+
+    fn bundled_table() -> LeapSecondTable {
+        LeapSecondTable::parse(include_str!("../../assets/data/leap_seconds.ron"))
+            .expect("bundled leap-second table should parse")
+    }
+
+    fn jd(text: &str, calendar: Calendar) -> f64 {
+        JulianDate::from_calendar(&text.parse().unwrap(), calendar)
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn meeus_reference_dates() {
+        // Meeus, Astronomical Algorithms, ch. 7.
+        for (date, expected) in [
+            ("2000-01-01T12:00", J2000.0),
+            ("1999-01-01", 2_451_179.5),
+            ("1987-06-19T12:00", 2_446_966.0),
+            ("1600-12-31", 2_305_812.5),
+            ("0837-04-10T07:12", 2_026_871.8),
+            ("-1000-07-12T12:00", 1_356_001.0),
+            ("-4712-01-01T12:00", 0.0),
+            ("1957-10-04T19:26:24", 2_436_116.31),
+        ] {
+            let actual = jd(date, Calendar::Reform);
+            assert!((actual - expected).abs() < 1e-8, "{date}: {actual}");
+        }
+    }
+
+    #[test]
+    fn reform_switches_calendars() {
+        // Thursday 4 October 1582 (Julian) was followed by Friday 15 October.
+        assert_eq!(jd("1582-10-04", Calendar::Reform), 2_299_159.5);
+        assert_eq!(jd("1582-10-15", Calendar::Reform), GREGORIAN_REFORM.0);
+        assert_eq!(
+            jd("1582-10-15", Calendar::Julian),
+            GREGORIAN_REFORM.0 + 10.0
+        );
+        assert_eq!(
+            jd("1582-10-04", Calendar::Gregorian),
+            GREGORIAN_REFORM.0 - 11.0
+        );
+
+        let before = JulianDate(2_299_159.5).to_calendar(Calendar::Reform);
+        assert_eq!((before.year, before.month, before.day), (1582, 10, 4));
+        let after = JulianDate(2_299_160.5).to_calendar(Calendar::Reform);
+        assert_eq!((after.year, after.month, after.day), (1582, 10, 15));
+    }
+
+    #[test]
+    fn calendar_round_trip() {
+        for calendar in [Calendar::Reform, Calendar::Gregorian, Calendar::Julian] {
+            for day in [0.0, 1_000_000.25, 2_299_160.5, 2_451_545.0, 2_460_000.75] {
+                let date = JulianDate(day).to_calendar(calendar);
+                let back = JulianDate::from_calendar(&date, calendar).unwrap().0;
+                assert!((back - day).abs() < 1e-8, "{calendar:?} {day} -> {date}");
+            }
+        }
+    }
+
+    #[test]
+    fn modified_and_unix_epochs() {
+        assert_eq!(ModifiedJulianDate::from(J2000).0, 51_544.5);
+        assert_eq!(JulianDate::from(ModifiedJulianDate(0.0)).0, MJD_OFFSET);
+        assert_eq!(JulianDate::from_unix_seconds(0.0), UNIX_EPOCH);
+        assert_eq!(jd("1970-01-01", Calendar::Reform), UNIX_EPOCH.0);
+        assert_eq!(J2000.j2000_seconds(), 0.0);
+    }
+
+    #[test]
+    fn leap_seconds_from_bundled_table() {
+        let table = bundled_table();
+        assert!(table.len() >= 28);
+        assert_eq!(
+            table.tai_minus_utc(JulianDate(jd("1972-01-01", Calendar::Reform))),
+            10.0
+        );
+        assert_eq!(table.tai_minus_utc(J2000), 32.0);
+        assert_eq!(
+            table.tai_minus_utc(JulianDate(jd("2016-12-31T23:59", Calendar::Reform))),
+            36.0
+        );
+        assert_eq!(
+            table.tai_minus_utc(JulianDate(jd("2017-01-01", Calendar::Reform))),
+            37.0
+        );
+    }
+
+    #[test]
+    fn time_scale_offsets() {
+        let table = bundled_table();
+
+        // J2000.0 is defined in TT; UTC was 64.184 s behind.
+        let utc = table.convert(J2000, TimeScale::Tt, TimeScale::Utc);
+        assert!(((J2000.0 - utc.0) * SECONDS_PER_DAY - 64.184).abs() < 1e-4);
+
+        let tai = table.convert(J2000, TimeScale::Tt, TimeScale::Tai);
+        assert!(((J2000.0 - tai.0) * SECONDS_PER_DAY - TT_MINUS_TAI).abs() < 1e-4);
+
+        // TDB − TT stays within about 1.7 ms.
+        let tdb = table.convert(J2000, TimeScale::Tt, TimeScale::Tdb);
+        assert!((tdb.0 - J2000.0).abs() < 2.0 * MILLISECOND_DAYS);
+
+        for scale in [TimeScale::Utc, TimeScale::Tai, TimeScale::Tdb] {
+            let there = table.convert(J2000, TimeScale::Tt, scale);
+            let back = table.convert(there, scale, TimeScale::Tt);
+            assert!((back.0 - J2000.0).abs() < 0.1 * MILLISECOND_DAYS);
+        }
+    }
+
+    #[test]
+    fn tdb_minus_tt_peaks_near_1_7_ms() {
+        let peak = (0..3650)
+            .map(|day| tdb_minus_tt(JulianDate(J2000.0 + f64::from(day) / 10.0)).abs())
+            .fold(0.0, f64::max);
+        assert!(peak > 1.5e-3 && peak < 1.8e-3, "peak {peak}");
+    }
+
+    #[test]
+    fn rejects_days_missing_from_the_calendar() {
+        let valid = |text: &str, calendar| {
+            JulianDate::from_calendar(&text.parse().unwrap(), calendar).is_ok()
+        };
+        assert!(!valid("2000-02-30", Calendar::Reform));
+        assert!(!valid("2000-04-31", Calendar::Gregorian));
+        assert!(valid("2000-02-29", Calendar::Gregorian));
+
+        // Century years are leap years only in the Julian calendar.
+        assert!(!valid("1500-02-29", Calendar::Gregorian));
+        assert!(valid("1500-02-29", Calendar::Reform));
+        assert!(valid("1900-02-29", Calendar::Julian));
+        assert!(!valid("1900-02-29", Calendar::Reform));
+
+        // The reform skipped 5 to 14 October 1582.
+        assert!(!valid("1582-10-05", Calendar::Reform));
+        assert!(!valid("1582-10-14", Calendar::Reform));
+        assert!(valid("1582-10-10", Calendar::Julian));
+        assert!(valid("1582-10-10", Calendar::Gregorian));
+    }
+
+    #[test]
+    fn rejects_malformed_dates() {
+        assert!("2000-13-01".parse::<CalendarDate>().is_err());
+        assert!("2000-01-01T24:00".parse::<CalendarDate>().is_err());
+        assert!("tomorrow".parse::<CalendarDate>().is_err());
+        assert!("2000-01-32".parse::<CalendarDate>().is_err());
+        assert!(LeapSecondTable::parse(r#"[(date: "2000-01-01", tai_minus_utc: 32), (date: "1999-01-01", tai_minus_utc: 31)]"#).is_err());
+    }
+}
@@ -0,0 +1,614 @@
+//! # VSOP87 Planetary Theory
+//!
+//! Heliocentric positions of the eight planets from the VSOP87D series
+//! (Bretagnon & Francou, 1988). Each of the ecliptic longitude `L`, latitude
+//! `B` and radius vector `R` (mean ecliptic and equinox of date) is a power
+//! series in Julian millennia `τ` from J2000.0:
+//!
+//! ```text
+//! X = Σα τ^α Σ A cos(B + C τ)
+//! ```
+//!
+//! Coefficients are read from files in the layout IMCCE distributes
+//! (`VSOP87D.ear`, `VSOP87D.mar`, ...): a header line per series naming the
+//! variable, the power of `τ` and the term count, followed by term lines
+//! whose last three fields are `A`, `B` and `C`. The bundled files hold the
+//! truncated series of Meeus, *Astronomical Algorithms*, Appendix III; the
+//! full files can be dropped in unchanged. TT is used for TDB.
+
+use std::f64::consts::TAU;
+use std::path::{Path, PathBuf};
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::JulianDate;
+use super::frames::{Frame, Spherical, convert};
+use crate::constants::{ASTRONOMICAL_UNIT, SECONDS_PER_DAY};
+
+/// Days per Julian millennium, the time unit of the series.
+const DAYS_PER_MILLENNIUM: f64 = 365_250.0;
+
+/// Half-width of the central difference used for the rotation of the
+/// ecliptic of date (s).
+const FRAME_STEP: f64 = 3600.0;
+
+/// Failure to read VSOP87 coefficients.
+#[derive(Debug, Error)]
+pub enum VsopError {
+    #[error("Failed to read VSOP87 file {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Malformed VSOP87 file at line {line}: {reason}")]
+    Malformed { line: usize, reason: String },
+}
+
+/// A planet covered by VSOP87.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Earth,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 8] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::Earth,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    /// Lower-case English name, as used for scenario bodies.
+    pub fn name(self) -> &'static str {
+        match self {
+            Planet::Mercury => "mercury",
+            Planet::Venus => "venus",
+            Planet::Earth => "earth",
+            Planet::Mars => "mars",
+            Planet::Jupiter => "jupiter",
+            Planet::Saturn => "saturn",
+            Planet::Uranus => "uranus",
+            Planet::Neptune => "neptune",
+        }
+    }
+
+    /// Name of the VSOP87D coefficient file.
+    pub fn file_name(self) -> String {
+        format!("VSOP87D.{}", &self.name()[..3])
+    }
+}
+
+/// Which terms to keep when reading a series. Fewer terms evaluate faster at
+/// the cost of accuracy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Truncation {
+    /// Terms with a smaller amplitude `A` are dropped.
+    pub min_amplitude: f64,
+    /// Keep at most this many of the largest terms per series.
+    pub max_terms: Option<usize>,
+}
+
+impl Default for Truncation {
+    fn default() -> Self {
+        Self {
+            min_amplitude: 0.0,
+            max_terms: None,
+        }
+    }
+}
+
+impl Truncation {
+    fn apply(&self, terms: &mut Vec<Term>) {
+        terms.retain(|term| term.amplitude.abs() >= self.min_amplitude);
+        if let Some(max) = self.max_terms
+            && terms.len() > max
+        {
+            terms.sort_by(|a, b| b.amplitude.abs().total_cmp(&a.amplitude.abs()));
+            terms.truncate(max);
+        }
+    }
+}
+
+/// One periodic term `A cos(B + C τ)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Term {
+    amplitude: f64,
+    phase: f64,
+    frequency: f64,
+}
+
+/// Series in `τ^0, τ^1, ...` for one coordinate.
+type PowerSeries = Vec<Vec<Term>>;
+
+/// The L, B and R series of one planet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetTheory {
+    pub planet: Planet,
+    series: [PowerSeries; 3],
+}
+
+impl PlanetTheory {
+    /// Reads `planet`'s coefficient file.
+    pub fn load(planet: Planet, path: &Path, truncation: &Truncation) -> Result<Self, VsopError> {
+        let text = std::fs::read_to_string(path).map_err(|source| VsopError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(planet, &text, truncation)
+    }
+
+    /// Parses coefficient text, checking each series against the term count
+    /// in its header before truncating it.
+    pub fn parse(planet: Planet, text: &str, truncation: &Truncation) -> Result<Self, VsopError> {
+        let mut series: [PowerSeries; 3] = Default::default();
+        let mut current: Option<Header> = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let malformed = |reason: &str| VsopError::Malformed {
+                line: number,
+                reason: reason.to_string(),
+            };
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            if trimmed.starts_with("VSOP87") {
+                finish_series(&mut series, current, truncation)?;
+                let header = Header::parse(trimmed, number)
+                    .ok_or_else(|| malformed("unreadable series header"))?;
+                let powers = &mut series[header.variable];
+                if powers.len() <= header.power {
+                    powers.resize(header.power + 1, Vec::new());
+                }
+                current = Some(header);
+                continue;
+            }
+
+            let Some(header) = current else {
+                return Err(malformed("term before the first series header"));
+            };
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            let [.., a, b, c] = fields[..] else {
+                return Err(malformed("expected A, B and C coefficients"));
+            };
+            let parse = |field: &str| {
+                field
+                    .parse::<f64>()
+                    .map_err(|_| malformed(&format!("invalid coefficient {field:?}")))
+            };
+            series[header.variable][header.power].push(Term {
+                amplitude: parse(a)?,
+                phase: parse(b)?,
+                frequency: parse(c)?,
+            });
+        }
+        finish_series(&mut series, current, truncation)?;
+
+        if series.iter().any(Vec::is_empty) {
+            return Err(VsopError::Malformed {
+                line: text.lines().count(),
+                reason: "missing L, B or R series".to_string(),
+            });
+        }
+        Ok(Self { planet, series })
+    }
+
+    /// Number of terms kept after truncation.
+    pub fn term_count(&self) -> usize {
+        self.series.iter().flatten().map(Vec::len).sum()
+    }
+
+    /// Heliocentric ecliptic longitude and latitude, and distance in AU,
+    /// referred to the mean ecliptic and equinox of `date` (TT).
+    pub fn spherical(&self, date: JulianDate) -> (Spherical, f64) {
+        let tau = date.days_since_j2000() / DAYS_PER_MILLENNIUM;
+        let [l, b, r] = self.series.each_ref().map(|series| evaluate(series, tau));
+        (Spherical::new(l.rem_euclid(TAU), b), r)
+    }
+
+    /// Heliocentric position in AU in the ecliptic J2000 (scene) frame.
+    pub fn position(&self, date: JulianDate) -> DVec3 {
+        let (direction, distance) = self.spherical(date);
+        convert(
+            direction.to_vector() * distance,
+            &Frame::EclipticOfDate(date),
+            &Frame::EclipticJ2000,
+        )
+    }
+
+    /// Heliocentric position (m) in the scene frame at `epoch`, TT seconds
+    /// since J2000.0 as kept by `SimClock`.
+    pub fn heliocentric(&self, epoch: f64) -> DVec3 {
+        self.position(JulianDate::from_j2000_seconds(epoch)) * ASTRONOMICAL_UNIT
+    }
+
+    /// Heliocentric position (m) and velocity (m/s) at `epoch`.
+    ///
+    /// The series are evaluated once, together with their derivatives; only
+    /// the slow rotation of the ecliptic of date is differenced.
+    pub fn state(&self, epoch: f64) -> (DVec3, DVec3) {
+        let date = JulianDate::from_j2000_seconds(epoch);
+        let tau = date.days_since_j2000() / DAYS_PER_MILLENNIUM;
+        let [(l, l_rate), (b, b_rate), (r, r_rate)] = self
+            .series
+            .each_ref()
+            .map(|series| evaluate_with_rate(series, tau));
+
+        let (sin_l, cos_l) = l.rem_euclid(TAU).sin_cos();
+        let (sin_b, cos_b) = b.sin_cos();
+        let direction = DVec3::new(cos_b * cos_l, cos_b * sin_l, sin_b);
+        let east = DVec3::new(-cos_b * sin_l, cos_b * cos_l, 0.0);
+        let north = DVec3::new(-sin_b * cos_l, -sin_b * sin_l, cos_b);
+        let position = direction * r;
+        let velocity = direction * r_rate + (east * l_rate + north * b_rate) * r;
+
+        let to_j2000 = |vector: DVec3, date: JulianDate| {
+            convert(vector, &Frame::EclipticOfDate(date), &Frame::EclipticJ2000)
+        };
+        let step = FRAME_STEP / SECONDS_PER_DAY;
+        let frame_drift = (to_j2000(position, JulianDate(date.0 + step))
+            - to_j2000(position, JulianDate(date.0 - step)))
+            / (2.0 * FRAME_STEP);
+        let per_second = DAYS_PER_MILLENNIUM * SECONDS_PER_DAY;
+        (
+            to_j2000(position, date) * ASTRONOMICAL_UNIT,
+            (to_j2000(velocity, date) / per_second + frame_drift) * ASTRONOMICAL_UNIT,
+        )
+    }
+}
+
+/// Sums `Σα τ^α Σ A cos(B + C τ)` by Horner's rule.
+fn evaluate(series: &PowerSeries, tau: f64) -> f64 {
+    series.iter().rev().fold(0.0, |acc, terms| {
+        let sum: f64 = terms
+            .iter()
+            .map(|term| term.amplitude * (term.phase + term.frequency * tau).cos())
+            .sum();
+        acc * tau + sum
+    })
+}
+
+/// Sums the series and its derivative with respect to `τ` in one pass.
+fn evaluate_with_rate(series: &PowerSeries, tau: f64) -> (f64, f64) {
+    series
+        .iter()
+        .rev()
+        .fold((0.0, 0.0), |(value, rate), terms| {
+            let (sum, sum_rate) = terms.iter().fold((0.0, 0.0), |(sum, sum_rate), term| {
+                let (sin, cos) = (term.phase + term.frequency * tau).sin_cos();
+                (
+                    sum + term.amplitude * cos,
+                    sum_rate - term.amplitude * term.frequency * sin,
+                )
+            });
+            (value * tau + sum, rate * tau + value + sum_rate)
+        })
+}
+
+/// A series header: which coordinate, which power of `τ`, how many terms.
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    /// 0 = L, 1 = B, 2 = R.
+    variable: usize,
+    power: usize,
+    terms: usize,
+    /// Line the header was read from.
+    line: usize,
+}
+
+impl Header {
+    fn parse(text: &str, line: usize) -> Option<Self> {
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        let position = |key: &str| fields.iter().position(|field| *field == key);
+        let variable = fields
+            .get(position("VARIABLE")? + 1)?
+            .parse::<usize>()
+            .ok()?
+            .checked_sub(1)
+            .filter(|variable| *variable < 3)?;
+        let power = fields
+            .iter()
+            .find_map(|field| field.strip_prefix("*T**"))?
+            .parse()
+            .ok()?;
+        let terms = fields
+            .get(position("TERMS")?.checked_sub(1)?)?
+            .parse()
+            .ok()?;
+        Some(Self {
+            variable,
+            power,
+            terms,
+            line,
+        })
+    }
+}
+
+/// Checks the series opened by `header` against its announced term count,
+/// then truncates it.
+fn finish_series(
+    series: &mut [PowerSeries; 3],
+    header: Option<Header>,
+    truncation: &Truncation,
+) -> Result<(), VsopError> {
+    let Some(header) = header else {
+        return Ok(());
+    };
+    let terms = &mut series[header.variable][header.power];
+    if terms.len() != header.terms {
+        return Err(VsopError::Malformed {
+            line: header.line,
+            reason: format!(
+                "header announces {} terms, found {}",
+                header.terms,
+                terms.len()
+            ),
+        });
+    }
+    truncation.apply(terms);
+    Ok(())
+}
+
+/// VSOP87 theories of every planet found in the ephemeris directory.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Ephemeris {
+    theories: Vec<PlanetTheory>,
+}
+
+impl Ephemeris {
+    /// Reads the coefficient file of each planet present in `dir`. Planets
+    /// without a file are left out; a malformed file is an error.
+    pub fn load(dir: &Path, truncation: &Truncation) -> Result<Self, VsopError> {
+        let mut theories = Vec::new();
+        for planet in Planet::ALL {
+            let path = dir.join(planet.file_name());
+            if path.exists() {
+                theories.push(PlanetTheory::load(planet, &path, truncation)?);
+            }
+        }
+        Ok(Self { theories })
+    }
+
+    pub fn from_theories(theories: Vec<PlanetTheory>) -> Self {
+        Self { theories }
+    }
+
+    pub fn len(&self) -> usize {
+        self.theories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.theories.is_empty()
+    }
+
+    /// Total number of terms across all planets.
+    pub fn term_count(&self) -> usize {
+        self.theories.iter().map(PlanetTheory::term_count).sum()
+    }
+
+    pub fn theory(&self, planet: Planet) -> Option<&PlanetTheory> {
+        self.theories.iter().find(|theory| theory.planet == planet)
+    }
+
+    /// Heliocentric position (m) in the scene frame at `epoch`, TT seconds
+    /// since J2000.0 as kept by `SimClock`.
+    pub fn heliocentric(&self, planet: Planet, epoch: f64) -> Option<DVec3> {
+        Some(self.theory(planet)?.heliocentric(epoch))
+    }
+
+    /// Heliocentric position (m) and velocity (m/s) at `epoch`.
+    pub fn state(&self, planet: Planet, epoch: f64) -> Option<(DVec3, DVec3)> {
+        Some(self.theory(planet)?.state(epoch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::time::J2000;
+    use crate::constants::SECONDS_PER_DAY;
+
+    /// Arcminute tolerance in radians.
+    const ARCMINUTE: f64 = std::f64::consts::PI / (180.0 * 60.0);
+
+    const EARTH: &str = include_str!("../../assets/ephemeris/VSOP87D.ear");
+    const VENUS: &str = include_str!("../../assets/ephemeris/VSOP87D.ven");
+    const JUPITER: &str = include_str!("../../assets/ephemeris/VSOP87D.jup");
+    const SATURN: &str = include_str!("../../assets/ephemeris/VSOP87D.sat");
+    const NEPTUNE: &str = include_str!("../../assets/ephemeris/VSOP87D.nep");
+
+    /// Speed on a circular heliocentric orbit of radius `distance` AU (m/s).
+    fn circular_speed(distance: f64) -> f64 {
+        TAU * ASTRONOMICAL_UNIT / (365.256_363 * SECONDS_PER_DAY * distance.sqrt())
+    }
+
+    fn theory(planet: Planet, text: &str, truncation: &Truncation) -> PlanetTheory {
+        PlanetTheory::parse(planet, text, truncation).expect("bundled file parses")
+    }
+
+    fn assert_close(
+        theory: &PlanetTheory,
+        date: JulianDate,
+        lon_deg: f64,
+        lat_deg: f64,
+        radius: f64,
+    ) {
+        let (position, distance) = theory.spherical(date);
+        let dlon = (position.lon - lon_deg.to_radians() + TAU / 2.0).rem_euclid(TAU) - TAU / 2.0;
+        let dlat = position.lat - lat_deg.to_radians();
+        assert!(dlon.abs() < ARCMINUTE, "longitude off by {dlon} rad");
+        assert!(dlat.abs() < ARCMINUTE, "latitude off by {dlat} rad");
+        // An arcminute at the planet's distance.
+        assert!(
+            (distance - radius).abs() < radius * ARCMINUTE,
+            "{distance} AU"
+        );
+    }
+
+    #[test]
+    fn earth_matches_meeus_example_25b() {
+        // 1992-10-13.0 TD: L = 19.907372°, B = -0.000179°, R = 0.99760775 AU.
+        let earth = theory(Planet::Earth, EARTH, &Truncation::default());
+        assert_close(
+            &earth,
+            JulianDate(2_448_908.5),
+            19.907_372,
+            -0.000_179,
+            0.997_607_75,
+        );
+    }
+
+    #[test]
+    fn venus_matches_meeus_example_32a() {
+        // 1992-12-20.0 TD: L = 26.11428°, B = -2.62070°, R = 0.724603 AU.
+        let venus = theory(Planet::Venus, VENUS, &Truncation::default());
+        assert_close(
+            &venus,
+            JulianDate(2_448_976.5),
+            26.114_28,
+            -2.620_70,
+            0.724_603,
+        );
+    }
+
+    #[test]
+    fn outer_planets_match_vsop87_check_values() {
+        // VSOP87D check values at J2000.0: L and B in radians, R in AU.
+        let checks: [(Planet, &str, f64, f64, f64); 3] = [
+            (
+                Planet::Jupiter,
+                JUPITER,
+                0.633_461_418_6,
+                -0.020_500_103_9,
+                4.965_381_315_4,
+            ),
+            (
+                Planet::Saturn,
+                SATURN,
+                0.798_003_876_1,
+                -0.040_198_414_9,
+                9.183_801_436_9,
+            ),
+            (
+                Planet::Neptune,
+                NEPTUNE,
+                5.304_562_925_2,
+                0.004_223_678_9,
+                30.120_532_839_2,
+            ),
+        ];
+        for (planet, text, l, b, r) in checks {
+            let theory = theory(planet, text, &Truncation::default());
+            assert_close(&theory, J2000, l.to_degrees(), b.to_degrees(), r);
+        }
+    }
+
+    #[test]
+    fn velocities_match_the_motion_of_the_position() {
+        let step = 600.0;
+        for (planet, text) in [(Planet::Earth, EARTH), (Planet::Neptune, NEPTUNE)] {
+            let theory = theory(planet, text, &Truncation::default());
+            // A century out, where the ecliptic of date has turned.
+            let epoch = 36_525.0 * SECONDS_PER_DAY;
+            let (position, velocity) = theory.state(epoch);
+            let difference = (theory.heliocentric(epoch + step)
+                - theory.heliocentric(epoch - step))
+                / (2.0 * step);
+            assert!((position - theory.heliocentric(epoch)).length() < 1.0e-3);
+            assert!(
+                (velocity - difference).length() < 1.0e-3,
+                "{planet:?}: {velocity} vs {difference}"
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_series_stay_within_an_arcminute() {
+        let full = theory(Planet::Earth, EARTH, &Truncation::default());
+        let truncated = theory(
+            Planet::Earth,
+            EARTH,
+            &Truncation {
+                min_amplitude: 1.0e-5,
+                max_terms: Some(8),
+            },
+        );
+        assert!(truncated.term_count() < full.term_count() / 4);
+        assert_close(
+            &truncated,
+            JulianDate(2_448_908.5),
+            19.907_372,
+            -0.000_179,
+            0.997_607_75,
+        );
+    }
+
+    #[test]
+    fn position_is_in_the_j2000_ecliptic() {
+        let earth = theory(Planet::Earth, EARTH, &Truncation::default());
+
+        // The frames coincide at J2000.0.
+        let (direction, distance) = earth.spherical(J2000);
+        let at_j2000 = earth.position(J2000);
+        assert!((at_j2000 - direction.to_vector() * distance).length() < 1e-12);
+
+        // A century later, general precession has moved the equinox ~1.397°.
+        let date = JulianDate(J2000.0 + 36_525.0);
+        let (of_date, _) = earth.spherical(date);
+        let j2000 = Spherical::from_vector(earth.position(date));
+        let shift = (of_date.lon - j2000.lon).to_degrees();
+        assert!((shift - 1.397).abs() < 0.01, "{shift}");
+    }
+
+    #[test]
+    fn ephemeris_states_have_orbital_speeds() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/ephemeris");
+        let ephemeris = Ephemeris::load(&dir, &Truncation::default()).expect("bundled files load");
+        assert_eq!(ephemeris.len(), Planet::ALL.len());
+
+        for planet in Planet::ALL {
+            let (position, velocity) = ephemeris.state(planet, 0.0).unwrap();
+            let distance = position.length() / ASTRONOMICAL_UNIT;
+            let speed = velocity.length() / circular_speed(distance);
+            assert!((0.8..1.25).contains(&speed), "{planet:?}: {speed}");
+        }
+    }
+
+    #[test]
+    fn malformed_files_report_the_line() {
+        let orphan = "  4310    1  1.0 0.0 0.0";
+        let Err(VsopError::Malformed { line, .. }) =
+            PlanetTheory::parse(Planet::Earth, orphan, &Truncation::default())
+        else {
+            panic!("expected a malformed-file error");
+        };
+        assert_eq!(line, 1);
+
+        let short = " VSOP87 VERSION D4    EARTH     VARIABLE 1 (LBR)       *T**0       2 TERMS\n 4310    1  1.0 0.0 0.0\n";
+        let Err(VsopError::Malformed { line, reason }) =
+            PlanetTheory::parse(Planet::Earth, short, &Truncation::default())
+        else {
+            panic!("expected a malformed-file error");
+        };
+        assert_eq!(line, 1);
+        assert!(reason.contains("2 terms"), "{reason}");
+    }
+}
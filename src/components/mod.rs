@@ -3,6 +3,12 @@
 //! ECS component definitions for the game layer.
 //! Components are pure data structures attached to entities.
 
+pub mod orbit;
+pub mod physics;
+pub mod spacecraft;
 pub mod world;
 
+pub use orbit::*;
+pub use physics::*;
+pub use spacecraft::*;
 pub use world::*;
@@ -0,0 +1,138 @@
+//! # Orbit Components
+//!
+//! Keplerian orbits and ephemeris placement for bodies propagated
+//! analytically instead of by N-body integration. The reference plane is
+//! XY with +Z as its pole; angles are in radians and times in simulation
+//! seconds.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::astro::Planet;
+
+/// Classical Keplerian orbital elements.
+///
+/// Hyperbolic orbits (`eccentricity > 1`) use a negative `semi_major_axis`.
+/// Parabolic orbits are not representable.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Semi-major axis `a`.
+    pub semi_major_axis: f64,
+    /// Eccentricity `e`.
+    pub eccentricity: f64,
+    /// Inclination `i` to the reference plane.
+    pub inclination: f64,
+    /// Longitude of the ascending node `Ω`.
+    pub longitude_of_ascending_node: f64,
+    /// Argument of periapsis `ω`.
+    pub argument_of_periapsis: f64,
+    /// Mean anomaly `M0` at `epoch`.
+    pub mean_anomaly_at_epoch: f64,
+    /// Time at which `mean_anomaly_at_epoch` is valid.
+    pub epoch: f64,
+}
+
+/// Marks a body whose position follows its `OrbitalElements` ("on rails").
+/// A rails body with neither elements nor a theory stays where it is.
+///
+/// Rails bodies still attract N-body bodies but are not accelerated themselves.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct OnRails {
+    /// Body the orbit is relative to; `None` orbits the world origin.
+    pub primary: Option<Entity>,
+    /// Gravitational parameter `μ = G (M + m)` of the orbit.
+    pub gravitational_parameter: f64,
+}
+
+/// Marks a body placed by the VSOP87 ephemeris instead of integrated.
+///
+/// Such bodies also carry `OnRails` (without `OrbitalElements`) so they
+/// attract N-body bodies like other rails bodies.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EphemerisBody(pub Planet);
+
+/// Marks a body placed by the lunar theory relative to its primary (the
+/// Earth). Carries `OnRails` like `EphemerisBody`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LunarBody;
+
+/// Body that this one was declared relative to in its scenario.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrbitParent(pub Entity);
+
+/// Past positions of a body in a fixed-size ring buffer; once full, each
+/// new sample overwrites the oldest.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Trail {
+    points: Vec<DVec3>,
+    /// Slot the next sample goes into once the buffer is full.
+    next: usize,
+    capacity: usize,
+}
+
+impl Trail {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            points: Vec::with_capacity(capacity),
+            next: 0,
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, point: DVec3) {
+        if self.points.len() < self.capacity {
+            self.points.push(point);
+        } else {
+            self.points[self.next] = point;
+            self.next = (self.next + 1) % self.capacity;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.next = 0;
+    }
+
+    /// Samples from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = DVec3> + '_ {
+        let (newer, older) = self.points.split_at(self.next);
+        older.iter().chain(newer).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trail_overwrites_oldest_samples() {
+        let mut trail = Trail::new(3);
+        assert!(trail.is_empty());
+        for x in 1..=2 {
+            trail.push(DVec3::splat(f64::from(x)));
+        }
+        assert_eq!(trail.iter().map(|p| p.x).collect::<Vec<_>>(), [1.0, 2.0]);
+
+        for x in 3..=7 {
+            trail.push(DVec3::splat(f64::from(x)));
+        }
+        assert_eq!(trail.len(), 3);
+        assert_eq!(
+            trail.iter().map(|p| p.x).collect::<Vec<_>>(),
+            [5.0, 6.0, 7.0]
+        );
+        assert_eq!(trail.iter().next_back(), Some(DVec3::splat(7.0)));
+
+        trail.clear();
+        trail.push(DVec3::ONE);
+        assert_eq!(trail.iter().collect::<Vec<_>>(), [DVec3::ONE]);
+    }
+}
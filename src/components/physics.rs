@@ -0,0 +1,36 @@
+//! # Physics Components
+//!
+//! Dynamic state for bodies taking part in the gravitational simulation.
+//! Values are stored in double precision and in SI units, matching
+//! `PhysicsConfig::gravitational_constant`.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+/// Position of a simulated body in the scene frame (metres).
+///
+/// This is the position physics reads and writes; the render `Transform` is
+/// derived from it relative to the `FloatingOrigin`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct WorldPosition(pub DVec3);
+
+/// Mass of a simulated body (kilograms).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Mass(pub f64);
+
+/// Mean radius of a simulated body.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Radius(pub f64);
+
+/// Linear velocity of a simulated body (units per second).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Velocity(pub DVec3);
+
+/// Debris of a tidally disrupted satellite. Fragments are never disrupted
+/// again, so a ring can form inside the Roche limit.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Fragment;
+
+/// Gravitational acceleration accumulated during the current fixed step.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Acceleration(pub DVec3);
@@ -0,0 +1,201 @@
+//! # Spacecraft Components
+//!
+//! Spacecraft spawned by the user, their delta-v budget and the maneuver
+//! nodes planned for them.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::physics::maneuver::ManeuverNode;
+
+/// Marks a body as a spacecraft and tracks the delta-v it has used.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Spacecraft {
+    /// Delta-v the craft started with (m/s).
+    pub delta_v_budget: f64,
+    /// Delta-v burned so far (m/s).
+    pub delta_v_spent: f64,
+}
+
+impl Spacecraft {
+    pub fn new(delta_v_budget: f64) -> Self {
+        Self {
+            delta_v_budget,
+            delta_v_spent: 0.0,
+        }
+    }
+
+    /// Delta-v left to burn (m/s).
+    pub fn remaining(&self) -> f64 {
+        (self.delta_v_budget - self.delta_v_spent).max(0.0)
+    }
+}
+
+/// Maneuver nodes of a spacecraft in time order, and the burn under way.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct ManeuverPlan {
+    nodes: Vec<ManeuverNode>,
+    /// Index of the node being edited.
+    selected: usize,
+    /// Whether nodes are burned when reached. Nodes of an unarmed plan are
+    /// dropped as their time passes.
+    pub armed: bool,
+    /// Fraction of the first node's delta-v burned so far.
+    pub progress: f64,
+    /// Scene-frame direction of the first node's burn, fixed when it starts.
+    pub direction: Option<DVec3>,
+}
+
+impl ManeuverPlan {
+    pub fn nodes(&self) -> &[ManeuverNode] {
+        &self.nodes
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        (self.selected < self.nodes.len()).then_some(self.selected)
+    }
+
+    pub fn selected(&self) -> Option<&ManeuverNode> {
+        self.nodes.get(self.selected)
+    }
+
+    /// Adds `node` in time order and selects it.
+    pub fn insert(&mut self, node: ManeuverNode) {
+        let index = self
+            .nodes
+            .partition_point(|other| other.epoch <= node.epoch);
+        if index == 0 {
+            self.restart_burn();
+        }
+        self.nodes.insert(index, node);
+        self.selected = index;
+    }
+
+    /// Removes the selected node, selecting the one after it.
+    pub fn remove_selected(&mut self) -> Option<ManeuverNode> {
+        self.selected_index()?;
+        let node = self.remove(self.selected);
+        self.selected = self.selected.min(self.nodes.len().saturating_sub(1));
+        Some(node)
+    }
+
+    /// Removes the first node, once burned or missed.
+    pub fn pop_first(&mut self) -> Option<ManeuverNode> {
+        (!self.nodes.is_empty()).then(|| {
+            let node = self.remove(0);
+            self.selected = self.selected.saturating_sub(1);
+            node
+        })
+    }
+
+    /// Selects the next node, wrapping around.
+    pub fn select_next(&mut self) {
+        if !self.nodes.is_empty() {
+            self.selected = (self.selected + 1) % self.nodes.len();
+        }
+    }
+
+    /// Applies `edit` to the selected node, keeping the nodes in time
+    /// order and the edited node selected. A burn under way carries on if
+    /// its node is still the first.
+    pub fn edit_selected(&mut self, edit: impl FnOnce(&mut ManeuverNode)) {
+        let Some(index) = self.selected_index() else {
+            return;
+        };
+        let burn = (self.progress, self.direction);
+        let mut node = self.remove(index);
+        edit(&mut node);
+        self.insert(node);
+        if index == 0 && self.selected == 0 {
+            (self.progress, self.direction) = burn;
+        }
+    }
+
+    /// Delta-v still to burn over every node (m/s).
+    pub fn planned_delta_v(&self) -> f64 {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let left = if index == 0 { 1.0 - self.progress } else { 1.0 };
+                node.delta_v() * left
+            })
+            .sum()
+    }
+
+    fn remove(&mut self, index: usize) -> ManeuverNode {
+        if index == 0 {
+            self.restart_burn();
+        }
+        self.nodes.remove(index)
+    }
+
+    fn restart_burn(&mut self) {
+        self.progress = 0.0;
+        self.direction = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(epoch: f64, prograde: f64) -> ManeuverNode {
+        ManeuverNode {
+            epoch,
+            prograde,
+            ..Default::default()
+        }
+    }
+
+    fn epochs(plan: &ManeuverPlan) -> Vec<f64> {
+        plan.nodes().iter().map(|node| node.epoch).collect()
+    }
+
+    #[test]
+    fn plans_stay_in_time_order() {
+        let mut plan = ManeuverPlan::default();
+        assert_eq!(plan.selected(), None);
+        for epoch in [300.0, 100.0, 200.0] {
+            plan.insert(node(epoch, 10.0));
+        }
+        assert_eq!(epochs(&plan), [100.0, 200.0, 300.0]);
+        assert_eq!(plan.selected_index(), Some(1));
+
+        plan.edit_selected(|node| node.epoch = 400.0);
+        assert_eq!(epochs(&plan), [100.0, 300.0, 400.0]);
+        assert_eq!(plan.selected_index(), Some(2));
+        plan.select_next();
+        assert_eq!(plan.selected_index(), Some(0));
+
+        plan.select_next();
+        assert_eq!(plan.remove_selected().map(|node| node.epoch), Some(300.0));
+        assert_eq!(plan.selected_index(), Some(1));
+        assert_eq!(plan.remove_selected().map(|node| node.epoch), Some(400.0));
+        assert_eq!(plan.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn burns_in_progress_count_towards_the_plan() {
+        let mut plan = ManeuverPlan::default();
+        plan.insert(node(100.0, 40.0));
+        plan.insert(node(200.0, -30.0));
+        plan.progress = 0.25;
+        plan.direction = Some(DVec3::Y);
+        assert_eq!(plan.planned_delta_v(), 60.0);
+
+        // Editing a later node, or the burning one in place, leaves the
+        // burn alone.
+        plan.edit_selected(|node| node.prograde = -20.0);
+        plan.select_next();
+        plan.edit_selected(|node| node.normal = 30.0);
+        assert_eq!((plan.progress, plan.direction), (0.25, Some(DVec3::Y)));
+        assert_eq!(plan.planned_delta_v(), 57.5);
+        plan.select_next();
+
+        assert_eq!(plan.pop_first().map(|node| node.epoch), Some(100.0));
+        assert_eq!((plan.progress, plan.direction), (0.0, None));
+        assert_eq!(plan.selected_index(), Some(0));
+        assert_eq!(plan.planned_delta_v(), 20.0);
+    }
+}
@@ -2,7 +2,14 @@
 //!
 //! ECS components specific to the game world.
 
+use std::sync::Arc;
+
+use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
+use bevy::tasks::Task;
+
+use crate::procgen::PlanetClass;
+use crate::procgen::terrain::{ChunkId, ChunkMesh, TerrainShape};
 
 /// Marker component for the game world entities.
 #[derive(Component)]
@@ -11,3 +18,138 @@ pub struct GameWorldRoot;
 /// Marker for entities with idle rotation while gameplay is active.
 #[derive(Component)]
 pub struct Rotates;
+
+/// Marker for bodies spawned from a scenario (planets, moons, stars).
+#[derive(Component)]
+pub struct CelestialBody;
+
+/// Sidereal rotation period of a `Rotates` body (seconds, negative is retrograde).
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct RotationPeriod(pub f64);
+
+/// Radius a body's mesh was built at (m). The body is drawn scaled by its
+/// current `Radius` over this, times the view's radius inflation.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct MeshRadius(pub f64);
+
+/// Class, albedo and colors of a generated planet.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct Surface {
+    pub class: PlanetClass,
+    /// Bond albedo.
+    pub albedo: f32,
+    /// Base color first.
+    pub palette: Vec<Color>,
+    /// Seed for surface detail.
+    pub seed: u64,
+}
+
+/// Quadtree terrain of a body, replacing its sphere mesh once the first
+/// chunks are built.
+#[derive(Component, Debug)]
+pub struct TerrainLod {
+    pub shape: Arc<TerrainShape>,
+    /// Double-sided vertex-colored material shared by the chunks.
+    pub material: Handle<StandardMaterial>,
+    /// Chunk entities by quadtree node, including chunks still building.
+    pub chunks: HashMap<ChunkId, Entity>,
+}
+
+/// One terrain chunk, a child of its body.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerrainChunk(pub ChunkId);
+
+/// Chunk mesh being built on the async compute pool.
+#[derive(Component)]
+pub struct ChunkBuild(pub Task<ChunkMesh>);
+
+/// Root of the background star field, kept centred on the camera.
+#[derive(Component)]
+pub struct CelestialSphere;
+
+/// UI root holding the constellation name labels.
+#[derive(Component)]
+pub struct ConstellationLabels;
+
+/// A constellation name pinned to a direction on the celestial sphere.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ConstellationLabel {
+    /// Unit vector in the equatorial frame.
+    pub direction: Vec3,
+}
+
+/// Ground disc under the surface observer.
+#[derive(Component)]
+pub struct ObserverGround;
+
+/// UI root holding the cardinal-point labels of the observer view.
+#[derive(Component)]
+pub struct HorizonLabels;
+
+/// A label pinned to a direction in the observer's horizontal frame.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HorizonLabel {
+    /// Unit vector with +X east, +Y north and +Z at the zenith.
+    pub direction: Vec3,
+}
+
+/// UI root of the selected body's info panel.
+#[derive(Component)]
+pub struct InfoPanel;
+
+/// Info panel text showing the selected body's name.
+#[derive(Component)]
+pub struct InfoTitle;
+
+/// Info panel text showing the selected body's planet class.
+#[derive(Component)]
+pub struct InfoClass;
+
+/// Info panel text showing one figure of the selected body.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InfoValue(pub InfoField);
+
+/// HUD text naming the current view scale.
+#[derive(Component)]
+pub struct ScaleLabel;
+
+/// HUD text listing the maneuver plan of the spacecraft being edited.
+#[derive(Component)]
+pub struct ManeuverPanel;
+
+/// Figures listed in the info panel, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfoField {
+    Mass,
+    Radius,
+    CameraDistance,
+    ParentDistance,
+    Velocity,
+    Period,
+    Eccentricity,
+}
+
+impl InfoField {
+    pub const ALL: [InfoField; 7] = [
+        InfoField::Mass,
+        InfoField::Radius,
+        InfoField::CameraDistance,
+        InfoField::ParentDistance,
+        InfoField::Velocity,
+        InfoField::Period,
+        InfoField::Eccentricity,
+    ];
+
+    /// Localization key of the row label.
+    pub fn key(self) -> &'static str {
+        match self {
+            InfoField::Mass => "info-mass",
+            InfoField::Radius => "info-radius",
+            InfoField::CameraDistance => "info-camera-distance",
+            InfoField::ParentDistance => "info-parent-distance",
+            InfoField::Velocity => "info-velocity",
+            InfoField::Period => "info-period",
+            InfoField::Eccentricity => "info-eccentricity",
+        }
+    }
+}
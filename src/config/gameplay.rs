@@ -13,8 +13,47 @@ pub struct PhysicsConfig {
     pub gravitational_constant: f64,
     /// Time step for fixed update physics.
     pub time_step: f32,
-    /// Maximum allowed velocity for any object.
+    /// Maximum allowed velocity for any object (m/s).
     pub max_velocity: f32,
+    /// Numerical scheme used to advance bodies each fixed step.
+    pub integrator: IntegratorKind,
+    /// Backend used to evaluate gravitational accelerations.
+    pub solver: GravitySolver,
+    /// Barnes–Hut opening angle θ. Smaller is more accurate but slower.
+    pub opening_angle: f64,
+    /// Longest simulated interval integrated in one sub-step (seconds).
+    /// High time scales split each fixed step into several sub-steps.
+    pub max_substep: f64,
+    /// Upper bound on sub-steps per fixed step; beyond it sub-steps grow.
+    pub max_substeps: u32,
+}
+
+/// Gravity force backends.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GravitySolver {
+    /// Exact O(n²) pairwise summation on the compute task pool.
+    #[default]
+    Direct,
+    /// O(n log n) Barnes–Hut octree approximation.
+    BarnesHut,
+}
+
+/// Numerical integration schemes available to the simulation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegratorKind {
+    /// First-order explicit Euler.
+    ExplicitEuler,
+    /// First-order symplectic Euler.
+    SemiImplicitEuler,
+    /// Second-order velocity Verlet / leapfrog.
+    #[default]
+    VelocityVerlet,
+    /// Classic fourth-order Runge–Kutta.
+    Rk4,
+    /// Fourth-order symplectic Yoshida scheme.
+    Yoshida4,
 }
 
 impl Default for PhysicsConfig {
@@ -22,7 +61,12 @@ impl Default for PhysicsConfig {
         Self {
             gravitational_constant: 6.67430e-11,
             time_step: 0.016,
-            max_velocity: 1000.0,
+            max_velocity: 1.0e6,
+            integrator: IntegratorKind::default(),
+            solver: GravitySolver::default(),
+            opening_angle: 0.5,
+            max_substep: 60.0,
+            max_substeps: 256,
         }
     }
 }
@@ -33,9 +77,9 @@ impl Default for PhysicsConfig {
 pub struct PlanetConfig {
     /// Default mass for a newly spawned planet (Earth mass).
     pub default_mass: f64,
-    /// Minimum allowed radius for a planet.
+    /// Minimum allowed radius for a planet (m).
     pub min_radius: f32,
-    /// Maximum allowed radius for a planet.
+    /// Maximum allowed radius for a planet (m).
     pub max_radius: f32,
     /// Default rotation speed (radians per second).
     pub rotation_speed: f32,
@@ -45,19 +89,861 @@ impl Default for PlanetConfig {
     fn default() -> Self {
         Self {
             default_mass: 5.972e24,
-            min_radius: 100.0,
-            max_radius: 10000.0,
+            min_radius: 1.0e3,
+            max_radius: 1.0e9,
             rotation_speed: 0.3,
         }
     }
 }
 
+/// How overlapping bodies respond to each other.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CollisionConfig {
+    pub policy: CollisionPolicy,
+    /// Coefficient of restitution for `Bounce`: 1 is elastic, 0 stops the
+    /// approach dead.
+    pub restitution: f64,
+    /// How a merged body's radius follows from its mass.
+    pub density: DensityModel,
+}
+
+/// Response to two bodies overlapping by radius.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionPolicy {
+    /// Perfectly inelastic merge into the heavier (or on-rails) body.
+    #[default]
+    Merge,
+    /// Impulsive bounce along the line of centres.
+    Bounce,
+    /// Bodies pass through each other undetected.
+    Ignore,
+}
+
+/// Density assumed when recomputing a merged body's radius.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DensityModel {
+    /// Total mass over total volume of the parts, so volume is conserved.
+    #[default]
+    Combined,
+    /// Fixed bulk density (kg/m³).
+    Uniform(f64),
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            policy: CollisionPolicy::default(),
+            restitution: 0.5,
+            density: DensityModel::default(),
+        }
+    }
+}
+
+/// Tidal break-up of satellites inside their primary's Roche limit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DisruptionConfig {
+    pub enabled: bool,
+    pub formula: RocheFormula,
+    /// Fragments a disrupted satellite breaks into, at most.
+    pub fragments: u32,
+    /// Lightest fragment allowed (kg); satellites too light for two stay
+    /// whole.
+    pub min_fragment_mass: f64,
+    /// Largest fragment kick, as a fraction of the satellite's surface
+    /// escape speed.
+    pub velocity_spread: f64,
+    /// Seed of the fragment kicks, mixed with the satellite's name.
+    pub seed: u64,
+}
+
+/// Which Roche limit applies to satellites.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RocheFormula {
+    /// Rigid sphere held together by its own gravity.
+    Rigid,
+    /// Fluid body that elongates under the tide.
+    #[default]
+    Fluid,
+}
+
+impl Default for DisruptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            formula: RocheFormula::default(),
+            fragments: 16,
+            min_fragment_mass: 1.0e15,
+            velocity_spread: 0.5,
+            seed: 42,
+        }
+    }
+}
+
+/// Procedural terrain of generated planets.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct TerrainConfig {
+    pub enabled: bool,
+    /// Vertices along each edge of a chunk.
+    pub resolution: u32,
+    /// Deepest quadtree level; each level halves the chunk size.
+    pub max_depth: u8,
+    /// A chunk splits when the camera is closer than this many chunk
+    /// widths.
+    pub split_distance: f64,
+    /// Most vertices one planet's chunks may hold together.
+    pub vertex_budget: u32,
+    /// Largest displacement, as a fraction of the radius.
+    pub amplitude: f64,
+    /// Noise frequency of the first octave, in cycles per radius.
+    pub frequency: f64,
+    /// Noise layers summed into the height.
+    pub octaves: u32,
+    /// Frequency multiplier between octaves.
+    pub lacunarity: f64,
+    /// Amplitude multiplier between octaves.
+    pub persistence: f64,
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            resolution: 17,
+            max_depth: 10,
+            split_distance: 2.0,
+            vertex_budget: 250_000,
+            amplitude: 0.005,
+            frequency: 2.0,
+            octaves: 6,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        }
+    }
+}
+
+/// Simulation clock start-up state and hotkeys.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ClockConfig {
+    /// UTC date the clock starts at, as `YYYY-MM-DDTHH:MM:SS`.
+    pub start_date: String,
+    /// Start following the system clock instead of `start_date`.
+    pub sync_to_system: bool,
+    /// Initial time-scale multiplier.
+    pub time_scale: f64,
+    /// Factor applied by the faster/slower hotkeys.
+    pub scale_factor: f64,
+    /// UTC date the jump hotkey moves the clock to.
+    pub jump_date: String,
+    pub bindings: ClockBindings,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            start_date: "2000-01-01T12:00:00".to_string(),
+            sync_to_system: false,
+            time_scale: 1.0,
+            scale_factor: 10.0,
+            jump_date: "2000-01-01T12:00:00".to_string(),
+            bindings: ClockBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the simulation clock, as `KeyCode` variant names
+/// (e.g. `"Space"`, `"KeyR"`, `"F5"`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ClockBindings {
+    pub toggle_pause: String,
+    pub reverse: String,
+    pub step: String,
+    pub faster: String,
+    pub slower: String,
+    pub jump: String,
+    pub sync: String,
+}
+
+impl Default for ClockBindings {
+    fn default() -> Self {
+        Self {
+            toggle_pause: "Space".to_string(),
+            reverse: "KeyR".to_string(),
+            step: "Period".to_string(),
+            faster: "Equal".to_string(),
+            slower: "Minus".to_string(),
+            jump: "Home".to_string(),
+            sync: "KeyN".to_string(),
+        }
+    }
+}
+
+impl ClockBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 7] {
+        [
+            ("toggle_pause", &self.toggle_pause),
+            ("reverse", &self.reverse),
+            ("step", &self.step),
+            ("faster", &self.faster),
+            ("slower", &self.slower),
+            ("jump", &self.jump),
+            ("sync", &self.sync),
+        ]
+    }
+}
+
+/// Background star field drawn on the celestial sphere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct StarConfig {
+    /// Key of the star catalog in the asset manifest's `catalogs` section.
+    pub catalog: String,
+    /// Faintest apparent magnitude drawn. The bundled catalog holds only
+    /// named bright stars down to about 3.7; a fainter limit needs a fuller
+    /// extract such as HYG or the Yale Bright Star Catalog.
+    pub limiting_magnitude: f32,
+    /// Radius of the celestial sphere around the camera (m). Must be beyond
+    /// the outermost body, Neptune at about 4.5e12 m, and below the scenario
+    /// camera's far plane.
+    pub sphere_radius: f32,
+    /// Angular diameter of a star at the limiting magnitude (radians).
+    pub min_angular_size: f32,
+}
+
+impl Default for StarConfig {
+    fn default() -> Self {
+        Self {
+            catalog: "stars".to_string(),
+            limiting_magnitude: 3.7,
+            sphere_radius: 8.0e12,
+            min_angular_size: 0.0015,
+        }
+    }
+}
+
+/// Constellation overlay drawn over the star field.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SkyCultureConfig {
+    /// Directory of sky cultures, relative to the assets directory.
+    pub directory: String,
+    /// Culture shown at start-up; must be listed in `cultures`.
+    pub culture: String,
+    /// Cultures the cycle hotkey steps through, in order.
+    pub cultures: Vec<String>,
+    pub show_lines: bool,
+    pub show_boundaries: bool,
+    pub show_names: bool,
+    /// Line colour as linear RGBA.
+    pub line_color: [f32; 4],
+    /// Boundary colour as linear RGBA.
+    pub boundary_color: [f32; 4],
+    pub bindings: SkyBindings,
+}
+
+impl Default for SkyCultureConfig {
+    fn default() -> Self {
+        Self {
+            directory: "skycultures".to_string(),
+            culture: "western".to_string(),
+            cultures: vec!["western".to_string(), "chinese".to_string()],
+            show_lines: true,
+            show_boundaries: false,
+            show_names: true,
+            line_color: [0.3, 0.45, 0.8, 0.6],
+            boundary_color: [0.6, 0.4, 0.2, 0.4],
+            bindings: SkyBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the constellation overlay, as `KeyCode` variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SkyBindings {
+    pub cycle_culture: String,
+    pub toggle_lines: String,
+    pub toggle_boundaries: String,
+    pub toggle_names: String,
+}
+
+impl Default for SkyBindings {
+    fn default() -> Self {
+        Self {
+            cycle_culture: "KeyC".to_string(),
+            toggle_lines: "KeyL".to_string(),
+            toggle_boundaries: "KeyB".to_string(),
+            toggle_names: "KeyJ".to_string(),
+        }
+    }
+}
+
+impl SkyBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 4] {
+        [
+            ("cycle_culture", &self.cycle_culture),
+            ("toggle_lines", &self.toggle_lines),
+            ("toggle_boundaries", &self.toggle_boundaries),
+            ("toggle_names", &self.toggle_names),
+        ]
+    }
+}
+
+/// Orbit trails and predicted trajectories.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct OrbitDisplayConfig {
+    pub show_trails: bool,
+    pub show_predictions: bool,
+    /// Samples kept per trail; the oldest is overwritten first.
+    pub trail_length: u32,
+    /// Simulated time between trail samples (s).
+    pub trail_spacing: f64,
+    /// Simulated time predicted ahead (s).
+    pub prediction_horizon: f64,
+    /// Points along each predicted path.
+    pub prediction_points: u32,
+    /// Real time between prediction refreshes (s).
+    pub prediction_interval: f32,
+    /// Scenario name of the body paths are drawn relative to; empty for the
+    /// scene frame.
+    pub reference: String,
+    /// Prediction line colour as linear RGBA.
+    pub prediction_color: [f32; 4],
+    pub bindings: OrbitBindings,
+}
+
+impl Default for OrbitDisplayConfig {
+    fn default() -> Self {
+        Self {
+            show_trails: true,
+            show_predictions: true,
+            trail_length: 512,
+            trail_spacing: 3600.0,
+            prediction_horizon: 2.592e6,
+            prediction_points: 256,
+            prediction_interval: 0.5,
+            reference: String::new(),
+            prediction_color: [0.9, 0.9, 0.4, 0.5],
+            bindings: OrbitBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for orbit trails and predictions, as `KeyCode` variant
+/// names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct OrbitBindings {
+    pub toggle_trails: String,
+    pub toggle_predictions: String,
+    pub cycle_reference: String,
+}
+
+impl Default for OrbitBindings {
+    fn default() -> Self {
+        Self {
+            toggle_trails: "KeyT".to_string(),
+            toggle_predictions: "KeyP".to_string(),
+            cycle_reference: "KeyK".to_string(),
+        }
+    }
+}
+
+impl OrbitBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 3] {
+        [
+            ("toggle_trails", &self.toggle_trails),
+            ("toggle_predictions", &self.toggle_predictions),
+            ("cycle_reference", &self.cycle_reference),
+        ]
+    }
+}
+
+/// Orbit camera of the orbital view.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CameraConfig {
+    /// Closest the camera may get to the point it orbits (m).
+    pub min_distance: f64,
+    /// Farthest the camera may get from the point it orbits (m).
+    pub max_distance: f64,
+    /// Distance from a newly focused body, in body radii.
+    pub focus_radii: f64,
+    /// Turn per pixel of mouse drag at sensitivity 1 (radians).
+    pub rotate_speed: f32,
+    /// Pan per pixel of mouse drag at sensitivity 1, as a fraction of the
+    /// distance.
+    pub pan_speed: f32,
+    /// Change of `ln(distance)` per wheel line.
+    pub zoom_step: f32,
+    /// Duration of the fly-to transition on focus change (s).
+    pub fly_duration: f32,
+    pub bindings: CameraBindings,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            min_distance: 1.0e3,
+            max_distance: 1.5e13,
+            focus_radii: 4.0,
+            rotate_speed: 0.005,
+            pan_speed: 0.002,
+            zoom_step: 0.15,
+            fly_duration: 1.5,
+            bindings: CameraBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the orbit camera, as `KeyCode` variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CameraBindings {
+    pub cycle_focus: String,
+    pub clear_focus: String,
+}
+
+impl Default for CameraBindings {
+    fn default() -> Self {
+        Self {
+            cycle_focus: "KeyF".to_string(),
+            clear_focus: "Backspace".to_string(),
+        }
+    }
+}
+
+impl CameraBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 2] {
+        [
+            ("cycle_focus", &self.cycle_focus),
+            ("clear_focus", &self.clear_focus),
+        ]
+    }
+}
+
+/// Heads-up display: body selection and the info panel.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct HudConfig {
+    /// Show the HUD when entering the game.
+    pub visible: bool,
+    /// Clicks within this many pixels of a body's centre select it when no
+    /// mesh is hit, so bodies smaller than a pixel can still be picked.
+    pub pick_radius: f32,
+    /// Width of the info panel (px).
+    pub panel_width: f32,
+    pub bindings: HudBindings,
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            pick_radius: 12.0,
+            panel_width: 280.0,
+            bindings: HudBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the HUD, as `KeyCode` variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct HudBindings {
+    pub toggle_hud: String,
+}
+
+impl Default for HudBindings {
+    fn default() -> Self {
+        Self {
+            toggle_hud: "KeyH".to_string(),
+        }
+    }
+}
+
+impl HudBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 1] {
+        [("toggle_hud", &self.toggle_hud)]
+    }
+}
+
+/// Rendered-scale remapping of the scene. Only what is drawn changes;
+/// simulation state stays at true scale.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ViewScaleConfig {
+    /// Distance remapping used when entering the game.
+    pub mode: ScaleMode,
+    /// Distances from the camera focus up to this stay at true scale in
+    /// every mode; only farther ones are compressed (m).
+    pub reference_distance: f64,
+    /// Factor body radii are drawn at when entering the game.
+    pub radius_inflation: f64,
+    /// Factor each inflate or deflate press multiplies the inflation by.
+    pub inflation_step: f64,
+    pub max_radius_inflation: f64,
+    /// Length of the animated blend between scales (s); 0 switches at once.
+    pub blend_duration: f32,
+    pub bindings: ViewScaleBindings,
+}
+
+/// How rendered distances `d` from the camera focus follow true distances
+/// beyond the reference distance `d₀`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScaleMode {
+    /// True scale everywhere.
+    #[default]
+    Linear,
+    /// `d₀·(1 + ln(d/d₀))`.
+    Logarithmic,
+    /// `√(d·d₀)`.
+    SquareRoot,
+}
+
+impl ScaleMode {
+    pub const ALL: [Self; 3] = [Self::Linear, Self::Logarithmic, Self::SquareRoot];
+
+    /// The mode after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Fluent key of the HUD scale label.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Linear => "scale-linear",
+            Self::Logarithmic => "scale-logarithmic",
+            Self::SquareRoot => "scale-square-root",
+        }
+    }
+}
+
+impl Default for ViewScaleConfig {
+    fn default() -> Self {
+        Self {
+            mode: ScaleMode::default(),
+            reference_distance: 1.0e9,
+            radius_inflation: 1.0,
+            inflation_step: 2.0,
+            max_radius_inflation: 1000.0,
+            blend_duration: 1.0,
+            bindings: ViewScaleBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the view scale, as `KeyCode` variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ViewScaleBindings {
+    pub cycle_mode: String,
+    pub inflate_radii: String,
+    pub deflate_radii: String,
+}
+
+impl Default for ViewScaleBindings {
+    fn default() -> Self {
+        Self {
+            cycle_mode: "KeyV".to_string(),
+            inflate_radii: "BracketRight".to_string(),
+            deflate_radii: "BracketLeft".to_string(),
+        }
+    }
+}
+
+impl ViewScaleBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 3] {
+        [
+            ("cycle_mode", &self.cycle_mode),
+            ("inflate_radii", &self.inflate_radii),
+            ("deflate_radii", &self.deflate_radii),
+        ]
+    }
+}
+
+/// Spacecraft spawned by the user and their maneuver planning.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SpacecraftConfig {
+    /// Mass (kg).
+    pub mass: f64,
+    /// Radius (m).
+    pub radius: f64,
+    /// Spacecraft start on a circular orbit this many parent radii from the
+    /// parent's centre. Low orbits need closer prediction points than the
+    /// default horizon gives to be drawn smoothly.
+    pub orbit_radii: f64,
+    /// Delta-v each spacecraft carries (m/s).
+    pub delta_v_budget: f64,
+    /// Thrust acceleration of finite burns (m/s²).
+    pub max_acceleration: f64,
+    pub burn_mode: BurnMode,
+    /// New nodes go this long after the clock or the last node (s).
+    pub node_lead: f64,
+    /// Time each later or earlier press moves the selected node by (s).
+    pub node_time_step: f64,
+    /// Delta-v each burn-direction press adds (m/s).
+    pub delta_v_step: f64,
+    /// Spacecraft and node marker colour as linear RGBA.
+    pub color: [f32; 4],
+    pub bindings: SpacecraftBindings,
+}
+
+/// How maneuver nodes are carried out.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnMode {
+    /// The whole delta-v at the node time.
+    #[default]
+    Impulsive,
+    /// At `max_acceleration`, centred on the node time.
+    Finite,
+}
+
+impl Default for SpacecraftConfig {
+    fn default() -> Self {
+        Self {
+            mass: 1000.0,
+            radius: 5.0,
+            orbit_radii: 10.0,
+            delta_v_budget: 4000.0,
+            max_acceleration: 1.0,
+            burn_mode: BurnMode::default(),
+            node_lead: 3600.0,
+            node_time_step: 600.0,
+            delta_v_step: 10.0,
+            color: [0.3, 0.9, 1.0, 1.0],
+            bindings: SpacecraftBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for spacecraft and maneuver nodes, as `KeyCode`
+/// variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SpacecraftBindings {
+    pub spawn: String,
+    pub add_node: String,
+    pub remove_node: String,
+    pub next_node: String,
+    pub prograde: String,
+    pub retrograde: String,
+    pub normal: String,
+    pub anti_normal: String,
+    pub radial_out: String,
+    pub radial_in: String,
+    pub later: String,
+    pub earlier: String,
+    pub toggle_execution: String,
+}
+
+impl Default for SpacecraftBindings {
+    fn default() -> Self {
+        Self {
+            spawn: "KeyX".to_string(),
+            add_node: "KeyU".to_string(),
+            remove_node: "Delete".to_string(),
+            next_node: "Tab".to_string(),
+            prograde: "KeyW".to_string(),
+            retrograde: "KeyS".to_string(),
+            normal: "KeyE".to_string(),
+            anti_normal: "KeyQ".to_string(),
+            radial_out: "KeyD".to_string(),
+            radial_in: "KeyA".to_string(),
+            later: "PageUp".to_string(),
+            earlier: "PageDown".to_string(),
+            toggle_execution: "Enter".to_string(),
+        }
+    }
+}
+
+impl SpacecraftBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 13] {
+        [
+            ("spawn", &self.spawn),
+            ("add_node", &self.add_node),
+            ("remove_node", &self.remove_node),
+            ("next_node", &self.next_node),
+            ("prograde", &self.prograde),
+            ("retrograde", &self.retrograde),
+            ("normal", &self.normal),
+            ("anti_normal", &self.anti_normal),
+            ("radial_out", &self.radial_out),
+            ("radial_in", &self.radial_in),
+            ("later", &self.later),
+            ("earlier", &self.earlier),
+            ("toggle_execution", &self.toggle_execution),
+        ]
+    }
+}
+
+/// Observer-on-surface view.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ObserverConfig {
+    /// Scenario name of the body the observer stands on.
+    pub body: String,
+    /// Geodetic latitude in degrees, north positive.
+    pub latitude: f64,
+    /// Longitude in degrees, east positive.
+    pub longitude: f64,
+    /// Eye height above the surface (m).
+    pub eye_height: f64,
+    /// Radius of the ground disc around the observer (m).
+    pub ground_radius: f32,
+    /// Ground colour as linear RGBA.
+    pub ground_color: [f32; 4],
+    /// Alt-az grid colour as linear RGBA.
+    pub grid_color: [f32; 4],
+    /// Show the alt-az grid when entering the view.
+    pub show_grid: bool,
+    /// Turn rate of the look keys (radians per second).
+    pub look_speed: f32,
+    pub bindings: ObserverBindings,
+}
+
+impl Default for ObserverConfig {
+    fn default() -> Self {
+        Self {
+            body: "earth".to_string(),
+            latitude: 51.4769,
+            longitude: -0.0005,
+            eye_height: 1.7,
+            ground_radius: 5.0e4,
+            ground_color: [0.02, 0.03, 0.02, 1.0],
+            grid_color: [0.2, 0.6, 0.3, 0.5],
+            show_grid: false,
+            look_speed: 0.8,
+            bindings: ObserverBindings::default(),
+        }
+    }
+}
+
+/// Keyboard bindings for the observer view, as `KeyCode` variant names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ObserverBindings {
+    pub toggle_view: String,
+    pub toggle_grid: String,
+    pub look_left: String,
+    pub look_right: String,
+    pub look_up: String,
+    pub look_down: String,
+}
+
+impl Default for ObserverBindings {
+    fn default() -> Self {
+        Self {
+            toggle_view: "KeyO".to_string(),
+            toggle_grid: "KeyG".to_string(),
+            look_left: "ArrowLeft".to_string(),
+            look_right: "ArrowRight".to_string(),
+            look_up: "ArrowUp".to_string(),
+            look_down: "ArrowDown".to_string(),
+        }
+    }
+}
+
+impl ObserverBindings {
+    /// Every binding paired with its field name.
+    pub fn entries(&self) -> [(&'static str, &str); 6] {
+        [
+            ("toggle_view", &self.toggle_view),
+            ("toggle_grid", &self.toggle_grid),
+            ("look_left", &self.look_left),
+            ("look_right", &self.look_right),
+            ("look_up", &self.look_up),
+            ("look_down", &self.look_down),
+        ]
+    }
+}
+
+/// Planetary theory used to place the Sun and planets.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EphemerisConfig {
+    /// Directory of VSOP87D coefficient files, relative to the assets directory.
+    pub directory: String,
+    /// Terms with a smaller amplitude are dropped (radians for L and B, AU
+    /// for R). Zero keeps every term in the files.
+    pub min_amplitude: f64,
+    /// Keep at most this many of the largest terms per series.
+    pub max_terms: Option<usize>,
+}
+
+impl Default for EphemerisConfig {
+    fn default() -> Self {
+        Self {
+            directory: "ephemeris".to_string(),
+            min_amplitude: 0.0,
+            max_terms: None,
+        }
+    }
+}
+
 /// Global resource for gameplay-related configuration.
-#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct GameplayConfig {
     pub physics: PhysicsConfig,
     pub planets: PlanetConfig,
+    pub collisions: CollisionConfig,
+    pub disruption: DisruptionConfig,
+    pub terrain: TerrainConfig,
+    pub clock: ClockConfig,
+    pub stars: StarConfig,
+    pub sky: SkyCultureConfig,
+    pub observer: ObserverConfig,
+    pub orbits: OrbitDisplayConfig,
+    pub camera: CameraConfig,
+    pub hud: HudConfig,
+    pub view_scale: ViewScaleConfig,
+    pub spacecraft: SpacecraftConfig,
+    pub ephemeris: EphemerisConfig,
+    /// Scenario file to build the world from, relative to the assets directory.
+    pub scenario: String,
+}
+
+impl Default for GameplayConfig {
+    fn default() -> Self {
+        Self {
+            physics: PhysicsConfig::default(),
+            planets: PlanetConfig::default(),
+            collisions: CollisionConfig::default(),
+            disruption: DisruptionConfig::default(),
+            terrain: TerrainConfig::default(),
+            clock: ClockConfig::default(),
+            stars: StarConfig::default(),
+            sky: SkyCultureConfig::default(),
+            observer: ObserverConfig::default(),
+            orbits: OrbitDisplayConfig::default(),
+            camera: CameraConfig::default(),
+            hud: HudConfig::default(),
+            view_scale: ViewScaleConfig::default(),
+            spacecraft: SpacecraftConfig::default(),
+            ephemeris: EphemerisConfig::default(),
+            scenario: "scenarios/earth_moon.ron".to_string(),
+        }
+    }
 }
 
 // Implementations of Validate moved to src/config/validation.rs for centralization.
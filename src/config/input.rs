@@ -0,0 +1,33 @@
+//! # Input Bindings
+//!
+//! Resolves key names from configuration files into `KeyCode`s.
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, TypeInfo, Typed, VariantInfo};
+
+/// Parses a `KeyCode` variant name such as `"Space"`, `"KeyR"` or `"F5"`.
+///
+/// Only unit variants are accepted; `Unidentified` keys cannot be bound.
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let TypeInfo::Enum(info) = KeyCode::type_info() else {
+        return None;
+    };
+    match info.variant(name)? {
+        VariantInfo::Unit(_) => KeyCode::from_reflect(&DynamicEnum::new(name, ())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_variant_names() {
+        assert_eq!(parse_key_code("Space"), Some(KeyCode::Space));
+        assert_eq!(parse_key_code("KeyR"), Some(KeyCode::KeyR));
+        assert_eq!(parse_key_code("F5"), Some(KeyCode::F5));
+        assert_eq!(parse_key_code("space"), None);
+        assert_eq!(parse_key_code("Unidentified"), None);
+    }
+}
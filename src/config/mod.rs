@@ -3,6 +3,7 @@
 //! Handles game-specific settings and balance parameters.
 
 pub mod gameplay;
+pub mod input;
 pub mod validation;
 
 pub use gameplay::GameplayConfig;
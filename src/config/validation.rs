@@ -2,7 +2,12 @@
 //!
 //! Validation logic for game-specific balance parameters.
 
-use super::gameplay::GameplayConfig;
+use super::gameplay::{DensityModel, GameplayConfig};
+use super::input::parse_key_code;
+use crate::astro::time::Calendar;
+use crate::astro::{CalendarDate, JulianDate};
+use crate::constants::{MAX_TIME_SCALE, MIN_TIME_SCALE};
+use crate::procgen::terrain::{self, CUBE_FACES, MAX_TERRAIN_DEPTH, MAX_TERRAIN_RESOLUTION};
 use menu::settings::validation::Validate;
 
 impl Validate for GameplayConfig {
@@ -14,12 +19,299 @@ impl Validate for GameplayConfig {
         if self.physics.gravitational_constant <= 0.0 {
             errors.push("gravitational_constant must be positive".to_string());
         }
+        if !(self.physics.time_step.is_finite() && self.physics.time_step > 0.0) {
+            errors.push(format!(
+                "time_step {} must be positive and finite for the {:?} integrator",
+                self.physics.time_step, self.physics.integrator
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.physics.opening_angle) {
+            errors.push(format!(
+                "opening_angle {} out of range [0.0, 1.0]",
+                self.physics.opening_angle
+            ));
+        }
+        if !(self.physics.max_substep.is_finite() && self.physics.max_substep > 0.0) {
+            errors.push("max_substep must be positive and finite".to_string());
+        }
+        if self.physics.max_substeps == 0 {
+            errors.push("max_substeps must be at least 1".to_string());
+        }
         if self.physics.max_velocity <= 0.0 {
             errors.push("max_velocity must be positive".to_string());
         }
         if self.planets.min_radius >= self.planets.max_radius {
             errors.push("min_radius must be less than max_radius".to_string());
         }
+        if !(0.0..=1.0).contains(&self.collisions.restitution) {
+            errors.push(format!(
+                "collision restitution {} out of range [0.0, 1.0]",
+                self.collisions.restitution
+            ));
+        }
+        if let DensityModel::Uniform(density) = self.collisions.density
+            && !(density.is_finite() && density > 0.0)
+        {
+            errors.push("collision density must be positive and finite".to_string());
+        }
+        if self.disruption.fragments < 2 {
+            errors.push("disruption fragments must be at least 2".to_string());
+        }
+        if !(self.disruption.min_fragment_mass.is_finite()
+            && self.disruption.min_fragment_mass > 0.0)
+        {
+            errors.push("disruption min_fragment_mass must be positive and finite".to_string());
+        }
+        if !(self.disruption.velocity_spread.is_finite() && self.disruption.velocity_spread >= 0.0)
+        {
+            errors.push("disruption velocity_spread must be non-negative and finite".to_string());
+        }
+        let terrain = &self.terrain;
+        if !(3..=MAX_TERRAIN_RESOLUTION).contains(&terrain.resolution) {
+            errors.push(format!(
+                "terrain resolution must be between 3 and {MAX_TERRAIN_RESOLUTION}"
+            ));
+        } else if (terrain.vertex_budget as usize)
+            < CUBE_FACES * terrain::vertices_per_chunk(terrain.resolution)
+        {
+            errors.push("terrain vertex_budget cannot hold the six root chunks".to_string());
+        }
+        if terrain.max_depth > MAX_TERRAIN_DEPTH {
+            errors.push(format!(
+                "terrain max_depth must be at most {MAX_TERRAIN_DEPTH}"
+            ));
+        }
+        if !(terrain.split_distance.is_finite() && terrain.split_distance > 0.0) {
+            errors.push("terrain split_distance must be positive and finite".to_string());
+        }
+        if !(0.0..0.5).contains(&terrain.amplitude) {
+            errors.push("terrain amplitude must be within [0, 0.5)".to_string());
+        }
+        if !(terrain.frequency.is_finite() && terrain.frequency > 0.0) {
+            errors.push("terrain frequency must be positive and finite".to_string());
+        }
+        if !(1..=16).contains(&terrain.octaves) {
+            errors.push("terrain octaves must be between 1 and 16".to_string());
+        }
+        if !(terrain.lacunarity.is_finite() && terrain.lacunarity > 1.0) {
+            errors.push("terrain lacunarity must be greater than 1".to_string());
+        }
+        if !(terrain.persistence > 0.0 && terrain.persistence < 1.0) {
+            errors.push("terrain persistence must be within (0, 1)".to_string());
+        }
+        for (field, date) in [
+            ("start_date", &self.clock.start_date),
+            ("jump_date", &self.clock.jump_date),
+        ] {
+            let valid = date
+                .parse::<CalendarDate>()
+                .is_ok_and(|date| JulianDate::from_calendar(&date, Calendar::Reform).is_ok());
+            if !valid {
+                errors.push(format!("clock {field} {date:?} is not a valid date"));
+            }
+        }
+        if !(MIN_TIME_SCALE..=MAX_TIME_SCALE).contains(&self.clock.time_scale) {
+            errors.push(format!(
+                "time_scale {} out of range [{MIN_TIME_SCALE}, {MAX_TIME_SCALE}]",
+                self.clock.time_scale
+            ));
+        }
+        if !(self.clock.scale_factor.is_finite() && self.clock.scale_factor > 1.0) {
+            errors.push("scale_factor must be greater than 1".to_string());
+        }
+        for (action, key) in self.clock.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("clock binding {action} has unknown key {key:?}"));
+            }
+        }
+        if !self.stars.limiting_magnitude.is_finite() {
+            errors.push("limiting_magnitude must be finite".to_string());
+        }
+        if !(self.stars.sphere_radius.is_finite() && self.stars.sphere_radius > 0.0) {
+            errors.push("sphere_radius must be positive and finite".to_string());
+        }
+        if !(self.stars.min_angular_size > 0.0 && self.stars.min_angular_size < 0.1) {
+            errors.push(format!(
+                "min_angular_size {} out of range (0.0, 0.1)",
+                self.stars.min_angular_size
+            ));
+        }
+        if self.sky.directory.is_empty() {
+            errors.push("sky directory must not be empty".to_string());
+        }
+        if !self.sky.cultures.contains(&self.sky.culture) {
+            errors.push(format!(
+                "sky culture {:?} is not listed in cultures",
+                self.sky.culture
+            ));
+        }
+        for (action, key) in self.sky.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("sky binding {action} has unknown key {key:?}"));
+            }
+        }
+        if self.observer.body.is_empty() {
+            errors.push("observer body must not be empty".to_string());
+        }
+        if !(-90.0..=90.0).contains(&self.observer.latitude) {
+            errors.push(format!(
+                "observer latitude {} out of range [-90, 90]",
+                self.observer.latitude
+            ));
+        }
+        if !(-180.0..=180.0).contains(&self.observer.longitude) {
+            errors.push(format!(
+                "observer longitude {} out of range [-180, 180]",
+                self.observer.longitude
+            ));
+        }
+        if !(self.observer.eye_height.is_finite() && self.observer.eye_height > 0.0) {
+            errors.push("observer eye_height must be positive and finite".to_string());
+        }
+        if f64::from(self.observer.ground_radius) <= self.observer.eye_height * 100.0 {
+            errors.push("observer ground_radius must exceed 100 eye heights".to_string());
+        }
+        if !(self.observer.look_speed.is_finite() && self.observer.look_speed > 0.0) {
+            errors.push("observer look_speed must be positive and finite".to_string());
+        }
+        for (action, key) in self.observer.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("observer binding {action} has unknown key {key:?}"));
+            }
+        }
+        let orbits = &self.orbits;
+        if orbits.trail_length < 2 {
+            errors.push("orbit trail_length must be at least 2".to_string());
+        }
+        if !(orbits.trail_spacing.is_finite() && orbits.trail_spacing > 0.0) {
+            errors.push("orbit trail_spacing must be positive and finite".to_string());
+        }
+        if !(orbits.prediction_horizon.is_finite() && orbits.prediction_horizon > 0.0) {
+            errors.push("orbit prediction_horizon must be positive and finite".to_string());
+        }
+        if orbits.prediction_points < 2 {
+            errors.push("orbit prediction_points must be at least 2".to_string());
+        }
+        if !(orbits.prediction_interval.is_finite() && orbits.prediction_interval > 0.0) {
+            errors.push("orbit prediction_interval must be positive and finite".to_string());
+        }
+        for (action, key) in orbits.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("orbit binding {action} has unknown key {key:?}"));
+            }
+        }
+
+        let camera = &self.camera;
+        if !(camera.min_distance.is_finite() && camera.min_distance > 0.0) {
+            errors.push("camera min_distance must be positive and finite".to_string());
+        }
+        if !(camera.max_distance.is_finite() && camera.max_distance > camera.min_distance) {
+            errors.push("camera max_distance must be finite and above min_distance".to_string());
+        }
+        if !(camera.focus_radii.is_finite() && camera.focus_radii > 1.0) {
+            errors.push("camera focus_radii must be finite and above 1".to_string());
+        }
+        for (name, value) in [
+            ("rotate_speed", camera.rotate_speed),
+            ("pan_speed", camera.pan_speed),
+            ("zoom_step", camera.zoom_step),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("camera {name} must be positive and finite"));
+            }
+        }
+        if !(camera.fly_duration.is_finite() && camera.fly_duration >= 0.0) {
+            errors.push("camera fly_duration must be non-negative and finite".to_string());
+        }
+        for (action, key) in camera.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("camera binding {action} has unknown key {key:?}"));
+            }
+        }
+
+        let hud = &self.hud;
+        if !(hud.pick_radius.is_finite() && hud.pick_radius >= 0.0) {
+            errors.push("hud pick_radius must be non-negative and finite".to_string());
+        }
+        if !(hud.panel_width.is_finite() && hud.panel_width > 0.0) {
+            errors.push("hud panel_width must be positive and finite".to_string());
+        }
+        for (action, key) in hud.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!("hud binding {action} has unknown key {key:?}"));
+            }
+        }
+
+        let scale = &self.view_scale;
+        if !(scale.reference_distance.is_finite() && scale.reference_distance > 0.0) {
+            errors.push("view_scale reference_distance must be positive and finite".to_string());
+        }
+        if !(scale.max_radius_inflation.is_finite() && scale.max_radius_inflation >= 1.0) {
+            errors
+                .push("view_scale max_radius_inflation must be finite and at least 1".to_string());
+        }
+        if !(scale.radius_inflation >= 1.0 && scale.radius_inflation <= scale.max_radius_inflation)
+        {
+            errors.push(
+                "view_scale radius_inflation must lie between 1 and max_radius_inflation"
+                    .to_string(),
+            );
+        }
+        if !(scale.inflation_step.is_finite() && scale.inflation_step > 1.0) {
+            errors.push("view_scale inflation_step must be finite and above 1".to_string());
+        }
+        if !(scale.blend_duration.is_finite() && scale.blend_duration >= 0.0) {
+            errors.push("view_scale blend_duration must be non-negative and finite".to_string());
+        }
+        for (action, key) in scale.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!(
+                    "view_scale binding {action} has unknown key {key:?}"
+                ));
+            }
+        }
+        let craft = &self.spacecraft;
+        for (name, value) in [
+            ("mass", craft.mass),
+            ("radius", craft.radius),
+            ("max_acceleration", craft.max_acceleration),
+            ("node_time_step", craft.node_time_step),
+            ("delta_v_step", craft.delta_v_step),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                errors.push(format!("spacecraft {name} must be positive and finite"));
+            }
+        }
+        if !(craft.orbit_radii.is_finite() && craft.orbit_radii > 1.0) {
+            errors.push("spacecraft orbit_radii must be finite and above 1".to_string());
+        }
+        for (name, value) in [
+            ("delta_v_budget", craft.delta_v_budget),
+            ("node_lead", craft.node_lead),
+        ] {
+            if !(value.is_finite() && value >= 0.0) {
+                errors.push(format!("spacecraft {name} must be non-negative and finite"));
+            }
+        }
+        for (action, key) in craft.bindings.entries() {
+            if parse_key_code(key).is_none() {
+                errors.push(format!(
+                    "spacecraft binding {action} has unknown key {key:?}"
+                ));
+            }
+        }
+        if self.ephemeris.directory.is_empty() {
+            errors.push("ephemeris directory must not be empty".to_string());
+        }
+        if !(self.ephemeris.min_amplitude.is_finite() && self.ephemeris.min_amplitude >= 0.0) {
+            errors.push("ephemeris min_amplitude must be non-negative and finite".to_string());
+        }
+        if self.ephemeris.max_terms == Some(0) {
+            errors.push("ephemeris max_terms must be at least 1".to_string());
+        }
+        if self.scenario.is_empty() {
+            errors.push("scenario path must not be empty".to_string());
+        }
 
         if errors.is_empty() {
             Ok(())
@@ -45,4 +337,141 @@ mod tests {
         config.physics.gravitational_constant = -1.0;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn invalid_gameplay_config_time_step() {
+        let mut config = GameplayConfig::default();
+        config.physics.time_step = 0.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_gameplay_config_opening_angle() {
+        let mut config = GameplayConfig::default();
+        config.physics.opening_angle = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_gameplay_config_collisions() {
+        let mut config = GameplayConfig::default();
+        config.collisions.restitution = 1.5;
+        config.collisions.density = DensityModel::Uniform(0.0);
+        assert_eq!(config.validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_disruption() {
+        let mut config = GameplayConfig::default();
+        config.disruption.fragments = 1;
+        config.disruption.min_fragment_mass = 0.0;
+        config.disruption.velocity_spread = f64::NAN;
+        assert_eq!(config.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_terrain() {
+        let mut config = GameplayConfig::default();
+        config.terrain.vertex_budget = 100;
+        config.terrain.max_depth = 40;
+        config.terrain.amplitude = -0.1;
+        config.terrain.persistence = 1.0;
+        assert_eq!(config.validate().unwrap_err().len(), 4);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_clock() {
+        let mut config = GameplayConfig::default();
+        config.clock.start_date = "2000-02-30T25:00:00".to_string();
+        config.clock.jump_date = "1582-10-10".to_string();
+        config.clock.bindings.reverse = "NotAKey".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_sky() {
+        let mut config = GameplayConfig::default();
+        config.sky.culture = "norse".to_string();
+        config.sky.bindings.toggle_names = "".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_observer() {
+        let mut config = GameplayConfig::default();
+        config.observer.latitude = 91.0;
+        config.observer.ground_radius = 10.0;
+        config.observer.bindings.look_up = "Up".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_orbits() {
+        let mut config = GameplayConfig::default();
+        config.orbits.trail_length = 1;
+        config.orbits.trail_spacing = 0.0;
+        config.orbits.prediction_interval = f32::INFINITY;
+        config.orbits.bindings.cycle_reference = "K".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 4);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_camera() {
+        let mut config = GameplayConfig::default();
+        config.camera.max_distance = config.camera.min_distance;
+        config.camera.focus_radii = 0.5;
+        config.camera.zoom_step = 0.0;
+        config.camera.fly_duration = -1.0;
+        config.camera.bindings.cycle_focus = "F".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 5);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_hud() {
+        let mut config = GameplayConfig::default();
+        config.hud.pick_radius = f32::NAN;
+        config.hud.panel_width = 0.0;
+        config.hud.bindings.toggle_hud = "H".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_view_scale() {
+        let mut config = GameplayConfig::default();
+        config.view_scale.reference_distance = 0.0;
+        config.view_scale.radius_inflation = 0.5;
+        config.view_scale.inflation_step = 1.0;
+        config.view_scale.blend_duration = f32::INFINITY;
+        config.view_scale.bindings.cycle_mode = "V".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 5);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_spacecraft() {
+        let mut config = GameplayConfig::default();
+        config.spacecraft.mass = 0.0;
+        config.spacecraft.max_acceleration = f64::NAN;
+        config.spacecraft.orbit_radii = 1.0;
+        config.spacecraft.delta_v_budget = -1.0;
+        config.spacecraft.bindings.add_node = "U".to_string();
+        assert_eq!(config.validate().unwrap_err().len(), 5);
+    }
+
+    #[test]
+    fn invalid_gameplay_config_ephemeris() {
+        let mut config = GameplayConfig::default();
+        config.ephemeris.min_amplitude = -1.0e-8;
+        config.ephemeris.max_terms = Some(0);
+        assert_eq!(config.validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn integrator_parses_from_ron() {
+        use crate::config::gameplay::IntegratorKind;
+
+        let config: GameplayConfig =
+            ron::from_str("(physics: (integrator: yoshida4))").expect("config should parse");
+        assert_eq!(config.physics.integrator, IntegratorKind::Yoshida4);
+        assert!(config.validate().is_ok());
+    }
 }
@@ -4,3 +4,21 @@
 
 /// Default planet rotation speed (radians per second).
 pub const PLANET_ROTATION_SPEED: f32 = 0.3;
+
+/// Slowest simulation time-scale multiplier (real time).
+pub const MIN_TIME_SCALE: f64 = 1.0;
+
+/// Fastest simulation time-scale multiplier.
+pub const MAX_TIME_SCALE: f64 = 1.0e7;
+
+/// Seconds in one day.
+pub const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Mean obliquity of the ecliptic at J2000.0 (radians, 23°26′21.448″).
+pub const OBLIQUITY_J2000: f64 = 0.409_092_804_222_329;
+
+/// Astronomical unit in metres (IAU 2012).
+pub const ASTRONOMICAL_UNIT: f64 = 149_597_870_700.0;
+
+/// Mass of the Sun in kilograms.
+pub const SOLAR_MASS: f64 = 1.988_47e30;
@@ -3,7 +3,568 @@
 //! Functions for spawning game entities with predefined component bundles.
 //! Centralizes entity creation logic for consistency and reuse.
 
-// Future entity spawner functions will be added here.
-// Examples:
-//   - spawn_planet(commands, meshes, materials, position) -> Entity
-//   - spawn_asteroid(commands, position, velocity) -> Entity
+pub mod stars;
+
+use bevy::camera::PerspectiveProjection;
+use bevy::math::DVec3;
+use bevy::picking::mesh_picking::MeshPickingCamera;
+use bevy::prelude::*;
+
+use crate::astro::{Ephemeris, lunar};
+use crate::components::{
+    Acceleration, CelestialBody, EphemerisBody, Fragment, LunarBody, ManeuverPlan, Mass,
+    MeshRadius, OnRails, OrbitParent, OrbitalElements, Radius, Rotates, RotationPeriod, Spacecraft,
+    Surface, Velocity, WorldPosition,
+};
+use crate::config::gameplay::SpacecraftConfig;
+use crate::physics::collision::Body;
+use crate::scenario::{BodySpec, CameraSpec, LightSpec, MotionSpec, OrbitSpec, Scenario};
+
+/// Asset stores used to build body meshes and materials.
+pub struct BodyAssets<'a> {
+    pub meshes: &'a mut Assets<Mesh>,
+    pub materials: &'a mut Assets<StandardMaterial>,
+    pub asset_server: &'a AssetServer,
+}
+
+/// Absolute initial state of a scenario body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InitialState {
+    pub position: DVec3,
+    pub velocity: DVec3,
+}
+
+/// Spawns every body, light and the camera of `scenario` under `root`.
+///
+/// `Ephemeris` and `Lunar` bodies start where their theory puts them at
+/// `epoch`.
+pub fn spawn_scenario(
+    commands: &mut Commands,
+    assets: &mut BodyAssets,
+    scenario: &Scenario,
+    gravitational_constant: f64,
+    ephemeris: &Ephemeris,
+    epoch: f64,
+    root: Entity,
+) {
+    let states = initial_states(scenario, gravitational_constant, ephemeris, epoch);
+    let mut entities: Vec<Entity> = Vec::with_capacity(scenario.bodies.len());
+
+    for (spec, state) in scenario.bodies.iter().zip(states) {
+        let parent = spec
+            .parent
+            .as_ref()
+            .and_then(|name| scenario.bodies.iter().position(|b| &b.name == name));
+        let parent = parent.map(|index| (entities[index], &scenario.bodies[index]));
+
+        let entity = spawn_body(commands, assets, spec, state, root);
+        let theory_rails = OnRails {
+            primary: parent.map(|(parent_entity, _)| parent_entity),
+            gravitational_parameter: gravitational_constant
+                * (parent.map_or(0.0, |(_, parent_spec)| parent_spec.mass) + spec.mass),
+        };
+        match spec.motion {
+            MotionSpec::Ephemeris(planet) => {
+                commands
+                    .entity(entity)
+                    .insert((EphemerisBody(planet), theory_rails));
+            }
+            MotionSpec::Lunar => {
+                commands.entity(entity).insert((LunarBody, theory_rails));
+            }
+            MotionSpec::State { .. } if spec.on_rails => {
+                commands.entity(entity).insert(OnRails {
+                    primary: None,
+                    gravitational_parameter: gravitational_constant * spec.mass,
+                });
+            }
+            MotionSpec::State { .. } | MotionSpec::Orbit(_) => {}
+        }
+        if let Some((parent_entity, parent_spec)) = parent {
+            commands.entity(entity).insert(OrbitParent(parent_entity));
+            if spec.on_rails
+                && let MotionSpec::Orbit(orbit) = &spec.motion
+            {
+                commands.entity(entity).insert((
+                    orbital_elements(orbit),
+                    OnRails {
+                        primary: Some(parent_entity),
+                        gravitational_parameter: gravitational_constant
+                            * (parent_spec.mass + spec.mass),
+                    },
+                ));
+            }
+        }
+        entities.push(entity);
+    }
+
+    for light in &scenario.lights {
+        spawn_light(commands, light, root);
+    }
+    spawn_camera(commands, &scenario.camera, root);
+}
+
+/// Spawns a single body with its mesh, material and physics components.
+pub fn spawn_body(
+    commands: &mut Commands,
+    assets: &mut BodyAssets,
+    spec: &BodySpec,
+    state: InitialState,
+    root: Entity,
+) -> Entity {
+    let color = parse_color(&spec.color);
+    let material = assets.materials.add(StandardMaterial {
+        base_color: color,
+        base_color_texture: spec
+            .texture
+            .as_ref()
+            .map(|path| assets.asset_server.load(path.clone())),
+        emissive: color.to_linear() * spec.emissive,
+        perceptual_roughness: 0.8,
+        ..default()
+    });
+
+    let mut body = commands.spawn((
+        Name::new(spec.name.clone()),
+        CelestialBody,
+        WorldPosition(state.position),
+        Transform::from_rotation(Quat::from_rotation_x(spec.axial_tilt as f32)),
+        Visibility::default(),
+        MeshMaterial3d(material),
+        Mass(spec.mass),
+        Radius(spec.radius),
+        MeshRadius(spec.radius),
+        Velocity(state.velocity),
+        Acceleration::default(),
+        Pickable::default(),
+        ChildOf(root),
+    ));
+
+    match Sphere::new(spec.radius as f32).mesh().ico(5) {
+        Ok(mesh) => {
+            body.insert(Mesh3d(assets.meshes.add(mesh)));
+        }
+        Err(e) => warn!("[Scenario] Failed to build mesh for '{}': {}", spec.name, e),
+    }
+
+    if let Some(period) = spec.rotation_period {
+        body.insert((Rotates, RotationPeriod(period)));
+    }
+    if let Some(surface) = &spec.surface {
+        body.insert(Surface {
+            class: surface.class,
+            albedo: surface.albedo,
+            palette: surface.palette.iter().map(|hex| parse_color(hex)).collect(),
+            seed: surface.seed,
+        });
+    }
+
+    body.id()
+}
+
+/// Spawns one fragment of a disrupted satellite next to `primary`, sharing
+/// the satellite's material.
+pub fn spawn_fragment(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    material: Option<Handle<StandardMaterial>>,
+    name: String,
+    fragment: &Body,
+    primary: Entity,
+    root: Option<Entity>,
+) -> Entity {
+    let mut entity = commands.spawn((
+        Name::new(name),
+        CelestialBody,
+        Fragment,
+        WorldPosition(fragment.position),
+        Transform::default(),
+        Visibility::default(),
+        Mass(fragment.mass),
+        Radius(fragment.radius),
+        MeshRadius(fragment.radius),
+        Velocity(fragment.velocity),
+        Acceleration::default(),
+        Pickable::default(),
+        OrbitParent(primary),
+    ));
+    if let Ok(mesh) = Sphere::new(fragment.radius as f32).mesh().ico(2) {
+        entity.insert(Mesh3d(meshes.add(mesh)));
+    }
+    if let Some(material) = material {
+        entity.insert(MeshMaterial3d(material));
+    }
+    if let Some(root) = root {
+        entity.insert(ChildOf(root));
+    }
+    entity.id()
+}
+
+/// Spawns a spacecraft with an empty maneuver plan at `state`, orbiting
+/// `parent`.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_spacecraft(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    name: String,
+    config: &SpacecraftConfig,
+    state: InitialState,
+    parent: Entity,
+    root: Option<Entity>,
+) -> Entity {
+    let [r, g, b, a] = config.color;
+    let mut entity = commands.spawn((
+        Name::new(name),
+        CelestialBody,
+        Spacecraft::new(config.delta_v_budget),
+        ManeuverPlan::default(),
+        WorldPosition(state.position),
+        Transform::default(),
+        Visibility::default(),
+        Mass(config.mass),
+        Radius(config.radius),
+        MeshRadius(config.radius),
+        Velocity(state.velocity),
+        Acceleration::default(),
+        Pickable::default(),
+        OrbitParent(parent),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::from(LinearRgba::new(r, g, b, a)),
+            unlit: true,
+            ..default()
+        })),
+    ));
+    if let Ok(mesh) = Sphere::new(config.radius as f32).mesh().ico(1) {
+        entity.insert(Mesh3d(meshes.add(mesh)));
+    }
+    if let Some(root) = root {
+        entity.insert(ChildOf(root));
+    }
+    entity.id()
+}
+
+/// Spawns a light source.
+pub fn spawn_light(commands: &mut Commands, spec: &LightSpec, root: Entity) -> Entity {
+    match spec {
+        LightSpec::Point {
+            position,
+            intensity,
+            range,
+            color,
+            shadows,
+        } => commands
+            .spawn((
+                PointLight {
+                    intensity: *intensity,
+                    range: *range,
+                    color: parse_color(color),
+                    shadows_enabled: *shadows,
+                    ..default()
+                },
+                WorldPosition(DVec3::from_array(*position)),
+                Transform::default(),
+                ChildOf(root),
+            ))
+            .id(),
+        LightSpec::Directional {
+            direction,
+            illuminance,
+            color,
+            shadows,
+        } => commands
+            .spawn((
+                DirectionalLight {
+                    illuminance: *illuminance,
+                    color: parse_color(color),
+                    shadows_enabled: *shadows,
+                    ..default()
+                },
+                Transform::default().looking_to(DVec3::from_array(*direction).as_vec3(), Vec3::Z),
+                ChildOf(root),
+            ))
+            .id(),
+    }
+}
+
+/// Spawns the 3D camera.
+pub fn spawn_camera(commands: &mut Commands, spec: &CameraSpec, root: Entity) -> Entity {
+    let position = DVec3::from_array(spec.position).as_vec3();
+    let target = DVec3::from_array(spec.target).as_vec3();
+    let up = DVec3::from_array(spec.up).as_vec3();
+
+    commands
+        .spawn((
+            Camera3d::default(),
+            Projection::Perspective(PerspectiveProjection {
+                fov: spec.fov.to_radians(),
+                far: spec.far,
+                ..default()
+            }),
+            Transform::from_translation(position).looking_at(target, up),
+            MeshPickingCamera,
+            ChildOf(root),
+        ))
+        .id()
+}
+
+/// Resolves absolute initial positions and velocities for every body.
+///
+/// Parent-relative states are accumulated in declaration order. With
+/// `zero_momentum`, the centre-of-mass velocity of the N-body bodies and the
+/// kinematic bodies below them is removed, so a planet gets the counter
+/// velocity of its moon on rails. Other kinematic bodies take no part.
+/// Planets missing from `ephemeris` start at their parent.
+pub fn initial_states(
+    scenario: &Scenario,
+    gravitational_constant: f64,
+    ephemeris: &Ephemeris,
+    epoch: f64,
+) -> Vec<InitialState> {
+    let mut states: Vec<InitialState> = Vec::with_capacity(scenario.bodies.len());
+
+    for spec in &scenario.bodies {
+        let parent = spec
+            .parent
+            .as_ref()
+            .and_then(|name| scenario.bodies.iter().position(|b| &b.name == name));
+
+        let (position, velocity) = match &spec.motion {
+            MotionSpec::State { position, velocity } => {
+                (DVec3::from_array(*position), DVec3::from_array(*velocity))
+            }
+            MotionSpec::Orbit(orbit) => {
+                let parent_mass = parent.map_or(0.0, |index| scenario.bodies[index].mass);
+                let mu = gravitational_constant * (parent_mass + spec.mass);
+                orbital_elements(orbit).state_at(mu, 0.0)
+            }
+            MotionSpec::Ephemeris(planet) => ephemeris
+                .state(*planet, epoch)
+                .unwrap_or((DVec3::ZERO, DVec3::ZERO)),
+            MotionSpec::Lunar => lunar::state(epoch),
+        };
+
+        let origin = parent.map_or(
+            InitialState {
+                position: DVec3::ZERO,
+                velocity: DVec3::ZERO,
+            },
+            |index| states[index],
+        );
+        states.push(InitialState {
+            position: origin.position + position,
+            velocity: origin.velocity + velocity,
+        });
+    }
+
+    if scenario.zero_momentum {
+        // Kinematic bodies follow their parents, so those below an N-body
+        // body move with it and count towards its momentum.
+        let mut moves: Vec<bool> = Vec::with_capacity(states.len());
+        for spec in &scenario.bodies {
+            let parent_moves = spec
+                .parent
+                .as_ref()
+                .and_then(|name| scenario.bodies.iter().position(|b| &b.name == name))
+                .and_then(|index| moves.get(index).copied())
+                .unwrap_or(false);
+            moves.push(!spec.is_kinematic() || parent_moves);
+        }
+        let (momentum, mass) = scenario
+            .bodies
+            .iter()
+            .zip(&states)
+            .zip(&moves)
+            .filter(|(_, moves)| **moves)
+            .fold((DVec3::ZERO, 0.0), |(p, m), ((spec, state), _)| {
+                (p + state.velocity * spec.mass, m + spec.mass)
+            });
+        if mass > 0.0 {
+            let drift = momentum / mass;
+            for (state, _) in states.iter_mut().zip(&moves).filter(|(_, moves)| **moves) {
+                state.velocity -= drift;
+            }
+        }
+    }
+
+    states
+}
+
+fn orbital_elements(orbit: &OrbitSpec) -> OrbitalElements {
+    OrbitalElements {
+        semi_major_axis: orbit.semi_major_axis,
+        eccentricity: orbit.eccentricity,
+        inclination: orbit.inclination,
+        longitude_of_ascending_node: orbit.longitude_of_ascending_node,
+        argument_of_periapsis: orbit.argument_of_periapsis,
+        mean_anomaly_at_epoch: orbit.mean_anomaly_at_epoch,
+        epoch: orbit.epoch,
+    }
+}
+
+fn parse_color(hex: &str) -> Color {
+    Srgba::hex(hex).map(Color::from).unwrap_or(Color::WHITE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(name: &str, mass: f64, parent: Option<&str>, motion: MotionSpec) -> BodySpec {
+        BodySpec {
+            name: name.to_string(),
+            mass,
+            radius: 1.0,
+            parent: parent.map(str::to_string),
+            motion,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn child_states_are_relative_to_parent() {
+        let scenario = Scenario {
+            bodies: vec![
+                body(
+                    "star",
+                    1.0,
+                    None,
+                    MotionSpec::State {
+                        position: [10.0, 0.0, 0.0],
+                        velocity: [0.0, 1.0, 0.0],
+                    },
+                ),
+                body(
+                    "planet",
+                    1.0e-3,
+                    Some("star"),
+                    MotionSpec::Orbit(OrbitSpec {
+                        semi_major_axis: 4.0,
+                        ..default()
+                    }),
+                ),
+            ],
+            ..default()
+        };
+
+        let states = initial_states(&scenario, 1.0, &Ephemeris::default(), 0.0);
+        assert_eq!(states[1].position, DVec3::new(14.0, 0.0, 0.0));
+        let orbital_speed = (1.001_f64 / 4.0).sqrt();
+        assert!((states[1].velocity - DVec3::new(0.0, 1.0 + orbital_speed, 0.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn zero_momentum_removes_drift() {
+        let scenario = Scenario {
+            zero_momentum: true,
+            bodies: vec![
+                body("a", 3.0, None, MotionSpec::default()),
+                body(
+                    "b",
+                    1.0,
+                    Some("a"),
+                    MotionSpec::State {
+                        position: [1.0, 0.0, 0.0],
+                        velocity: [0.0, 4.0, 0.0],
+                    },
+                ),
+            ],
+            ..default()
+        };
+
+        let states = initial_states(&scenario, 1.0, &Ephemeris::default(), 0.0);
+        let momentum = states[0].velocity * 3.0 + states[1].velocity;
+        assert!(momentum.length() < 1e-12);
+    }
+
+    #[test]
+    fn ephemeris_bodies_start_at_their_theory() {
+        use crate::astro::Planet;
+        use crate::astro::vsop87::{PlanetTheory, Truncation};
+
+        let earth = PlanetTheory::parse(
+            Planet::Earth,
+            include_str!("../../assets/ephemeris/VSOP87D.ear"),
+            &Truncation::default(),
+        )
+        .unwrap();
+        let ephemeris = Ephemeris::from_theories(vec![earth]);
+        let scenario = Scenario {
+            zero_momentum: true,
+            bodies: vec![
+                body(
+                    "sun",
+                    1.0,
+                    None,
+                    MotionSpec::State {
+                        position: [1.0, 0.0, 0.0],
+                        velocity: [0.0; 3],
+                    },
+                ),
+                body(
+                    "earth",
+                    3.0e-6,
+                    Some("sun"),
+                    MotionSpec::Ephemeris(Planet::Earth),
+                ),
+            ],
+            ..default()
+        };
+
+        let states = initial_states(&scenario, 1.0, &ephemeris, 0.0);
+        let (position, _) = ephemeris.state(Planet::Earth, 0.0).unwrap();
+        assert_eq!(states[1].position, DVec3::X + position);
+    }
+
+    #[test]
+    fn lunar_bodies_start_at_the_moon() {
+        let scenario = Scenario {
+            bodies: vec![
+                body(
+                    "earth",
+                    1.0,
+                    None,
+                    MotionSpec::State {
+                        position: [0.0, 1.0, 0.0],
+                        velocity: [0.0; 3],
+                    },
+                ),
+                body("moon", 0.0123, Some("earth"), MotionSpec::Lunar),
+            ],
+            ..default()
+        };
+
+        let states = initial_states(&scenario, 1.0, &Ephemeris::default(), 1.0e8);
+        let (position, velocity) = lunar::state(1.0e8);
+        assert_eq!(states[1].position, DVec3::Y + position);
+        assert_eq!(states[1].velocity, velocity);
+    }
+
+    #[test]
+    fn planets_balance_the_momentum_of_their_kinematic_moons() {
+        let moon = |parent| body("moon", 0.0123, parent, MotionSpec::Lunar);
+        let scenario = Scenario {
+            zero_momentum: true,
+            bodies: vec![
+                body("earth", 1.0, None, MotionSpec::default()),
+                moon(Some("earth")),
+            ],
+            ..default()
+        };
+
+        let states = initial_states(&scenario, 1.0, &Ephemeris::default(), 1.0e8);
+        let (_, velocity) = lunar::state(1.0e8);
+        let momentum = states[0].velocity + states[1].velocity * 0.0123;
+        assert!(momentum.length() < 1e-12);
+        // The Moon keeps its velocity relative to the Earth.
+        assert!((states[1].velocity - states[0].velocity - velocity).length() < 1e-9);
+
+        // A kinematic body placed from the origin takes no part.
+        let scenario = Scenario {
+            bodies: vec![body("earth", 1.0, None, MotionSpec::default()), moon(None)],
+            ..scenario
+        };
+        let states = initial_states(&scenario, 1.0, &Ephemeris::default(), 1.0e8);
+        assert_eq!(states[0].velocity, DVec3::ZERO);
+    }
+}
@@ -0,0 +1,89 @@
+//! # Star Field Spawner
+//!
+//! Places catalog stars on a camera-centred celestial sphere.
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use super::BodyAssets;
+use crate::astro::StarCatalog;
+use crate::astro::catalog::{color_from_bv, star_angular_size, star_intensity};
+use crate::astro::frames::Frame;
+use crate::components::CelestialSphere;
+use crate::config::gameplay::StarConfig;
+
+/// Colour-index buckets used to share star materials.
+const COLOR_BINS: f32 = 12.0;
+
+/// Intensity buckets used to share star materials.
+const INTENSITY_BINS: f32 = 8.0;
+
+/// Spawns the star field under `root` and returns the sphere entity.
+///
+/// Every star shares one mesh, and materials are bucketed by colour and
+/// intensity, so the renderer draws the field as a few instanced batches.
+/// The sphere is tilted so that equatorial catalog positions line up with
+/// the scene's ecliptic (XY) reference plane.
+pub fn spawn_star_field(
+    commands: &mut Commands,
+    assets: &mut BodyAssets,
+    catalog: &StarCatalog,
+    config: &StarConfig,
+    root: Entity,
+) -> Entity {
+    let sphere = commands
+        .spawn((
+            Transform::from_rotation(Quat::from_mat3(&Frame::EclipticJ2000.from_icrs().as_mat3())),
+            Visibility::default(),
+            CelestialSphere,
+            ChildOf(root),
+        ))
+        .id();
+
+    let mesh = assets.meshes.add(Sphere::new(0.5).mesh().ico(1).unwrap());
+    let mut materials: HashMap<(u8, u8), Handle<StandardMaterial>> = HashMap::default();
+
+    let mut count = 0;
+    for star in catalog.visible(config.limiting_magnitude) {
+        let bv = star.color_index.unwrap_or(0.6).clamp(-0.4, 2.0);
+        let color_bin = ((bv + 0.4) / 2.4 * (COLOR_BINS - 1.0)).round() as u8;
+        let intensity = star_intensity(star.magnitude, config.limiting_magnitude);
+        let intensity_bin = ((intensity - 0.25) / 0.75 * (INTENSITY_BINS - 1.0)).round() as u8;
+
+        let material = materials
+            .entry((color_bin, intensity_bin))
+            .or_insert_with(|| {
+                let bv = f32::from(color_bin) / (COLOR_BINS - 1.0) * 2.4 - 0.4;
+                let intensity = 0.25 + f32::from(intensity_bin) / (INTENSITY_BINS - 1.0) * 0.75;
+                let [r, g, b] = color_from_bv(bv);
+                assets.materials.add(StandardMaterial {
+                    base_color: Color::srgb(r * intensity, g * intensity, b * intensity),
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .clone();
+
+        let size = star_angular_size(
+            star.magnitude,
+            config.limiting_magnitude,
+            config.min_angular_size,
+        ) * config.sphere_radius;
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(star.direction().as_vec3() * config.sphere_radius)
+                .with_scale(Vec3::splat(size)),
+            ChildOf(sphere),
+        ));
+        count += 1;
+    }
+
+    info!(
+        "[Stars] Spawned {} stars to magnitude {} with {} materials",
+        count,
+        config.limiting_magnitude,
+        materials.len()
+    );
+    sphere
+}
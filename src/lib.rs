@@ -2,19 +2,31 @@
 //!
 //! Architecture:
 //! - `plugin`: Core gameplay logic entry point.
+//! - `astro`: Engine-independent astronomy (time scales, frames, catalogs).
 //! - `components`: ECS components.
 //! - `config`: Game configuration.
 //! - `constants`: Game constants.
 //! - `entities`: Entity spawners.
+//! - `messages`: Buffered messages between systems.
+//! - `physics`: Engine-independent simulation math.
+//! - `procgen`: Seeded procedural content.
 //! - `resources`: Global resources.
+//! - `scenario`: Data-driven world descriptions.
+//! - `states`: In-game sub-states.
 //! - `systems`: Game systems.
 
+pub mod astro;
 pub mod components;
 pub mod config;
 pub mod constants;
 pub mod entities;
+pub mod messages;
+pub mod physics;
 pub mod plugin;
+pub mod procgen;
 pub mod resources;
+pub mod scenario;
+pub mod states;
 pub mod systems;
 
 pub use plugin::GamePlugin;
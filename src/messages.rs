@@ -0,0 +1,39 @@
+//! # Game Messages
+//!
+//! Buffered messages that systems emit for the HUD and gameplay logic.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+/// Two bodies touched and were resolved by the collision policy.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct BodyCollided {
+    pub first: Entity,
+    pub second: Entity,
+    pub outcome: CollisionOutcome,
+    /// Closing speed along the line of centres at impact (m/s).
+    pub impact_speed: f64,
+    /// Centre of mass of the pair at impact.
+    pub position: DVec3,
+}
+
+/// A satellite crossed its primary's Roche limit and broke up.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct BodyDisrupted {
+    /// The satellite, despawned and replaced by `fragments`.
+    pub body: Entity,
+    pub primary: Entity,
+    pub fragments: Vec<Entity>,
+    /// Satellite's distance from the primary at break-up (m).
+    pub distance: f64,
+}
+
+/// What became of a colliding pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionOutcome {
+    /// `survivor` (one of the pair) absorbed the other, which was despawned.
+    Merged {
+        survivor: Entity,
+    },
+    Bounced,
+}
@@ -0,0 +1,266 @@
+//! # Collisions
+//!
+//! Overlap detection between spherical bodies and the two responses:
+//! a perfectly inelastic merge and an impulsive bounce. Both conserve total
+//! mass and linear momentum among free bodies. Kinematic (on-rails) bodies
+//! act as immovable: they absorb mass but their motion is left to their
+//! theory.
+
+use std::f64::consts::PI;
+
+use bevy::math::DVec3;
+
+use crate::config::gameplay::DensityModel;
+
+/// State of one body as seen by the collision routines.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Body {
+    pub position: DVec3,
+    pub velocity: DVec3,
+    pub mass: f64,
+    pub radius: f64,
+    /// Moved by an analytic theory rather than by forces.
+    pub kinematic: bool,
+}
+
+impl Body {
+    /// Inverse mass, zero for kinematic bodies.
+    pub fn inverse_mass(&self) -> f64 {
+        if self.kinematic { 0.0 } else { 1.0 / self.mass }
+    }
+
+    fn volume(&self) -> f64 {
+        4.0 / 3.0 * PI * self.radius.powi(3)
+    }
+}
+
+/// Index pairs `(i, j)`, `i < j`, of bodies whose spheres overlap, in index
+/// order. Pairs of two kinematic bodies are skipped.
+pub fn contacts(bodies: &[Body]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (i, a) in bodies.iter().enumerate() {
+        for (j, b) in bodies.iter().enumerate().skip(i + 1) {
+            if a.kinematic && b.kinematic {
+                continue;
+            }
+            let reach = a.radius + b.radius;
+            if a.position.distance_squared(b.position) < reach * reach {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+/// Radius of a body of `mass` assembled from `parts` under `density`,
+/// clamped to `[min_radius, max_radius]`.
+pub fn merged_radius(
+    parts: [&Body; 2],
+    mass: f64,
+    density: DensityModel,
+    min_radius: f64,
+    max_radius: f64,
+) -> f64 {
+    let volume = match density {
+        DensityModel::Combined => parts.iter().map(|part| part.volume()).sum(),
+        DensityModel::Uniform(density) => mass / density,
+    };
+    (volume * 3.0 / (4.0 * PI))
+        .cbrt()
+        .clamp(min_radius, max_radius)
+}
+
+/// Merges two bodies into one at their centre of mass, moving with their
+/// total momentum. A kinematic part keeps its position and velocity.
+pub fn merge(a: &Body, b: &Body, density: DensityModel, min_radius: f64, max_radius: f64) -> Body {
+    let mass = a.mass + b.mass;
+    let (position, velocity) = match (a.kinematic, b.kinematic) {
+        (true, _) => (a.position, a.velocity),
+        (false, true) => (b.position, b.velocity),
+        (false, false) => (
+            (a.position * a.mass + b.position * b.mass) / mass,
+            (a.velocity * a.mass + b.velocity * b.mass) / mass,
+        ),
+    };
+    Body {
+        position,
+        velocity,
+        mass,
+        radius: merged_radius([a, b], mass, density, min_radius, max_radius),
+        kinematic: a.kinematic || b.kinematic,
+    }
+}
+
+/// Speed at which `a` and `b` approach along their line of centres;
+/// negative when separating.
+pub fn closing_speed(a: &Body, b: &Body) -> f64 {
+    (a.velocity - b.velocity).dot(line_of_centres(a, b))
+}
+
+/// Unit vector from `a` to `b`, +X when they coincide.
+fn line_of_centres(a: &Body, b: &Body) -> DVec3 {
+    (b.position - a.position)
+        .try_normalize()
+        .unwrap_or(DVec3::X)
+}
+
+/// Bounces two overlapping bodies off each other with `restitution`, then
+/// pushes them apart until they just touch, keeping their centre of mass.
+///
+/// Returns the approach speed along the line of centres; bodies already
+/// separating get no impulse and return zero.
+pub fn bounce(a: &mut Body, b: &mut Body, restitution: f64) -> f64 {
+    let inverse_mass = a.inverse_mass() + b.inverse_mass();
+    if inverse_mass == 0.0 {
+        return 0.0;
+    }
+    let normal = line_of_centres(a, b);
+    let distance = a.position.distance(b.position);
+
+    let closing = closing_speed(a, b);
+    if closing > 0.0 {
+        let impulse = (1.0 + restitution) * closing / inverse_mass;
+        a.velocity -= normal * impulse * a.inverse_mass();
+        b.velocity += normal * impulse * b.inverse_mass();
+    }
+
+    let overlap = (a.radius + b.radius - distance).max(0.0);
+    a.position -= normal * overlap * a.inverse_mass() / inverse_mass;
+    b.position += normal * overlap * b.inverse_mass() / inverse_mass;
+    closing.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(position: DVec3, velocity: DVec3, mass: f64, radius: f64) -> Body {
+        Body {
+            position,
+            velocity,
+            mass,
+            radius,
+            kinematic: false,
+        }
+    }
+
+    fn momentum(bodies: &[&Body]) -> DVec3 {
+        bodies.iter().map(|b| b.velocity * b.mass).sum()
+    }
+
+    #[test]
+    fn contacts_find_overlapping_pairs() {
+        let bodies = [
+            body(DVec3::ZERO, DVec3::ZERO, 1.0, 1.0),
+            body(DVec3::new(1.5, 0.0, 0.0), DVec3::ZERO, 1.0, 1.0),
+            body(DVec3::new(10.0, 0.0, 0.0), DVec3::ZERO, 1.0, 1.0),
+        ];
+        assert_eq!(contacts(&bodies), vec![(0, 1)]);
+
+        let mut rails = bodies;
+        rails[0].kinematic = true;
+        rails[1].kinematic = true;
+        assert!(contacts(&rails).is_empty());
+    }
+
+    #[test]
+    fn merge_conserves_mass_and_momentum() {
+        let a = body(DVec3::ZERO, DVec3::new(3.0, 0.0, 0.0), 2.0, 1.0);
+        let b = body(
+            DVec3::new(1.0, 1.0, 0.0),
+            DVec3::new(-1.0, 2.0, 0.5),
+            6.0,
+            2.0,
+        );
+        let merged = merge(&a, &b, DensityModel::Combined, 0.0, f64::MAX);
+
+        assert_eq!(merged.mass, 8.0);
+        assert!((momentum(&[&merged]) - momentum(&[&a, &b])).length() < 1e-12);
+        assert!((merged.position - DVec3::new(0.75, 0.75, 0.0)).length() < 1e-12);
+        // Volume is conserved: 1³ + 2³ = 9.
+        assert!((merged.radius - 9f64.cbrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn merged_radius_follows_density_and_clamps() {
+        let a = body(DVec3::ZERO, DVec3::ZERO, 1.0, 1.0);
+        let b = a;
+        let mass = 4.0 / 3.0 * PI * 1000.0;
+        let uniform = merged_radius([&a, &b], mass, DensityModel::Uniform(1.0), 0.0, 100.0);
+        assert!((uniform - 10.0).abs() < 1e-9);
+        assert_eq!(
+            merged_radius([&a, &b], mass, DensityModel::Uniform(1.0), 0.0, 5.0),
+            5.0
+        );
+        assert_eq!(
+            merged_radius([&a, &b], 1.0, DensityModel::Combined, 3.0, 5.0),
+            3.0
+        );
+    }
+
+    #[test]
+    fn kinematic_bodies_absorb_without_moving() {
+        let mut planet = body(DVec3::ZERO, DVec3::new(0.0, 30.0, 0.0), 100.0, 5.0);
+        planet.kinematic = true;
+        let rock = body(DVec3::new(4.0, 0.0, 0.0), DVec3::X, 1.0, 1.0);
+
+        let merged = merge(&rock, &planet, DensityModel::Combined, 0.0, f64::MAX);
+        assert!(merged.kinematic);
+        assert_eq!(merged.position, planet.position);
+        assert_eq!(merged.velocity, planet.velocity);
+        assert_eq!(merged.mass, 101.0);
+
+        let mut rock = rock;
+        rock.velocity = DVec3::new(-2.0, 30.0, 0.0);
+        let before = planet;
+        bounce(&mut rock, &mut planet, 1.0);
+        assert_eq!(planet, before);
+        assert!((rock.velocity - DVec3::new(2.0, 30.0, 0.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn bounce_conserves_momentum() {
+        for restitution in [0.0, 0.5, 1.0] {
+            let mut a = body(DVec3::ZERO, DVec3::new(2.0, 1.0, 0.0), 3.0, 1.0);
+            let mut b = body(
+                DVec3::new(1.5, 0.0, 0.0),
+                DVec3::new(-1.0, 0.0, 0.0),
+                1.0,
+                1.0,
+            );
+            let (p, centre) = (
+                momentum(&[&a, &b]),
+                (a.position * a.mass + b.position * b.mass) / 4.0,
+            );
+            let energy = |bodies: [&Body; 2]| {
+                bodies
+                    .iter()
+                    .map(|b| 0.5 * b.mass * b.velocity.length_squared())
+                    .sum::<f64>()
+            };
+            let before = energy([&a, &b]);
+
+            let closing = bounce(&mut a, &mut b, restitution);
+
+            assert!((closing - 3.0).abs() < 1e-12);
+            assert!((momentum(&[&a, &b]) - p).length() < 1e-12);
+            let separation = (b.velocity - a.velocity).x;
+            assert!((separation - restitution * 3.0).abs() < 1e-12);
+            // Only an elastic bounce keeps the kinetic energy.
+            assert_eq!((energy([&a, &b]) - before).abs() < 1e-9, restitution == 1.0);
+            // Pushed apart to touching, centre of mass unmoved.
+            assert!((a.position.distance(b.position) - 2.0).abs() < 1e-12);
+            let moved = (a.position * a.mass + b.position * b.mass) / 4.0;
+            assert!((moved - centre).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn separating_bodies_get_no_impulse() {
+        let mut a = body(DVec3::ZERO, DVec3::new(-1.0, 0.0, 0.0), 1.0, 1.0);
+        let mut b = body(DVec3::new(1.0, 0.0, 0.0), DVec3::X, 1.0, 1.0);
+        assert_eq!(bounce(&mut a, &mut b, 1.0), 0.0);
+        assert_eq!(a.velocity, DVec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(b.velocity, DVec3::X);
+    }
+}
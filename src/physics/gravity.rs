@@ -0,0 +1,110 @@
+//! # Newtonian Gravity
+//!
+//! Gravitational accelerations, either by direct pairwise summation or
+//! through the Barnes–Hut octree selected in `PhysicsConfig::solver`.
+
+use bevy::math::DVec3;
+
+use super::kernel::{self, SoaBodies};
+use super::octree::Octree;
+use crate::config::gameplay::{GravitySolver, PhysicsConfig};
+
+/// Computes accelerations with the backend selected in `config`. The
+/// `Direct` solver refills `bodies` in place, so callers stepping many times
+/// keep one set of arrays instead of allocating per call.
+pub fn solve(
+    config: &PhysicsConfig,
+    positions: &[DVec3],
+    masses: &[f64],
+    bodies: &mut SoaBodies,
+    out: &mut [DVec3],
+) {
+    let g = config.gravitational_constant;
+    match config.solver {
+        GravitySolver::Direct => {
+            bodies.fill(positions, masses);
+            kernel::accelerations(bodies, g, out);
+        }
+        GravitySolver::BarnesHut => {
+            Octree::build(positions, masses).accelerations(
+                positions,
+                masses,
+                g,
+                config.opening_angle,
+                out,
+            );
+        }
+    }
+}
+
+/// Computes the gravitational acceleration acting on every body, serially.
+///
+/// This is the reference implementation; the `Direct` solver runs the
+/// equivalent parallel kernel in [`kernel`]. `positions`, `masses` and `out`
+/// must have the same length. Coincident bodies are skipped instead of
+/// producing an infinite acceleration.
+pub fn accelerations(positions: &[DVec3], masses: &[f64], g: f64, out: &mut [DVec3]) {
+    debug_assert_eq!(positions.len(), masses.len());
+    debug_assert_eq!(positions.len(), out.len());
+
+    out.fill(DVec3::ZERO);
+
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            let delta = positions[j] - positions[i];
+            let dist_sq = delta.length_squared();
+            if dist_sq == 0.0 {
+                continue;
+            }
+
+            let inv_dist_cubed = 1.0 / (dist_sq * dist_sq.sqrt());
+            let pull = delta * (g * inv_dist_cubed);
+            out[i] += pull * masses[j];
+            out[j] -= pull * masses[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_bodies_attract_each_other() {
+        let positions = [DVec3::ZERO, DVec3::new(2.0, 0.0, 0.0)];
+        let masses = [4.0, 1.0];
+        let mut out = [DVec3::ZERO; 2];
+
+        accelerations(&positions, &masses, 1.0, &mut out);
+
+        // a = G * m / r^2
+        assert!((out[0] - DVec3::new(0.25, 0.0, 0.0)).length() < 1e-12);
+        assert!((out[1] - DVec3::new(-1.0, 0.0, 0.0)).length() < 1e-12);
+    }
+
+    #[test]
+    fn net_force_is_zero() {
+        let positions = [
+            DVec3::new(1.0, 0.0, 0.0),
+            DVec3::new(-3.0, 2.0, 0.5),
+            DVec3::new(0.0, -1.0, 4.0),
+        ];
+        let masses = [5.0, 2.0, 7.0];
+        let mut out = [DVec3::ZERO; 3];
+
+        accelerations(&positions, &masses, 6.67430e-11, &mut out);
+
+        let net: DVec3 = out.iter().zip(masses).map(|(a, m)| *a * m).sum();
+        assert!(net.length() < 1e-20);
+    }
+
+    #[test]
+    fn coincident_bodies_are_ignored() {
+        let positions = [DVec3::ONE, DVec3::ONE];
+        let mut out = [DVec3::ZERO; 2];
+
+        accelerations(&positions, &[1.0, 1.0], 1.0, &mut out);
+
+        assert!(out.iter().all(|a| a.is_finite() && *a == DVec3::ZERO));
+    }
+}
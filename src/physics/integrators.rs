@@ -0,0 +1,290 @@
+//! # Numerical Integrators
+//!
+//! Fixed-step schemes for advancing an N-body state. Every scheme only needs
+//! an acceleration callback, so the same integrator works with any force model.
+
+use bevy::math::DVec3;
+
+use crate::config::gameplay::IntegratorKind;
+
+/// Callback filling `out` with the accelerations at the given positions.
+pub type AccelerationFn<'a> = dyn FnMut(&[DVec3], &mut [DVec3]) + 'a;
+
+/// A fixed-step numerical integration scheme.
+pub trait Integrator: Send + Sync {
+    /// Advances `positions` and `velocities` in place by `dt`.
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    );
+}
+
+/// First-order explicit (forward) Euler. Not symplectic: orbits spiral outwards.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExplicitEuler;
+
+impl Integrator for ExplicitEuler {
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    ) {
+        let mut a = vec![DVec3::ZERO; positions.len()];
+        accel(positions, &mut a);
+        for ((x, v), a) in positions.iter_mut().zip(velocities.iter_mut()).zip(&a) {
+            *x += *v * dt;
+            *v += *a * dt;
+        }
+    }
+}
+
+/// First-order semi-implicit (symplectic) Euler: kick, then drift.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SemiImplicitEuler;
+
+impl Integrator for SemiImplicitEuler {
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    ) {
+        let mut a = vec![DVec3::ZERO; positions.len()];
+        accel(positions, &mut a);
+        kick(velocities, &a, dt);
+        drift(positions, velocities, dt);
+    }
+}
+
+/// Second-order velocity Verlet in its drift-kick-drift (leapfrog) form.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VelocityVerlet;
+
+impl Integrator for VelocityVerlet {
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    ) {
+        let mut a = vec![DVec3::ZERO; positions.len()];
+        drift(positions, velocities, 0.5 * dt);
+        accel(positions, &mut a);
+        kick(velocities, &a, dt);
+        drift(positions, velocities, 0.5 * dt);
+    }
+}
+
+/// Classic fourth-order Runge–Kutta. Accurate per step, but not symplectic.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RungeKutta4;
+
+impl Integrator for RungeKutta4 {
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    ) {
+        let n = positions.len();
+        let x0 = positions.to_vec();
+        let v0 = velocities.to_vec();
+        let mut stage_x = vec![DVec3::ZERO; n];
+
+        // k1
+        let k1_x = v0.clone();
+        let mut k1_v = vec![DVec3::ZERO; n];
+        accel(&x0, &mut k1_v);
+
+        // k2
+        let k2_x: Vec<DVec3> = (0..n).map(|i| v0[i] + k1_v[i] * (0.5 * dt)).collect();
+        for i in 0..n {
+            stage_x[i] = x0[i] + k1_x[i] * (0.5 * dt);
+        }
+        let mut k2_v = vec![DVec3::ZERO; n];
+        accel(&stage_x, &mut k2_v);
+
+        // k3
+        let k3_x: Vec<DVec3> = (0..n).map(|i| v0[i] + k2_v[i] * (0.5 * dt)).collect();
+        for i in 0..n {
+            stage_x[i] = x0[i] + k2_x[i] * (0.5 * dt);
+        }
+        let mut k3_v = vec![DVec3::ZERO; n];
+        accel(&stage_x, &mut k3_v);
+
+        // k4
+        let k4_x: Vec<DVec3> = (0..n).map(|i| v0[i] + k3_v[i] * dt).collect();
+        for i in 0..n {
+            stage_x[i] = x0[i] + k3_x[i] * dt;
+        }
+        let mut k4_v = vec![DVec3::ZERO; n];
+        accel(&stage_x, &mut k4_v);
+
+        let sixth = dt / 6.0;
+        for i in 0..n {
+            positions[i] = x0[i] + (k1_x[i] + 2.0 * k2_x[i] + 2.0 * k3_x[i] + k4_x[i]) * sixth;
+            velocities[i] = v0[i] + (k1_v[i] + 2.0 * k2_v[i] + 2.0 * k3_v[i] + k4_v[i]) * sixth;
+        }
+    }
+}
+
+/// Fourth-order symplectic scheme built by Yoshida (1990) from three leapfrog stages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Yoshida4;
+
+impl Yoshida4 {
+    const CBRT_2: f64 = 1.259_921_049_894_873_2;
+    const W1: f64 = 1.0 / (2.0 - Self::CBRT_2);
+    const W0: f64 = -Self::CBRT_2 / (2.0 - Self::CBRT_2);
+    /// Drift coefficients.
+    const C: [f64; 4] = [
+        Self::W1 / 2.0,
+        (Self::W0 + Self::W1) / 2.0,
+        (Self::W0 + Self::W1) / 2.0,
+        Self::W1 / 2.0,
+    ];
+    /// Kick coefficients.
+    const D: [f64; 3] = [Self::W1, Self::W0, Self::W1];
+}
+
+impl Integrator for Yoshida4 {
+    fn step(
+        &self,
+        positions: &mut [DVec3],
+        velocities: &mut [DVec3],
+        dt: f64,
+        accel: &mut AccelerationFn,
+    ) {
+        let mut a = vec![DVec3::ZERO; positions.len()];
+        for (c, d) in Self::C.iter().zip(Self::D) {
+            drift(positions, velocities, c * dt);
+            accel(positions, &mut a);
+            kick(velocities, &a, d * dt);
+        }
+        drift(positions, velocities, Self::C[3] * dt);
+    }
+}
+
+impl IntegratorKind {
+    /// Returns the integrator implementing this scheme.
+    pub fn integrator(self) -> &'static dyn Integrator {
+        match self {
+            Self::ExplicitEuler => &ExplicitEuler,
+            Self::SemiImplicitEuler => &SemiImplicitEuler,
+            Self::VelocityVerlet => &VelocityVerlet,
+            Self::Rk4 => &RungeKutta4,
+            Self::Yoshida4 => &Yoshida4,
+        }
+    }
+}
+
+fn drift(positions: &mut [DVec3], velocities: &[DVec3], dt: f64) {
+    for (x, v) in positions.iter_mut().zip(velocities) {
+        *x += *v * dt;
+    }
+}
+
+fn kick(velocities: &mut [DVec3], accelerations: &[DVec3], dt: f64) {
+    for (v, a) in velocities.iter_mut().zip(accelerations) {
+        *v += *a * dt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::gravity;
+
+    const ALL: [IntegratorKind; 5] = [
+        IntegratorKind::ExplicitEuler,
+        IntegratorKind::SemiImplicitEuler,
+        IntegratorKind::VelocityVerlet,
+        IntegratorKind::Rk4,
+        IntegratorKind::Yoshida4,
+    ];
+
+    /// Unit harmonic oscillator `a = -x`, exact solution `x = cos(t)`.
+    fn oscillator_error(kind: IntegratorKind, dt: f64) -> f64 {
+        let mut x = [DVec3::X];
+        let mut v = [DVec3::ZERO];
+        let steps = (1.0 / dt).round() as usize;
+        let mut accel = |x: &[DVec3], out: &mut [DVec3]| out[0] = -x[0];
+        for _ in 0..steps {
+            kind.integrator().step(&mut x, &mut v, dt, &mut accel);
+        }
+        let t = steps as f64 * dt;
+        (x[0].x - t.cos()).abs()
+    }
+
+    #[test]
+    fn convergence_order_matches_scheme() {
+        let expected = [
+            (IntegratorKind::ExplicitEuler, 1.0),
+            (IntegratorKind::SemiImplicitEuler, 1.0),
+            (IntegratorKind::VelocityVerlet, 2.0),
+            (IntegratorKind::Rk4, 4.0),
+            (IntegratorKind::Yoshida4, 4.0),
+        ];
+        for (kind, order) in expected {
+            let coarse = oscillator_error(kind, 0.02);
+            let fine = oscillator_error(kind, 0.01);
+            let measured = (coarse / fine).log2();
+            assert!(
+                (measured - order).abs() < 0.3,
+                "{kind:?}: measured order {measured}, expected {order}"
+            );
+        }
+    }
+
+    /// Total energy of a two-body system after `orbits` circular orbits.
+    fn two_body_energy_drift(kind: IntegratorKind, orbits: usize) -> f64 {
+        let masses: [f64; 2] = [1.0, 1.0e-3];
+        let mut x = [DVec3::ZERO, DVec3::X];
+        let speed = (1.0 + masses[1]).sqrt();
+        let mut v = [DVec3::new(0.0, -speed * masses[1], 0.0), DVec3::new(0.0, speed, 0.0)];
+
+        let energy = |x: &[DVec3], v: &[DVec3]| {
+            let kinetic: f64 = (0..2).map(|i| 0.5 * masses[i] * v[i].length_squared()).sum();
+            kinetic - masses[0] * masses[1] / x[0].distance(x[1])
+        };
+
+        let e0 = energy(&x, &v);
+        let period = std::f64::consts::TAU / speed;
+        let steps_per_orbit = 200;
+        let dt = period / steps_per_orbit as f64;
+        let mut accel = |x: &[DVec3], out: &mut [DVec3]| {
+            gravity::accelerations(x, &masses, 1.0, out);
+        };
+        for _ in 0..orbits * steps_per_orbit {
+            kind.integrator().step(&mut x, &mut v, dt, &mut accel);
+        }
+        ((energy(&x, &v) - e0) / e0).abs()
+    }
+
+    #[test]
+    fn symplectic_schemes_bound_energy_error() {
+        let euler = two_body_energy_drift(IntegratorKind::ExplicitEuler, 20);
+        let verlet = two_body_energy_drift(IntegratorKind::VelocityVerlet, 20);
+        let yoshida = two_body_energy_drift(IntegratorKind::Yoshida4, 20);
+
+        assert!(euler > 0.1, "explicit Euler should drift, got {euler}");
+        assert!(verlet < 1e-3, "velocity Verlet drifted by {verlet}");
+        assert!(yoshida < 1e-6, "Yoshida drifted by {yoshida}");
+    }
+
+    #[test]
+    fn empty_state_is_a_no_op() {
+        for kind in ALL {
+            let mut accel = |_: &[DVec3], _: &mut [DVec3]| {};
+            kind.integrator().step(&mut [], &mut [], 0.1, &mut accel);
+        }
+    }
+}
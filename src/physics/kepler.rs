@@ -0,0 +1,264 @@
+//! # Two-Body Kepler Problem
+//!
+//! Analytic propagation of `OrbitalElements` and exact conversions between
+//! elements and Cartesian state vectors.
+
+use std::f64::consts::TAU;
+
+use bevy::math::{DQuat, DVec3};
+
+use crate::components::OrbitalElements;
+
+/// Below this magnitude eccentricity and node vectors are treated as zero.
+const EPSILON: f64 = 1.0e-11;
+/// Convergence threshold for the Kepler equation solvers.
+const TOLERANCE: f64 = 1.0e-15;
+/// Iteration cap for the Kepler equation solvers.
+const MAX_ITERATIONS: usize = 64;
+
+/// Solves Kepler's equation `M = E - e sin E` for the eccentric anomaly.
+pub fn eccentric_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let m = mean_anomaly.rem_euclid(TAU);
+    let mut e_anomaly = if eccentricity < 0.8 { m } else { std::f64::consts::PI };
+
+    for _ in 0..MAX_ITERATIONS {
+        let f = e_anomaly - eccentricity * e_anomaly.sin() - m;
+        let step = f / (1.0 - eccentricity * e_anomaly.cos());
+        e_anomaly -= step;
+        if step.abs() < TOLERANCE {
+            break;
+        }
+    }
+    e_anomaly
+}
+
+/// Solves the hyperbolic Kepler equation `M = e sinh H - H`.
+pub fn hyperbolic_anomaly(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let mut h = (mean_anomaly / eccentricity).asinh();
+
+    for _ in 0..MAX_ITERATIONS {
+        let f = eccentricity * h.sinh() - h - mean_anomaly;
+        let step = f / (eccentricity * h.cosh() - 1.0);
+        h -= step;
+        if step.abs() < TOLERANCE * h.abs().max(1.0) {
+            break;
+        }
+    }
+    h
+}
+
+impl OrbitalElements {
+    /// Mean motion `n` for gravitational parameter `mu`.
+    pub fn mean_motion(&self, mu: f64) -> f64 {
+        (mu / self.semi_major_axis.abs().powi(3)).sqrt()
+    }
+
+    /// Orbital period, or `None` for open orbits.
+    pub fn period(&self, mu: f64) -> Option<f64> {
+        (self.eccentricity < 1.0).then(|| TAU / self.mean_motion(mu))
+    }
+
+    /// Mean anomaly at time `t`.
+    pub fn mean_anomaly_at(&self, mu: f64, t: f64) -> f64 {
+        self.mean_anomaly_at_epoch + self.mean_motion(mu) * (t - self.epoch)
+    }
+
+    /// Rotation from the perifocal frame into the reference frame.
+    fn orientation(&self) -> DQuat {
+        DQuat::from_rotation_z(self.longitude_of_ascending_node)
+            * DQuat::from_rotation_x(self.inclination)
+            * DQuat::from_rotation_z(self.argument_of_periapsis)
+    }
+
+    /// Position and velocity relative to the primary at time `t`.
+    pub fn state_at(&self, mu: f64, t: f64) -> (DVec3, DVec3) {
+        let a = self.semi_major_axis;
+        let e = self.eccentricity;
+        let n = self.mean_motion(mu);
+        let m = self.mean_anomaly_at(mu, t);
+
+        let (position, velocity) = if e < 1.0 {
+            let ea = eccentric_anomaly(m, e);
+            let (sin_e, cos_e) = ea.sin_cos();
+            let b = a * (1.0 - e * e).sqrt();
+            let rate = n / (1.0 - e * cos_e);
+            (
+                DVec3::new(a * (cos_e - e), b * sin_e, 0.0),
+                DVec3::new(-a * sin_e * rate, b * cos_e * rate, 0.0),
+            )
+        } else {
+            let h = hyperbolic_anomaly(m, e);
+            let (sinh_h, cosh_h) = (h.sinh(), h.cosh());
+            let b = -a * (e * e - 1.0).sqrt();
+            let rate = n / (e * cosh_h - 1.0);
+            (
+                DVec3::new(a * (cosh_h - e), b * sinh_h, 0.0),
+                DVec3::new(a * sinh_h * rate, b * cosh_h * rate, 0.0),
+            )
+        };
+
+        let rotation = self.orientation();
+        (rotation * position, rotation * velocity)
+    }
+
+    /// Derives elements from a relative state vector at time `epoch`.
+    pub fn from_state(position: DVec3, velocity: DVec3, mu: f64, epoch: f64) -> Self {
+        let r = position.length();
+        let h = position.cross(velocity);
+        let node = DVec3::Z.cross(h);
+        let e_vec =
+            ((velocity.length_squared() - mu / r) * position - position.dot(velocity) * velocity)
+                / mu;
+        let e = e_vec.length();
+        let energy = 0.5 * velocity.length_squared() - mu / r;
+        let a = -mu / (2.0 * energy);
+
+        let h_hat = h.normalize();
+        let inclination = h_hat.z.clamp(-1.0, 1.0).acos();
+        let (node_hat, longitude_of_ascending_node) = if node.length() > EPSILON * h.length() {
+            (node.normalize(), node.y.atan2(node.x).rem_euclid(TAU))
+        } else {
+            (DVec3::X, 0.0)
+        };
+
+        // In-plane basis: p1 towards the ascending node, p2 90° ahead of it.
+        let p2 = h_hat.cross(node_hat);
+        let argument_of_latitude = position.dot(p2).atan2(position.dot(node_hat));
+        let argument_of_periapsis = if e > EPSILON {
+            e_vec.dot(p2).atan2(e_vec.dot(node_hat))
+        } else {
+            0.0
+        };
+        let true_anomaly = argument_of_latitude - argument_of_periapsis;
+
+        let mean_anomaly = if e < 1.0 {
+            let (sin_nu, cos_nu) = true_anomaly.sin_cos();
+            let ea = ((1.0 - e * e).sqrt() * sin_nu).atan2(e + cos_nu);
+            (ea - e * ea.sin()).rem_euclid(TAU)
+        } else {
+            let half = (true_anomaly * 0.5).tan();
+            let h_anomaly = 2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * half).atanh();
+            e * h_anomaly.sinh() - h_anomaly
+        };
+
+        Self {
+            semi_major_axis: a,
+            eccentricity: e,
+            inclination,
+            longitude_of_ascending_node,
+            argument_of_periapsis: argument_of_periapsis.rem_euclid(TAU),
+            mean_anomaly_at_epoch: mean_anomaly,
+            epoch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sun's gravitational parameter (m³/s²).
+    const MU_SUN: f64 = 1.327_124_400_18e20;
+    /// One astronomical unit (m).
+    const AU: f64 = 1.495_978_707e11;
+
+    fn assert_state_close(a: (DVec3, DVec3), b: (DVec3, DVec3)) {
+        let dr = (a.0 - b.0).length() / a.0.length();
+        let dv = (a.1 - b.1).length() / a.1.length();
+        assert!(dr < 1e-10 && dv < 1e-10, "dr = {dr:e}, dv = {dv:e}");
+    }
+
+    #[test]
+    fn kepler_equation_is_satisfied() {
+        for &e in &[0.0, 0.1, 0.5, 0.9, 0.99] {
+            for k in 0..16 {
+                let m = k as f64 * TAU / 16.0;
+                let ea = eccentric_anomaly(m, e);
+                assert!((ea - e * ea.sin() - m).abs() < 1e-12, "e = {e}, M = {m}");
+            }
+        }
+        for &e in &[1.1, 2.0, 5.0] {
+            for m in [-10.0, -0.5, 0.0, 0.5, 10.0] {
+                let h = hyperbolic_anomaly(m, e);
+                assert!((e * h.sinh() - h - m).abs() < 1e-10, "e = {e}, M = {m}");
+            }
+        }
+    }
+
+    #[test]
+    fn earth_orbit_period_is_one_year() {
+        let elements = OrbitalElements {
+            semi_major_axis: AU,
+            eccentricity: 0.0167,
+            inclination: 0.0,
+            longitude_of_ascending_node: 0.0,
+            argument_of_periapsis: 0.0,
+            mean_anomaly_at_epoch: 0.0,
+            epoch: 0.0,
+        };
+        let days = elements.period(MU_SUN).unwrap() / 86_400.0;
+        assert!((days - 365.25).abs() < 0.1, "period = {days} days");
+    }
+
+    #[test]
+    fn state_round_trips_through_elements() {
+        let states = [
+            // Generic inclined ellipse.
+            (DVec3::new(1.2e11, 3.0e10, 1.0e10), DVec3::new(-8.0e3, 2.9e4, 2.0e3)),
+            // Circular, equatorial.
+            (DVec3::new(AU, 0.0, 0.0), DVec3::new(0.0, (MU_SUN / AU).sqrt(), 0.0)),
+            // Retrograde, equatorial.
+            (DVec3::new(0.0, AU, 0.0), DVec3::new(3.1e4, 0.0, 0.0)),
+            // Polar.
+            (DVec3::new(AU, 0.0, 0.0), DVec3::new(0.0, 0.0, 2.5e4)),
+            // Hyperbolic flyby.
+            (DVec3::new(AU, 2.0e10, -1.0e10), DVec3::new(-1.0e4, 6.0e4, 5.0e3)),
+        ];
+
+        for (position, velocity) in states {
+            let elements = OrbitalElements::from_state(position, velocity, MU_SUN, 100.0);
+            assert_state_close(elements.state_at(MU_SUN, 100.0), (position, velocity));
+        }
+    }
+
+    #[test]
+    fn elements_round_trip_through_state() {
+        let original = OrbitalElements {
+            semi_major_axis: 2.0 * AU,
+            eccentricity: 0.3,
+            inclination: 0.4,
+            longitude_of_ascending_node: 1.1,
+            argument_of_periapsis: 2.5,
+            mean_anomaly_at_epoch: 4.0,
+            epoch: 0.0,
+        };
+        let later = 1.0e7;
+        let (r, v) = original.state_at(MU_SUN, later);
+        let derived = OrbitalElements::from_state(r, v, MU_SUN, later);
+
+        assert!((derived.semi_major_axis / original.semi_major_axis - 1.0).abs() < 1e-10);
+        assert!((derived.eccentricity - original.eccentricity).abs() < 1e-10);
+        assert!((derived.inclination - original.inclination).abs() < 1e-10);
+        assert!(
+            (derived.longitude_of_ascending_node - original.longitude_of_ascending_node).abs()
+                < 1e-10
+        );
+        assert!((derived.argument_of_periapsis - original.argument_of_periapsis).abs() < 1e-9);
+        let expected_m = original.mean_anomaly_at(MU_SUN, later).rem_euclid(TAU);
+        assert!((derived.mean_anomaly_at_epoch - expected_m).abs() < 1e-9);
+    }
+
+    #[test]
+    fn propagation_conserves_energy_and_momentum() {
+        let (r0, v0) = (DVec3::new(1.2e11, 3.0e10, 1.0e10), DVec3::new(-8.0e3, 2.9e4, 2.0e3));
+        let elements = OrbitalElements::from_state(r0, v0, MU_SUN, 0.0);
+        let energy = |r: DVec3, v: DVec3| 0.5 * v.length_squared() - MU_SUN / r.length();
+
+        for t in [1.0e5, 1.0e6, 3.0e7, 1.0e9] {
+            let (r, v) = elements.state_at(MU_SUN, t);
+            assert!((energy(r, v) / energy(r0, v0) - 1.0).abs() < 1e-10);
+            let h = r.cross(v);
+            assert!((h - r0.cross(v0)).length() / h.length() < 1e-10);
+        }
+    }
+}
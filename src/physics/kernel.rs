@@ -0,0 +1,166 @@
+//! # Parallel Direct-Sum Kernel
+//!
+//! Structure-of-arrays O(n²) gravity evaluated on Bevy's `ComputeTaskPool`.
+//!
+//! Every output element is produced by exactly one task, summing its sources
+//! in index order. The result is therefore bit-for-bit identical regardless of
+//! how many threads the pool has or how the bodies are chunked.
+
+use bevy::math::DVec3;
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+
+/// Minimum number of target bodies handed to a single task.
+const MIN_CHUNK: usize = 64;
+
+/// Body positions and masses laid out as contiguous `f64` arrays.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoaBodies {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub z: Vec<f64>,
+    pub mass: Vec<f64>,
+}
+
+impl SoaBodies {
+    /// Copies positions and masses into a new set of arrays.
+    pub fn from_slices(positions: &[DVec3], masses: &[f64]) -> Self {
+        let mut bodies = Self::default();
+        bodies.fill(positions, masses);
+        bodies
+    }
+
+    /// Refills the arrays in place, reusing their allocations.
+    pub fn fill(&mut self, positions: &[DVec3], masses: &[f64]) {
+        debug_assert_eq!(positions.len(), masses.len());
+        self.x.clear();
+        self.y.clear();
+        self.z.clear();
+        self.mass.clear();
+        for p in positions {
+            self.x.push(p.x);
+            self.y.push(p.y);
+            self.z.push(p.z);
+        }
+        self.mass.extend_from_slice(masses);
+    }
+
+    /// Number of bodies.
+    pub fn len(&self) -> usize {
+        self.mass.len()
+    }
+
+    /// Returns `true` if there are no bodies.
+    pub fn is_empty(&self) -> bool {
+        self.mass.is_empty()
+    }
+}
+
+/// Computes accelerations on the global `ComputeTaskPool`.
+pub fn accelerations(bodies: &SoaBodies, g: f64, out: &mut [DVec3]) {
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    accelerations_on(pool, bodies, g, out);
+}
+
+/// Computes accelerations, splitting the targets across `pool`.
+pub fn accelerations_on(pool: &TaskPool, bodies: &SoaBodies, g: f64, out: &mut [DVec3]) {
+    debug_assert_eq!(bodies.len(), out.len());
+
+    let chunk_size = out
+        .len()
+        .div_ceil(pool.thread_num().max(1))
+        .max(MIN_CHUNK);
+
+    pool.scope(|scope| {
+        for (chunk_index, chunk) in out.chunks_mut(chunk_size).enumerate() {
+            let start = chunk_index * chunk_size;
+            scope.spawn(async move {
+                for (offset, acceleration) in chunk.iter_mut().enumerate() {
+                    *acceleration = acceleration_on(bodies, start + offset, g);
+                }
+            });
+        }
+    });
+}
+
+/// Sums the pull of every other body on body `i`, in index order.
+fn acceleration_on(bodies: &SoaBodies, i: usize, g: f64) -> DVec3 {
+    let (xi, yi, zi) = (bodies.x[i], bodies.y[i], bodies.z[i]);
+    let (mut ax, mut ay, mut az) = (0.0, 0.0, 0.0);
+
+    for j in 0..bodies.len() {
+        let dx = bodies.x[j] - xi;
+        let dy = bodies.y[j] - yi;
+        let dz = bodies.z[j] - zi;
+        let dist_sq = dx * dx + dy * dy + dz * dz;
+        if dist_sq == 0.0 {
+            continue;
+        }
+        let s = bodies.mass[j] / (dist_sq * dist_sq.sqrt());
+        ax += dx * s;
+        ay += dy * s;
+        az += dz * s;
+    }
+
+    DVec3::new(ax, ay, az) * g
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::gravity;
+    use bevy::tasks::TaskPoolBuilder;
+    use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+    fn random_bodies(count: usize) -> SoaBodies {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let (positions, masses): (Vec<DVec3>, Vec<f64>) = (0..count)
+            .map(|_| {
+                let p = DVec3::new(
+                    rng.random_range(-1.0..1.0),
+                    rng.random_range(-1.0..1.0),
+                    rng.random_range(-1.0..1.0),
+                );
+                (p, rng.random_range(0.5..2.0))
+            })
+            .unzip();
+        SoaBodies::from_slices(&positions, &masses)
+    }
+
+    #[test]
+    fn deterministic_across_thread_counts() {
+        let bodies = random_bodies(500);
+
+        let results: Vec<Vec<DVec3>> = [1, 2, 3, 8]
+            .into_iter()
+            .map(|threads| {
+                let pool = TaskPoolBuilder::new().num_threads(threads).build();
+                let mut out = vec![DVec3::ZERO; bodies.len()];
+                accelerations_on(&pool, &bodies, 1.0, &mut out);
+                out
+            })
+            .collect();
+
+        for other in &results[1..] {
+            for (a, b) in results[0].iter().zip(other) {
+                assert_eq!(a.to_array().map(f64::to_bits), b.to_array().map(f64::to_bits));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_serial_direct_sum() {
+        let bodies = random_bodies(300);
+        let positions: Vec<DVec3> = (0..bodies.len())
+            .map(|i| DVec3::new(bodies.x[i], bodies.y[i], bodies.z[i]))
+            .collect();
+
+        let mut serial = vec![DVec3::ZERO; bodies.len()];
+        gravity::accelerations(&positions, &bodies.mass, 1.0, &mut serial);
+        let mut parallel = vec![DVec3::ZERO; bodies.len()];
+        accelerations(&bodies, 1.0, &mut parallel);
+
+        for (s, p) in serial.iter().zip(&parallel) {
+            assert!((*s - *p).length() <= s.length() * 1e-10);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! # Maneuvers
+//!
+//! Maneuver nodes and the burns that carry them out. A node's delta-v is
+//! given along prograde, normal and radial axes of the craft's orbit around
+//! its reference body, and resolved into the scene frame when the burn
+//! starts. Finite burns are centred on the node time, so a short finite
+//! burn ends up where the impulsive one would.
+
+use bevy::math::DVec3;
+
+use crate::config::gameplay::BurnMode;
+
+/// A planned burn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ManeuverNode {
+    /// Simulation time of the burn (s since J2000, TT).
+    pub epoch: f64,
+    /// Delta-v along the velocity (m/s).
+    pub prograde: f64,
+    /// Delta-v along the orbit normal (m/s).
+    pub normal: f64,
+    /// Delta-v away from the reference body, in the orbit plane (m/s).
+    pub radial: f64,
+}
+
+impl ManeuverNode {
+    /// Magnitude of the node's delta-v (m/s).
+    pub fn delta_v(&self) -> f64 {
+        DVec3::new(self.prograde, self.normal, self.radial).length()
+    }
+
+    /// The node with its delta-v multiplied by `factor`.
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            prograde: self.prograde * factor,
+            normal: self.normal * factor,
+            radial: self.radial * factor,
+            ..*self
+        }
+    }
+
+    /// Scene-frame delta-v for a craft at `position` moving at `velocity`
+    /// relative to its reference body.
+    pub fn delta_v_vector(&self, position: DVec3, velocity: DVec3) -> DVec3 {
+        let [prograde, normal, radial] = burn_frame(position, velocity);
+        prograde * self.prograde + normal * self.normal + radial * self.radial
+    }
+
+    /// Start and end of the burn (s since J2000). Impulsive burns start and
+    /// end at the node; finite burns at `acceleration` straddle it.
+    pub fn window(&self, mode: BurnMode, acceleration: f64) -> (f64, f64) {
+        match mode {
+            BurnMode::Impulsive => (self.epoch, self.epoch),
+            BurnMode::Finite => {
+                let half = self.delta_v() / acceleration / 2.0;
+                (self.epoch - half, self.epoch + half)
+            }
+        }
+    }
+}
+
+/// Prograde, normal and radial unit vectors of an orbit at `position`
+/// moving at `velocity`. Degenerate states fall back to the scene axes.
+pub fn burn_frame(position: DVec3, velocity: DVec3) -> [DVec3; 3] {
+    let prograde = velocity.normalize_or(DVec3::X);
+    let normal = position
+        .cross(velocity)
+        .normalize_or(prograde.any_orthonormal_vector());
+    [prograde, normal, prograde.cross(normal)]
+}
+
+/// `nodes` as burned in order from `budget` (m/s): each is cut short
+/// once the budget runs out.
+pub fn within_budget(
+    nodes: impl IntoIterator<Item = ManeuverNode>,
+    budget: f64,
+) -> Vec<ManeuverNode> {
+    let mut remaining = budget.max(0.0);
+    nodes
+        .into_iter()
+        .map(|node| {
+            let delta_v = node.delta_v();
+            let burned = delta_v.min(remaining);
+            remaining -= burned;
+            if burned < delta_v {
+                node.scaled(burned / delta_v)
+            } else {
+                node
+            }
+        })
+        .collect()
+}
+
+/// Fraction of a burn over `window` carried out between `from` and `to`.
+/// An impulsive burn is carried out whole in the step that reaches it,
+/// including one starting right at it; callers drop a node once burned.
+pub fn burned_fraction(window: (f64, f64), from: f64, to: f64) -> f64 {
+    let (start, end) = window;
+    if end <= start {
+        return if from <= start && start <= to {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    ((to.min(end) - from.max(start)) / (end - start)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_axes_follow_the_orbit() {
+        let position = DVec3::new(7.0e6, 0.0, 0.0);
+        let velocity = DVec3::new(0.0, 7.5e3, 0.0);
+        let [prograde, normal, radial] = burn_frame(position, velocity);
+        for (axis, expected) in [(prograde, DVec3::Y), (normal, DVec3::Z), (radial, DVec3::X)] {
+            assert!(axis.distance(expected) < 1e-12);
+        }
+
+        let node = ManeuverNode {
+            epoch: 0.0,
+            prograde: 3.0,
+            normal: -4.0,
+            radial: 12.0,
+        };
+        assert_eq!(node.delta_v(), 13.0);
+        let dv = node.delta_v_vector(position, velocity);
+        assert!(dv.distance(DVec3::new(12.0, 3.0, -4.0)) < 1e-12);
+        // A head-on state has no orbit plane but still gets a frame.
+        let [_, normal, radial] = burn_frame(DVec3::X, DVec3::NEG_X);
+        assert!(normal.is_normalized() && radial.is_normalized());
+    }
+
+    #[test]
+    fn finite_burns_straddle_the_node() {
+        let node = ManeuverNode {
+            epoch: 100.0,
+            prograde: 20.0,
+            ..Default::default()
+        };
+        let window = node.window(BurnMode::Finite, 0.5);
+        assert_eq!(window, (80.0, 120.0));
+        let steps = [60.0, 85.0, 100.0, 130.0];
+        let total: f64 = steps
+            .windows(2)
+            .map(|step| burned_fraction(window, step[0], step[1]))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(burned_fraction(window, 85.0, 100.0), 0.375);
+
+        let impulse = node.window(BurnMode::Impulsive, 0.5);
+        assert_eq!(burned_fraction(impulse, 90.0, 100.0), 1.0);
+        assert_eq!(burned_fraction(impulse, 100.0, 110.0), 1.0);
+        assert_eq!(burned_fraction(impulse, 100.5, 110.0), 0.0);
+    }
+
+    #[test]
+    fn nodes_past_the_budget_are_cut_short() {
+        let node = |prograde| ManeuverNode {
+            prograde,
+            ..Default::default()
+        };
+        let nodes = within_budget([node(300.0), node(-400.0), node(100.0)], 500.0);
+        assert_eq!(nodes, [node(300.0), node(-200.0), node(0.0)]);
+        assert_eq!(within_budget([node(50.0)], 500.0), [node(50.0)]);
+    }
+}
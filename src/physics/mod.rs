@@ -0,0 +1,16 @@
+//! # Physics Core
+//!
+//! Engine-independent numerical routines used by the simulation systems.
+//! Everything here operates on plain slices so it can be tested without an `App`.
+
+pub mod collision;
+pub mod gravity;
+pub mod integrators;
+pub mod kepler;
+pub mod kernel;
+pub mod maneuver;
+pub mod octree;
+pub mod prediction;
+pub mod roche;
+
+pub use integrators::Integrator;
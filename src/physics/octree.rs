@@ -0,0 +1,320 @@
+//! # Barnes–Hut Octree
+//!
+//! Approximates N-body gravity in O(n log n) by replacing distant groups of
+//! bodies with their total mass placed at their centre of mass.
+
+use bevy::math::DVec3;
+
+/// Maximum number of bodies kept in a leaf before it is subdivided.
+const LEAF_CAPACITY: usize = 1;
+/// Depth at which subdivision stops, so coincident bodies share a leaf.
+const MAX_DEPTH: u32 = 32;
+/// Marker for an absent child.
+const NO_CHILD: u32 = u32::MAX;
+
+/// A single cubic cell of the octree.
+#[derive(Debug, Clone)]
+struct Node {
+    center: DVec3,
+    half_size: f64,
+    mass: f64,
+    center_of_mass: DVec3,
+    children: [u32; 8],
+    /// Range into `Octree::order` for the bodies held by a leaf.
+    bodies: std::ops::Range<usize>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.children.iter().all(|&c| c == NO_CHILD)
+    }
+
+    fn contains(&self, position: DVec3) -> bool {
+        (position - self.center).abs().max_element() <= self.half_size
+    }
+}
+
+/// Octree over a set of point masses, rebuilt from scratch every step.
+#[derive(Debug, Clone, Default)]
+pub struct Octree {
+    nodes: Vec<Node>,
+    /// Body indices permuted so that each leaf references a contiguous slice.
+    order: Vec<usize>,
+}
+
+impl Octree {
+    /// Builds an octree enclosing all `positions`.
+    pub fn build(positions: &[DVec3], masses: &[f64]) -> Self {
+        debug_assert_eq!(positions.len(), masses.len());
+
+        let mut tree = Self {
+            nodes: Vec::with_capacity(positions.len() * 2),
+            order: (0..positions.len()).collect(),
+        };
+        if positions.is_empty() {
+            return tree;
+        }
+
+        let (min, max) = positions
+            .iter()
+            .fold((DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)), |(lo, hi), p| {
+                (lo.min(*p), hi.max(*p))
+            });
+        let center = (min + max) * 0.5;
+        // Pad slightly so bodies on the boundary fall strictly inside.
+        let half_size = ((max - min).max_element() * 0.5).max(f64::MIN_POSITIVE) * 1.000_001;
+
+        tree.subdivide(positions, masses, center, half_size, 0..positions.len(), 0);
+        tree
+    }
+
+    /// Number of cells in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the tree holds no bodies.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Computes the acceleration on every body.
+    ///
+    /// A cell of width `s` at distance `d` is treated as a point mass when
+    /// `s / d < theta`, unless it contains the body itself, whose own mass
+    /// must not pull on it. `theta = 0` degenerates to direct summation.
+    pub fn accelerations(
+        &self,
+        positions: &[DVec3],
+        masses: &[f64],
+        g: f64,
+        theta: f64,
+        out: &mut [DVec3],
+    ) {
+        for (i, acceleration) in out.iter_mut().enumerate() {
+            *acceleration = self.acceleration_at(i, positions, masses, g, theta);
+        }
+    }
+
+    /// Computes the acceleration on body `index`.
+    pub fn acceleration_at(
+        &self,
+        index: usize,
+        positions: &[DVec3],
+        masses: &[f64],
+        g: f64,
+        theta: f64,
+    ) -> DVec3 {
+        if self.nodes.is_empty() {
+            return DVec3::ZERO;
+        }
+
+        let target = positions[index];
+        let theta_sq = theta * theta;
+        let mut acceleration = DVec3::ZERO;
+        let mut stack = vec![0u32];
+
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index as usize];
+
+            if node.is_leaf() {
+                for &j in &self.order[node.bodies.clone()] {
+                    if j != index {
+                        acceleration += pull(positions[j] - target, masses[j], g);
+                    }
+                }
+                continue;
+            }
+
+            let delta = node.center_of_mass - target;
+            let dist_sq = delta.length_squared();
+            let size = 2.0 * node.half_size;
+            if size * size < theta_sq * dist_sq && !node.contains(target) {
+                acceleration += pull(delta, node.mass, g);
+            } else {
+                stack.extend(node.children.iter().filter(|&&c| c != NO_CHILD));
+            }
+        }
+
+        acceleration
+    }
+
+    fn subdivide(
+        &mut self,
+        positions: &[DVec3],
+        masses: &[f64],
+        center: DVec3,
+        half_size: f64,
+        range: std::ops::Range<usize>,
+        depth: u32,
+    ) -> u32 {
+        let node_index = self.nodes.len() as u32;
+        let (mass, weighted) = self.order[range.clone()]
+            .iter()
+            .fold((0.0, DVec3::ZERO), |(m, w), &i| {
+                (m + masses[i], w + positions[i] * masses[i])
+            });
+        let center_of_mass = if mass > 0.0 { weighted / mass } else { center };
+
+        self.nodes.push(Node {
+            center,
+            half_size,
+            mass,
+            center_of_mass,
+            children: [NO_CHILD; 8],
+            bodies: range.clone(),
+        });
+
+        if range.len() <= LEAF_CAPACITY || depth >= MAX_DEPTH {
+            return node_index;
+        }
+
+        // Bucket the bodies of this cell by octant, in place.
+        let slice = &mut self.order[range.clone()];
+        slice.sort_unstable_by_key(|&i| octant(center, positions[i]));
+
+        let quarter = half_size * 0.5;
+        let mut start = range.start;
+        let mut children = [NO_CHILD; 8];
+        for (octant_index, child) in children.iter_mut().enumerate() {
+            let end = start
+                + self.order[start..range.end]
+                    .iter()
+                    .take_while(|&&i| octant(center, positions[i]) == octant_index)
+                    .count();
+            if end > start {
+                let offset = DVec3::new(
+                    if octant_index & 1 != 0 { quarter } else { -quarter },
+                    if octant_index & 2 != 0 { quarter } else { -quarter },
+                    if octant_index & 4 != 0 { quarter } else { -quarter },
+                );
+                *child = self.subdivide(
+                    positions,
+                    masses,
+                    center + offset,
+                    quarter,
+                    start..end,
+                    depth + 1,
+                );
+            }
+            start = end;
+        }
+
+        let node = &mut self.nodes[node_index as usize];
+        node.children = children;
+        node.bodies = range.start..range.start;
+        node_index
+    }
+}
+
+/// Index of the child octant of `center` containing `position`.
+fn octant(center: DVec3, position: DVec3) -> usize {
+    usize::from(position.x >= center.x)
+        | usize::from(position.y >= center.y) << 1
+        | usize::from(position.z >= center.z) << 2
+}
+
+/// Acceleration towards a mass `m` displaced by `delta`.
+fn pull(delta: DVec3, m: f64, g: f64) -> DVec3 {
+    let dist_sq = delta.length_squared();
+    if dist_sq == 0.0 {
+        return DVec3::ZERO;
+    }
+    delta * (g * m / (dist_sq * dist_sq.sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::gravity;
+    use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+    /// Relative RMS error tolerated for `theta = 0.5` on a random cluster.
+    const TOLERANCE: f64 = 1.0e-2;
+
+    fn random_bodies(count: usize, seed: u64) -> (Vec<DVec3>, Vec<f64>) {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (0..count)
+            .map(|_| {
+                let position = DVec3::new(
+                    rng.random_range(-1.0..1.0),
+                    rng.random_range(-1.0..1.0),
+                    rng.random_range(-1.0..1.0),
+                ) * 1.0e3;
+                (position, rng.random_range(1.0e20..1.0e22))
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn matches_direct_summation_on_random_bodies() {
+        let (positions, masses) = random_bodies(1_000, 42);
+        let g = 6.67430e-11;
+
+        let mut direct = vec![DVec3::ZERO; positions.len()];
+        gravity::accelerations(&positions, &masses, g, &mut direct);
+
+        let tree = Octree::build(&positions, &masses);
+        let mut approx = vec![DVec3::ZERO; positions.len()];
+        tree.accelerations(&positions, &masses, g, 0.5, &mut approx);
+
+        let error_sq: f64 = direct
+            .iter()
+            .zip(&approx)
+            .map(|(d, a)| (*d - *a).length_squared())
+            .sum();
+        let norm_sq: f64 = direct.iter().map(|d| d.length_squared()).sum();
+        let relative = (error_sq / norm_sq).sqrt();
+        assert!(relative < TOLERANCE, "relative RMS error {relative}");
+    }
+
+    #[test]
+    fn zero_opening_angle_is_exact() {
+        let (positions, masses) = random_bodies(200, 7);
+
+        let mut direct = vec![DVec3::ZERO; positions.len()];
+        gravity::accelerations(&positions, &masses, 1.0, &mut direct);
+
+        let tree = Octree::build(&positions, &masses);
+        let mut exact = vec![DVec3::ZERO; positions.len()];
+        tree.accelerations(&positions, &masses, 1.0, 0.0, &mut exact);
+
+        for (d, e) in direct.iter().zip(&exact) {
+            assert!((*d - *e).length() <= d.length() * 1e-9);
+        }
+    }
+
+    #[test]
+    fn coincident_bodies_share_a_leaf() {
+        let positions = [DVec3::ONE, DVec3::ONE, DVec3::ZERO];
+        let masses = [1.0, 1.0, 1.0];
+
+        let tree = Octree::build(&positions, &masses);
+        let mut out = [DVec3::ZERO; 3];
+        tree.accelerations(&positions, &masses, 1.0, 0.5, &mut out);
+
+        assert!(out.iter().all(|a| a.is_finite()));
+        assert!(tree.len() > 1);
+    }
+
+    #[test]
+    fn cells_holding_the_body_are_never_approximated() {
+        // The cell of the first two bodies spans [0, 1]; its centre of mass
+        // lies further from the first body than the cell is wide.
+        let positions = [DVec3::ZERO, DVec3::ONE, DVec3::NEG_ONE];
+        let masses = [1.0, 2.0, 1.0];
+
+        let mut direct = [DVec3::ZERO; 3];
+        gravity::accelerations(&positions, &masses, 1.0, &mut direct);
+
+        let tree = Octree::build(&positions, &masses);
+        let approx = tree.acceleration_at(0, &positions, &masses, 1.0, 1.0);
+        assert!((approx - direct[0]).length() <= direct[0].length() * 1e-9);
+    }
+
+    #[test]
+    fn empty_tree_is_empty() {
+        let tree = Octree::build(&[], &[]);
+        assert!(tree.is_empty());
+    }
+}
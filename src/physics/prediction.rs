@@ -0,0 +1,493 @@
+//! # Trajectory Prediction
+//!
+//! Runs the configured integrator forward on a copy of the N-body state to
+//! sample where the free bodies will be. Kinematic bodies are not
+//! integrated; they attract from where their theories put them at the
+//! start of each sub-step.
+//!
+//! Planned maneuvers are applied as impulses at their node times, so a
+//! finite burn is predicted as the impulsive burn it is centred on.
+
+use bevy::math::DVec3;
+
+use super::gravity;
+use super::kernel::SoaBodies;
+use super::maneuver::ManeuverNode;
+use crate::astro::lunar;
+use crate::astro::vsop87::PlanetTheory;
+use crate::components::OrbitalElements;
+use crate::config::gameplay::{GravitySolver, PhysicsConfig};
+
+/// Guards against cycles in misconfigured primary chains.
+const MAX_PRIMARY_DEPTH: usize = 16;
+
+/// Free bodies and kinematic attractors to predict from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PredictionState {
+    /// Simulation time the state is taken at (s since J2000, TT).
+    pub epoch: f64,
+    pub positions: Vec<DVec3>,
+    pub velocities: Vec<DVec3>,
+    pub masses: Vec<f64>,
+    /// Bodies moved along their theories during the prediction.
+    pub attractors: Vec<Attractor>,
+    pub burns: Vec<PlannedBurn>,
+}
+
+/// A kinematic body and the theory it follows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attractor {
+    pub mass: f64,
+    pub rail: Rail,
+    /// Body the rail is relative to.
+    pub primary: Primary,
+}
+
+impl Attractor {
+    /// An attractor held still at `position`.
+    pub fn fixed(position: DVec3, mass: f64) -> Self {
+        Self {
+            mass,
+            rail: Rail::Fixed(position),
+            primary: Primary::Origin,
+        }
+    }
+}
+
+/// Theory placing an attractor relative to its primary.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rail {
+    /// A constant offset.
+    Fixed(DVec3),
+    /// A Keplerian orbit with its gravitational parameter.
+    Kepler(OrbitalElements, f64),
+    /// A VSOP87 heliocentric theory.
+    Ephemeris(PlanetTheory),
+    /// The geocentric lunar theory.
+    Lunar,
+}
+
+impl Rail {
+    /// Position and velocity relative to the primary at `epoch`.
+    pub fn state(&self, epoch: f64) -> (DVec3, DVec3) {
+        match self {
+            Self::Fixed(offset) => (*offset, DVec3::ZERO),
+            Self::Kepler(elements, mu) => elements.state_at(*mu, epoch),
+            Self::Ephemeris(theory) => theory.state(epoch),
+            Self::Lunar => lunar::state(epoch),
+        }
+    }
+}
+
+/// Body an attractor's rail is relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Primary {
+    /// The scene origin.
+    #[default]
+    Origin,
+    /// Index into `PredictionState::positions`.
+    Free(usize),
+    /// Index into `PredictionState::attractors`.
+    Attractor(usize),
+}
+
+impl PredictionState {
+    /// Scene-frame position and velocity of every attractor at `epoch`,
+    /// given the free bodies' current state. Attractors whose primaries
+    /// form a cycle stay at their offset from the origin.
+    pub fn attractor_states(
+        &self,
+        epoch: f64,
+        positions: &[DVec3],
+        velocities: &[DVec3],
+    ) -> Vec<(DVec3, DVec3)> {
+        let local: Vec<(DVec3, DVec3)> = self
+            .attractors
+            .iter()
+            .map(|attractor| attractor.rail.state(epoch))
+            .collect();
+        let resolve = |index: usize| {
+            let (mut position, mut velocity) = local[index];
+            let mut primary = self.attractors[index].primary;
+            for _ in 0..MAX_PRIMARY_DEPTH {
+                let (offset, drift) = match primary {
+                    Primary::Origin => return Some((position, velocity)),
+                    Primary::Free(other) => {
+                        return Some((position + positions[other], velocity + velocities[other]));
+                    }
+                    Primary::Attractor(other) => {
+                        primary = self.attractors[other].primary;
+                        local[other]
+                    }
+                };
+                position += offset;
+                velocity += drift;
+            }
+            None
+        };
+        (0..local.len())
+            .map(|index| resolve(index).unwrap_or(local[index]))
+            .collect()
+    }
+}
+
+/// A maneuver node of one of the free bodies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlannedBurn {
+    /// Index of the burning body in `PredictionState::positions`.
+    pub body: usize,
+    /// The node, with `epoch` counted from the start of the prediction.
+    pub node: ManeuverNode,
+    /// Body whose orbit the node's axes follow.
+    pub reference: BurnReference,
+}
+
+/// Body a burn's prograde, normal and radial axes are taken against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BurnReference {
+    /// The scene frame.
+    #[default]
+    Scene,
+    /// Index into `PredictionState::positions`.
+    Free(usize),
+    /// Index into `PredictionState::attractors`.
+    Attractor(usize),
+}
+
+/// Predicted paths, and where each planned burn takes place.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Prediction {
+    /// Evenly spaced positions of every free body.
+    pub paths: Vec<Vec<DVec3>>,
+    /// Positions of every attractor at the same instants.
+    pub attractor_paths: Vec<Vec<DVec3>>,
+    /// Position of each of `PredictionState::burns` when it is applied, with
+    /// the fractional path index it falls at; `None` beyond the horizon.
+    pub burns: Vec<Option<(DVec3, f64)>>,
+}
+
+/// Predicts `points` evenly spaced positions of every free body over
+/// `horizon` seconds, the first being the current position.
+///
+/// Each interval is split into sub-steps like `step_bodies`, using
+/// `PhysicsConfig::max_substep` and `max_substeps`. The direct solver runs
+/// serially here, since predictions already run off the main thread.
+/// Attractors are placed at each sub-step's epoch and held there through
+/// the sub-step. Burns are applied at the first sub-step boundary at or
+/// after their node.
+pub fn predict(
+    physics: &PhysicsConfig,
+    state: &PredictionState,
+    horizon: f64,
+    points: usize,
+) -> Prediction {
+    let count = state.positions.len();
+    let mut rails = state.attractor_states(state.epoch, &state.positions, &state.velocities);
+    let mut paths = start_paths(state.positions.iter().copied(), points);
+    let mut attractor_paths = start_paths(rails.iter().map(|&(position, _)| position), points);
+    let mut burns = vec![None; state.burns.len()];
+    if points < 2 || count == 0 {
+        return Prediction {
+            paths,
+            attractor_paths,
+            burns,
+        };
+    }
+
+    let interval = horizon / (points - 1) as f64;
+    let substeps = (interval.abs() / physics.max_substep)
+        .ceil()
+        .clamp(1.0, f64::from(physics.max_substeps)) as u32;
+    let dt = interval / f64::from(substeps);
+
+    let mut positions = state.positions.clone();
+    let mut velocities = state.velocities.clone();
+    let mut all_positions = positions.clone();
+    let mut all_masses = state.masses.clone();
+    for (attractor, &(position, _)) in state.attractors.iter().zip(&rails) {
+        all_positions.push(position);
+        all_masses.push(attractor.mass);
+    }
+    let mut all_accelerations = vec![DVec3::ZERO; all_positions.len()];
+
+    let mut order: Vec<usize> = (0..state.burns.len()).collect();
+    order.sort_by(|&a, &b| {
+        state.burns[a]
+            .node
+            .epoch
+            .total_cmp(&state.burns[b].node.epoch)
+    });
+    let mut order = order.into_iter().peekable();
+    let mut burn =
+        |elapsed: f64, rails: &[(DVec3, DVec3)], positions: &[DVec3], velocities: &mut [DVec3]| {
+            while let Some(index) = order.next_if(|&index| state.burns[index].node.epoch <= elapsed)
+            {
+                let PlannedBurn {
+                    body,
+                    node,
+                    reference,
+                } = state.burns[index];
+                let (origin, frame_velocity) = match reference {
+                    BurnReference::Scene => (DVec3::ZERO, DVec3::ZERO),
+                    BurnReference::Free(other) => (positions[other], velocities[other]),
+                    BurnReference::Attractor(other) => rails[other],
+                };
+                velocities[body] += node
+                    .delta_v_vector(positions[body] - origin, velocities[body] - frame_velocity);
+                burns[index] = Some((positions[body], elapsed / interval));
+            }
+        };
+
+    let integrator = physics.integrator.integrator();
+    let mut elapsed = 0.0;
+    for _ in 1..points {
+        for _ in 0..substeps {
+            burn(elapsed, &rails, &positions, &mut velocities);
+            for (slot, &(position, _)) in all_positions[count..].iter_mut().zip(&rails) {
+                *slot = position;
+            }
+            let mut accel = |x: &[DVec3], out: &mut [DVec3]| {
+                all_positions[..count].copy_from_slice(x);
+                match physics.solver {
+                    GravitySolver::Direct => gravity::accelerations(
+                        &all_positions,
+                        &all_masses,
+                        physics.gravitational_constant,
+                        &mut all_accelerations,
+                    ),
+                    GravitySolver::BarnesHut => gravity::solve(
+                        physics,
+                        &all_positions,
+                        &all_masses,
+                        &mut SoaBodies::default(),
+                        &mut all_accelerations,
+                    ),
+                }
+                out.copy_from_slice(&all_accelerations[..count]);
+            };
+            integrator.step(&mut positions, &mut velocities, dt, &mut accel);
+            elapsed += dt;
+            rails = state.attractor_states(state.epoch + elapsed, &positions, &velocities);
+        }
+        for (path, &position) in paths.iter_mut().zip(&positions) {
+            path.push(position);
+        }
+        for (path, &(position, _)) in attractor_paths.iter_mut().zip(&rails) {
+            path.push(position);
+        }
+    }
+    burn(elapsed, &rails, &positions, &mut velocities);
+    Prediction {
+        paths,
+        attractor_paths,
+        burns,
+    }
+}
+
+/// One path per position, holding just that position.
+fn start_paths(positions: impl Iterator<Item = DVec3>, points: usize) -> Vec<Vec<DVec3>> {
+    positions
+        .map(|position| {
+            let mut path = Vec::with_capacity(points);
+            path.push(position);
+            path
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::TAU;
+
+    #[test]
+    fn circular_orbit_closes_after_one_period() {
+        let physics = PhysicsConfig::default();
+        let (mass, radius) = (5.972e24, 4.2e7);
+        let mu = physics.gravitational_constant * mass;
+        let speed = (mu / radius).sqrt();
+        let period = TAU * radius / speed;
+        let state = PredictionState {
+            epoch: 0.0,
+            positions: vec![DVec3::new(radius, 0.0, 0.0)],
+            velocities: vec![DVec3::new(0.0, speed, 0.0)],
+            masses: vec![1.0],
+            attractors: vec![Attractor::fixed(DVec3::ZERO, mass)],
+            burns: Vec::new(),
+        };
+
+        let paths = predict(&physics, &state, period, 65).paths;
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].len(), 65);
+        for point in &paths[0] {
+            assert!((point.length() / radius - 1.0).abs() < 1e-5);
+        }
+        assert!(paths[0][64].distance(paths[0][0]) < radius * 1e-3);
+        // Half a period later the body is on the far side.
+        assert!(paths[0][32].distance(DVec3::new(-radius, 0.0, 0.0)) < radius * 1e-3);
+    }
+
+    #[test]
+    fn prediction_leaves_the_state_untouched() {
+        let physics = PhysicsConfig::default();
+        let state = PredictionState {
+            epoch: 0.0,
+            positions: vec![DVec3::ZERO, DVec3::new(1.0e7, 0.0, 0.0)],
+            velocities: vec![DVec3::ZERO, DVec3::new(0.0, 5.0e3, 0.0)],
+            masses: vec![5.972e24, 7.342e22],
+            attractors: Vec::new(),
+            burns: Vec::new(),
+        };
+        let before = state.clone();
+        let paths = predict(&physics, &state, 3600.0, 10).paths;
+        assert_eq!(state, before);
+
+        // The pair's centre of mass drifts at constant velocity.
+        let total = state.masses[0] + state.masses[1];
+        let start = state.positions[1] * state.masses[1] / total;
+        let drift = state.velocities[1] * state.masses[1] / total;
+        for (i, (a, b)) in paths[0].iter().zip(&paths[1]).enumerate() {
+            let centre = (*a * state.masses[0] + *b * state.masses[1]) / total;
+            assert!(centre.distance(start + drift * 400.0 * i as f64) < 1e-3);
+        }
+    }
+
+    #[test]
+    fn prograde_burns_raise_the_far_side_of_the_orbit() {
+        // Short sub-steps, so the burn lands close to its node.
+        let physics = PhysicsConfig {
+            max_substep: 1.0,
+            ..Default::default()
+        };
+        let (mass, radius) = (5.972e24, 1.0e7);
+        let mu = physics.gravitational_constant * mass;
+        let speed = (mu / radius).sqrt();
+        // Hohmann transfer out to twice the radius, burned a quarter of the
+        // way into the prediction around an attractor off the origin.
+        let transfer = 1.5 * radius;
+        let delta_v = (mu * (2.0 / radius - 1.0 / transfer)).sqrt() - speed;
+        let half_transfer = std::f64::consts::PI * (transfer.powi(3) / mu).sqrt();
+        let centre = DVec3::new(0.0, 0.0, 3.0e7);
+        let state = PredictionState {
+            epoch: 0.0,
+            positions: vec![centre + DVec3::new(0.0, -radius, 0.0)],
+            velocities: vec![DVec3::new(speed, 0.0, 0.0)],
+            masses: vec![1.0],
+            attractors: vec![Attractor::fixed(centre, mass)],
+            burns: vec![PlannedBurn {
+                body: 0,
+                node: ManeuverNode {
+                    epoch: TAU * radius / speed / 4.0,
+                    prograde: delta_v,
+                    ..Default::default()
+                },
+                reference: BurnReference::Attractor(0),
+            }],
+        };
+
+        let horizon = state.burns[0].node.epoch + half_transfer;
+        let prediction = predict(&physics, &state, horizon, 129);
+        let (point, sample) = prediction.burns[0].unwrap();
+        assert!(point.distance(centre + DVec3::new(radius, 0.0, 0.0)) < radius * 1e-3);
+        assert!((sample / 128.0 - state.burns[0].node.epoch / horizon).abs() < 1e-3);
+        let far = prediction.paths[0][128] - centre;
+        assert!(far.distance(DVec3::new(-2.0 * radius, 0.0, 0.0)) < radius * 1e-2);
+
+        let late = PlannedBurn {
+            node: ManeuverNode {
+                epoch: 2.0 * horizon,
+                ..state.burns[0].node
+            },
+            ..state.burns[0]
+        };
+        let state = PredictionState {
+            burns: vec![late],
+            ..state
+        };
+        assert_eq!(predict(&physics, &state, horizon, 129).burns, [None]);
+    }
+
+    /// A craft in a low orbit around a Moon circling a fixed Earth, with
+    /// the Moon's orbit and its gravitational parameter.
+    fn lunar_orbiter(
+        physics: &PhysicsConfig,
+        epoch: f64,
+    ) -> (PredictionState, OrbitalElements, f64) {
+        let g = physics.gravitational_constant;
+        let (earth, moon) = (5.972e24, 7.342e22);
+        let distance = 3.844e8;
+        let mu = g * (earth + moon);
+        let moon_velocity = DVec3::new(0.0, (mu / distance).sqrt(), 0.0);
+        let elements =
+            OrbitalElements::from_state(DVec3::new(distance, 0.0, 0.0), moon_velocity, mu, epoch);
+        let low = 2.0e6;
+        let low_speed = (g * moon / low).sqrt();
+        let state = PredictionState {
+            epoch,
+            positions: vec![DVec3::new(distance, low, 0.0)],
+            velocities: vec![moon_velocity + DVec3::new(-low_speed, 0.0, 0.0)],
+            masses: vec![1.0],
+            attractors: vec![
+                Attractor::fixed(DVec3::ZERO, earth),
+                Attractor {
+                    mass: moon,
+                    rail: Rail::Kepler(elements, mu),
+                    primary: Primary::Attractor(0),
+                },
+            ],
+            burns: Vec::new(),
+        };
+        (state, elements, mu)
+    }
+
+    #[test]
+    fn attractors_move_along_their_rails() {
+        let physics = PhysicsConfig::default();
+        let epoch = 1.0e6;
+        let (state, elements, mu) = lunar_orbiter(&physics, epoch);
+
+        let prediction = predict(&physics, &state, 86_400.0, 25);
+        for (i, (craft, moon)) in prediction.paths[0]
+            .iter()
+            .zip(&prediction.attractor_paths[1])
+            .enumerate()
+        {
+            let expected = elements.state_at(mu, epoch + 3600.0 * i as f64).0;
+            assert!(moon.distance(expected) < 1.0);
+            // The craft keeps up with the Moon.
+            assert!((craft.distance(*moon) / 2.0e6 - 1.0).abs() < 0.1);
+        }
+        assert!(
+            prediction.attractor_paths[0]
+                .iter()
+                .all(|&p| p == DVec3::ZERO)
+        );
+    }
+
+    #[test]
+    fn burns_follow_the_velocity_relative_to_moving_attractors() {
+        let physics = PhysicsConfig {
+            max_substep: 1.0,
+            ..Default::default()
+        };
+        let (coasting, ..) = lunar_orbiter(&physics, 0.0);
+        let burning = PredictionState {
+            burns: vec![PlannedBurn {
+                body: 0,
+                node: ManeuverNode {
+                    epoch: 0.0,
+                    prograde: 100.0,
+                    ..Default::default()
+                },
+                reference: BurnReference::Attractor(1),
+            }],
+            ..coasting.clone()
+        };
+
+        // Prograde around the Moon is -X, whatever the Moon's own motion.
+        let end = |state: &PredictionState| predict(&physics, state, 10.0, 2).paths[0][1];
+        let shift = end(&burning) - end(&coasting);
+        assert!((shift.length() / 1000.0 - 1.0).abs() < 1e-3);
+        assert!(shift.normalize().distance(DVec3::NEG_X) < 1e-3);
+    }
+}
@@ -0,0 +1,218 @@
+//! # Roche Limit
+//!
+//! Tidal disruption of satellites that stray too close to their primary.
+//! The Roche limit `d = k R_M (ρ_M / ρ_m)^(1/3)` is evaluated in the
+//! equivalent form `d = k r_m (M / m)^(1/3)`, which needs only the masses
+//! and the satellite's radius. `k` is `2^(1/3) ≈ 1.26` for a rigid
+//! spherical satellite and `≈ 2.44` for a fluid one that deforms under the
+//! tide.
+//!
+//! A disrupted satellite is replaced by equal fragments spread over a shell
+//! with seeded random velocity kicks. The fragments keep the satellite's
+//! mass, centre of mass and momentum exactly.
+
+use std::f64::consts::PI;
+
+use bevy::math::DVec3;
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+use super::collision::Body;
+use crate::config::gameplay::RocheFormula;
+
+/// Minimum gap between fragment surfaces, as a fraction of their radius.
+const FRAGMENT_CLEARANCE: f64 = 0.1;
+
+impl RocheFormula {
+    /// Coefficient `k` of the Roche limit.
+    pub fn coefficient(self) -> f64 {
+        match self {
+            RocheFormula::Rigid => 2f64.cbrt(),
+            RocheFormula::Fluid => 2.44,
+        }
+    }
+}
+
+/// Distance from the primary's centre inside which a satellite of
+/// `satellite_mass` and `satellite_radius` is torn apart.
+pub fn roche_limit(
+    primary_mass: f64,
+    satellite_mass: f64,
+    satellite_radius: f64,
+    formula: RocheFormula,
+) -> f64 {
+    formula.coefficient() * satellite_radius * (primary_mass / satellite_mass).cbrt()
+}
+
+/// Number of fragments a satellite of `mass` breaks into: at most
+/// `max_fragments`, none lighter than `min_fragment_mass`. Fewer than two
+/// means the satellite stays whole.
+pub fn fragment_count(mass: f64, max_fragments: u32, min_fragment_mass: f64) -> u32 {
+    let by_mass = (mass / min_fragment_mass).floor();
+    if by_mass < f64::from(max_fragments) {
+        by_mass as u32
+    } else {
+        max_fragments
+    }
+}
+
+/// Mixes `seed` with a body's name (FNV-1a), so a scenario breaks up the
+/// same way on every run whatever entities its bodies get.
+pub fn body_seed(seed: u64, name: &str) -> u64 {
+    name.bytes().fold(seed ^ 0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Breaks `satellite` into `count` equal fragments.
+///
+/// Fragment radii conserve volume. Centres lie on a Fibonacci sphere just
+/// wide enough that no two fragments touch. Each fragment gets a kick in a
+/// random direction of up to `kick_speed`; the mean kick and mean offset
+/// are removed so mass, centre of mass and momentum are unchanged.
+pub fn fragments(satellite: &Body, count: usize, kick_speed: f64, seed: u64) -> Vec<Body> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let n = count as f64;
+    let radius = satellite.radius / n.cbrt();
+
+    let golden_angle = PI * (3.0 - 5f64.sqrt());
+    let mut offsets: Vec<DVec3> = (0..count)
+        .map(|k| {
+            let z = 1.0 - (2.0 * k as f64 + 1.0) / n;
+            let (sin, cos) = (golden_angle * k as f64).sin_cos();
+            let ring = (1.0 - z * z).sqrt();
+            DVec3::new(ring * cos, ring * sin, z)
+        })
+        .collect();
+    let closest = offsets
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| offsets[i + 1..].iter().map(move |b| a.distance(*b)))
+        .fold(f64::INFINITY, f64::min);
+    let shell = if closest.is_finite() {
+        satellite
+            .radius
+            .max((2.0 + FRAGMENT_CLEARANCE) * radius / closest)
+    } else {
+        0.0
+    };
+    let mean_offset = offsets.iter().sum::<DVec3>() / n;
+    for offset in &mut offsets {
+        *offset = (*offset - mean_offset) * shell;
+    }
+
+    let mut kicks: Vec<DVec3> = (0..count)
+        .map(|_| random_direction(&mut rng) * rng.random_range(0.0..=kick_speed))
+        .collect();
+    let mean_kick = kicks.iter().sum::<DVec3>() / n;
+    for kick in &mut kicks {
+        *kick -= mean_kick;
+    }
+
+    offsets
+        .into_iter()
+        .zip(kicks)
+        .map(|(offset, kick)| Body {
+            position: satellite.position + offset,
+            velocity: satellite.velocity + kick,
+            mass: satellite.mass / n,
+            radius,
+            kinematic: false,
+        })
+        .collect()
+}
+
+/// Uniformly distributed unit vector, by rejection from the unit cube.
+fn random_direction(rng: &mut ChaCha8Rng) -> DVec3 {
+    loop {
+        let v = DVec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+        );
+        let length_squared = v.length_squared();
+        if length_squared > 1e-6 && length_squared <= 1.0 {
+            return v / length_squared.sqrt();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::collision;
+
+    fn satellite() -> Body {
+        Body {
+            position: DVec3::new(1.0e8, 2.0e7, 0.0),
+            velocity: DVec3::new(0.0, 1.0e3, 10.0),
+            mass: 7.342e22,
+            radius: 1.7374e6,
+            kinematic: false,
+        }
+    }
+
+    #[test]
+    fn roche_limit_matches_density_form() {
+        // Earth–Moon: ρ_M = 5514, ρ_m = 3344 kg/m³, R_M = 6371 km gives a
+        // fluid limit of about 18 400 km and a rigid one of about 9 500 km.
+        let (earth_mass, moon) = (5.9722e24, satellite());
+        let fluid = roche_limit(earth_mass, moon.mass, moon.radius, RocheFormula::Fluid);
+        let rigid = roche_limit(earth_mass, moon.mass, moon.radius, RocheFormula::Rigid);
+        let density_ratio: f64 = 5514.0 / 3344.0;
+        assert!((fluid / (2.44 * 6.371e6 * density_ratio.cbrt()) - 1.0).abs() < 0.01);
+        assert!((rigid / 9.5e6 - 1.0).abs() < 0.02);
+        assert!(fluid > rigid);
+    }
+
+    #[test]
+    fn fragment_count_respects_minimum_mass() {
+        assert_eq!(fragment_count(100.0, 8, 1.0), 8);
+        assert_eq!(fragment_count(5.5, 8, 1.0), 5);
+        assert_eq!(fragment_count(0.5, 8, 1.0), 0);
+    }
+
+    #[test]
+    fn fragments_conserve_mass_and_momentum() {
+        let whole = satellite();
+        for count in [2, 7, 24] {
+            let parts = fragments(&whole, count, 50.0, 7);
+            assert_eq!(parts.len(), count);
+
+            let mass: f64 = parts.iter().map(|p| p.mass).sum();
+            assert!((mass / whole.mass - 1.0).abs() < 1e-12);
+            let momentum: DVec3 = parts.iter().map(|p| p.velocity * p.mass).sum();
+            let expected = whole.velocity * whole.mass;
+            assert!((momentum - expected).length() < expected.length() * 1e-12);
+            let centre: DVec3 = parts.iter().map(|p| p.position * p.mass).sum::<DVec3>() / mass;
+            assert!((centre - whole.position).length() < 1e-3);
+
+            let volume: f64 = parts.iter().map(|p| p.radius.powi(3)).sum();
+            assert!((volume / whole.radius.powi(3) - 1.0).abs() < 1e-12);
+            assert!(collision::contacts(&parts).is_empty());
+            assert!(
+                parts
+                    .iter()
+                    .all(|p| (p.velocity - whole.velocity).length() <= 100.0)
+            );
+        }
+    }
+
+    #[test]
+    fn fragments_are_seeded() {
+        let whole = satellite();
+        assert_eq!(
+            fragments(&whole, 12, 50.0, 1),
+            fragments(&whole, 12, 50.0, 1)
+        );
+        assert_ne!(
+            fragments(&whole, 12, 50.0, 1),
+            fragments(&whole, 12, 50.0, 2)
+        );
+        assert_eq!(body_seed(7, "Moon"), body_seed(7, "Moon"));
+        assert_ne!(body_seed(7, "Moon"), body_seed(7, "Phobos"));
+        assert_ne!(body_seed(7, "Moon"), body_seed(8, "Moon"));
+    }
+}
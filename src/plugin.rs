@@ -2,13 +2,25 @@
 //!
 //! The top-level plugin that bundles all game-specific logic.
 
+use bevy::picking::mesh_picking::{MeshPickingPlugin, MeshPickingSettings};
 use bevy::prelude::*;
 
+use super::astro::vsop87::Truncation;
+use super::astro::{Ephemeris, LeapSecondTable};
 use super::config::GameplayConfig;
+use super::messages::{BodyCollided, BodyDisrupted};
+use super::resources::{
+    ActiveSpacecraft, CameraHotkeys, ClockHotkeys, ConstellationGizmos, FloatingOrigin, Hud,
+    HudHotkeys, MoonPhase, ObserverGizmos, ObserverHotkeys, ObserverView, OrbitCamera, OrbitGizmos,
+    OrbitHotkeys, OrbitOverlay, PredictedPaths, Selection, SimClock, SkyHotkeys, SkyOverlay,
+    SpacecraftHotkeys, TrailClock, ViewScale, ViewScaleHotkeys,
+};
+use super::states::ViewMode;
 use super::systems;
 use launcher::config::AppPaths;
 use launcher::states::AppState;
 use menu::pause::state::gameplay_active;
+use menu::settings::validation::Validate;
 
 pub struct GamePlugin;
 
@@ -17,18 +29,254 @@ impl Plugin for GamePlugin {
         // Load gameplay config during initialization
         let gameplay_config = load_gameplay_config(app);
 
+        let time_step = f64::from(gameplay_config.physics.time_step);
+
+        let hotkeys = ClockHotkeys::from(&gameplay_config.clock.bindings);
+        let sky_hotkeys = SkyHotkeys::from(&gameplay_config.sky.bindings);
+        let sky_overlay = SkyOverlay::from(&gameplay_config.sky);
+        let observer_hotkeys = ObserverHotkeys::from(&gameplay_config.observer.bindings);
+        let orbit_hotkeys = OrbitHotkeys::from(&gameplay_config.orbits.bindings);
+        let orbit_overlay = OrbitOverlay::from(&gameplay_config.orbits);
+        let camera_hotkeys = CameraHotkeys::from(&gameplay_config.camera.bindings);
+        let hud = Hud::from(&gameplay_config.hud);
+        let hud_hotkeys = HudHotkeys::from(&gameplay_config.hud.bindings);
+        let view_scale = ViewScale::from(&gameplay_config.view_scale);
+        let scale_hotkeys = ViewScaleHotkeys::from(&gameplay_config.view_scale.bindings);
+        let spacecraft_hotkeys = SpacecraftHotkeys::from(&gameplay_config.spacecraft.bindings);
+        let leap_seconds = load_leap_seconds(app);
+        let ephemeris = load_ephemeris(app, &gameplay_config);
+
+        // Bodies are picked by ray-casting their meshes. Only entities marked
+        // `Pickable` are tested, which keeps the star field out of it.
+        if !app.is_plugin_added::<MeshPickingPlugin>() {
+            app.add_plugins(MeshPickingPlugin);
+        }
+
         app.insert_resource(gameplay_config)
-            .add_systems(OnEnter(AppState::InGame), systems::setup::setup_game_world)
+            .insert_resource(hotkeys)
+            .insert_resource(sky_hotkeys)
+            .insert_resource(sky_overlay)
+            .insert_resource(observer_hotkeys)
+            .insert_resource(orbit_hotkeys)
+            .insert_resource(orbit_overlay)
+            .insert_resource(camera_hotkeys)
+            .insert_resource(hud)
+            .insert_resource(hud_hotkeys)
+            .insert_resource(view_scale)
+            .insert_resource(scale_hotkeys)
+            .insert_resource(spacecraft_hotkeys)
+            .insert_resource(MeshPickingSettings {
+                require_markers: true,
+                ..default()
+            })
+            .insert_resource(leap_seconds)
+            .insert_resource(ephemeris)
+            .init_resource::<SimClock>()
+            .init_resource::<MoonPhase>()
+            .init_resource::<TrailClock>()
+            .init_resource::<PredictedPaths>()
+            .init_resource::<Selection>()
+            .init_resource::<FloatingOrigin>()
+            .init_resource::<ActiveSpacecraft>()
+            .insert_resource(Time::<Fixed>::from_seconds(time_step))
+            .add_message::<BodyCollided>()
+            .add_message::<BodyDisrupted>()
+            .init_gizmo_group::<ConstellationGizmos>()
+            .init_gizmo_group::<ObserverGizmos>()
+            .init_gizmo_group::<OrbitGizmos>()
+            .add_sub_state::<ViewMode>()
+            .add_systems(
+                OnEnter(AppState::Loading),
+                (
+                    systems::scenario::load_active_scenario,
+                    systems::stars::load_star_catalog,
+                ),
+            )
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (
+                    systems::clock::reset_sim_clock,
+                    systems::setup::setup_game_world,
+                    systems::camera::reset_orbit_camera,
+                    systems::stars::spawn_star_field,
+                    systems::hud::spawn_info_panel,
+                    systems::hud::spawn_scale_label,
+                    systems::spacecraft::spawn_maneuver_panel,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::clock::handle_clock_hotkeys,
+                    systems::sky::handle_sky_hotkeys,
+                    systems::orbit_display::handle_orbit_hotkeys,
+                    systems::hud::handle_hud_hotkeys,
+                    systems::hud::pick_body,
+                    systems::scale::handle_view_scale_hotkeys,
+                    systems::spacecraft::launch_spacecraft,
+                    systems::spacecraft::handle_maneuver_hotkeys
+                        .after(systems::spacecraft::launch_spacecraft)
+                        .after(systems::hud::pick_body),
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(gameplay_active),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::sky::apply_sky_culture,
+                    systems::orbit_display::update_predictions,
+                    systems::hud::update_info_panel.after(systems::hud::pick_body),
+                    systems::hud::update_scale_label,
+                    systems::spacecraft::update_maneuver_panel,
+                    systems::scale::advance_view_scale,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::terrain::attach_terrain,
+                    systems::terrain::update_terrain_lod,
+                    systems::terrain::finish_terrain_chunks,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::observer::toggle_view_mode,
+                    systems::observer::handle_observer_hotkeys
+                        .run_if(resource_exists::<ObserverView>),
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(gameplay_active),
+            )
             .add_systems(
                 Update,
-                systems::gameplay::rotate_planet
+                systems::camera::handle_camera_input
+                    .run_if(in_state(ViewMode::Orbital))
+                    .run_if(resource_exists::<OrbitCamera>)
+                    .run_if(gameplay_active),
+            )
+            .add_systems(
+                OnEnter(ViewMode::Observer),
+                systems::observer::enter_observer_view,
+            )
+            .add_systems(
+                OnExit(ViewMode::Observer),
+                systems::observer::exit_observer_view,
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    systems::observer::update_observer_camera
+                        .run_if(resource_exists::<ObserverView>)
+                        .before(systems::stars::center_celestial_sphere),
+                    systems::camera::update_orbit_camera
+                        .run_if(in_state(ViewMode::Orbital))
+                        .run_if(resource_exists::<OrbitCamera>)
+                        .before(systems::stars::center_celestial_sphere),
+                    systems::origin::sync_render_transforms
+                        .after(systems::observer::update_observer_camera)
+                        .after(systems::camera::update_orbit_camera)
+                        .before(TransformSystems::Propagate),
+                    systems::stars::center_celestial_sphere.before(TransformSystems::Propagate),
+                    (
+                        systems::sky::draw_constellations,
+                        systems::sky::position_constellation_labels,
+                        systems::orbit_display::draw_orbits,
+                        systems::spacecraft::draw_maneuver_nodes,
+                        (
+                            systems::observer::draw_alt_az_grid,
+                            systems::observer::position_horizon_labels,
+                        )
+                            .run_if(resource_exists::<ObserverView>),
+                    )
+                        .after(TransformSystems::Propagate),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    systems::clock::advance_sim_clock,
+                    systems::orbit::propagate_ephemeris,
+                    systems::orbit::propagate_lunar,
+                    systems::orbit::propagate_on_rails,
+                    systems::physics::step_bodies,
+                    systems::spacecraft::execute_maneuvers,
+                    systems::physics::resolve_collisions,
+                    systems::physics::disrupt_satellites,
+                    systems::gameplay::rotate_planet,
+                    systems::moon::update_moon_phase,
+                    systems::orbit_display::record_trails,
+                )
+                    .chain()
                     .run_if(in_state(AppState::InGame))
                     .run_if(gameplay_active),
             )
             .add_systems(
                 OnExit(AppState::InGame),
-                systems::cleanup::cleanup_game_world,
+                (
+                    systems::cleanup::cleanup_game_world,
+                    systems::scenario::unload_active_scenario,
+                    systems::sky::despawn_constellation_labels,
+                    systems::hud::despawn_hud,
+                    systems::spacecraft::clear_active_spacecraft,
+                ),
+            );
+    }
+}
+
+fn load_leap_seconds(app: &App) -> LeapSecondTable {
+    let paths = app
+        .world()
+        .get_resource::<AppPaths>()
+        .expect("AppPaths must be initialized");
+    let path = paths.assets_dir.join("data").join("leap_seconds.ron");
+
+    match LeapSecondTable::load(&path) {
+        Ok(table) => {
+            info!("[Time] Loaded {} leap-second entries", table.len());
+            table
+        }
+        Err(e) => {
+            error!("[Time] {}. UTC will be treated as TAI.", e);
+            LeapSecondTable::default()
+        }
+    }
+}
+
+fn load_ephemeris(app: &App, config: &GameplayConfig) -> Ephemeris {
+    let paths = app
+        .world()
+        .get_resource::<AppPaths>()
+        .expect("AppPaths must be initialized");
+    let dir = paths.assets_dir.join(&config.ephemeris.directory);
+    let truncation = Truncation {
+        min_amplitude: config.ephemeris.min_amplitude,
+        max_terms: config.ephemeris.max_terms,
+    };
+
+    match Ephemeris::load(&dir, &truncation) {
+        Ok(ephemeris) => {
+            info!(
+                "[Ephemeris] Loaded VSOP87 series for {} planets ({} terms)",
+                ephemeris.len(),
+                ephemeris.term_count()
             );
+            ephemeris
+        }
+        Err(e) => {
+            error!(
+                "[Ephemeris] {}. Ephemeris bodies will stay at their parents.",
+                e
+            );
+            Ephemeris::default()
+        }
     }
 }
 
@@ -42,10 +290,19 @@ fn load_gameplay_config(app: &App) -> GameplayConfig {
     if config_path.exists() {
         match std::fs::read_to_string(&config_path) {
             Ok(content) => match ron::from_str::<GameplayConfig>(&content) {
-                Ok(config) => {
-                    info!("[Config] Loaded gameplay settings from {:?}", config_path);
-                    config
-                }
+                Ok(config) => match config.validate() {
+                    Ok(()) => {
+                        info!("[Config] Loaded gameplay settings from {:?}", config_path);
+                        config
+                    }
+                    Err(errors) => {
+                        error!(
+                            "[Config] Invalid gameplay.ron: {}. Using defaults.",
+                            errors.join("; ")
+                        );
+                        GameplayConfig::default()
+                    }
+                },
                 Err(e) => {
                     error!(
                         "[Config] Failed to parse gameplay.ron: {}. Using defaults.",
@@ -0,0 +1,18 @@
+//! # Procedural Generation
+//!
+//! Seeded, engine-independent generation of scenario content. Every
+//! generator takes an explicit seed and draws from `ChaCha8Rng`, whose
+//! output is identical across platforms, so a seed in a bug report
+//! reproduces the same world.
+//!
+//! - `noise`: Seeded gradient noise and its fractal sums.
+//! - `planet`: Classes and physical traits of single planets.
+//! - `system`: Whole planetary systems expanded into scenario bodies.
+//! - `terrain`: Noise-displaced cube-sphere chunks and their quadtree LOD.
+
+pub mod noise;
+pub mod planet;
+pub mod system;
+pub mod terrain;
+
+pub use planet::{GeneratedPlanet, Palette, PlanetClass, PlanetGenerator};
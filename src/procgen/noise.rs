@@ -0,0 +1,171 @@
+//! # Noise
+//!
+//! Seeded 3D gradient noise (Perlin's 2002 "improved noise") and fractal
+//! Brownian motion built from it. Sampling on the unit sphere gives
+//! seamless planet-wide height fields with no pole or seam artifacts.
+
+use bevy::math::DVec3;
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+/// Gradient noise with a seeded permutation table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perlin {
+    /// Shuffled `0..256`, repeated once so lookups need no wrapping.
+    permutation: [u8; 512],
+}
+
+/// Octave parameters of `Perlin::fbm`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fractal {
+    /// Frequency of the first octave.
+    pub frequency: f64,
+    pub octaves: u32,
+    /// Frequency multiplier between octaves.
+    pub lacunarity: f64,
+    /// Amplitude multiplier between octaves.
+    pub persistence: f64,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in (1..table.len()).rev() {
+            table.swap(i, rng.random_range(0..=i));
+        }
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    /// Noise at `point`, within about `[-1, 1]` and zero on integer
+    /// lattice points.
+    pub fn sample(&self, point: DVec3) -> f64 {
+        let cell = point.floor();
+        let local = point - cell;
+        let [x, y, z] = [cell.x, cell.y, cell.z].map(|c| (c.rem_euclid(256.0)) as usize);
+        let p = &self.permutation;
+
+        let a = p[x] as usize + y;
+        let aa = p[a] as usize + z;
+        let ab = p[a + 1] as usize + z;
+        let b = p[x + 1] as usize + y;
+        let ba = p[b] as usize + z;
+        let bb = p[b + 1] as usize + z;
+
+        let (u, v, w) = (fade(local.x), fade(local.y), fade(local.z));
+        let (fx, fy, fz) = (local.x, local.y, local.z);
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p[aa], fx, fy, fz), grad(p[ba], fx - 1.0, fy, fz)),
+                lerp(
+                    u,
+                    grad(p[ab], fx, fy - 1.0, fz),
+                    grad(p[bb], fx - 1.0, fy - 1.0, fz),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p[aa + 1], fx, fy, fz - 1.0),
+                    grad(p[ba + 1], fx - 1.0, fy, fz - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p[ab + 1], fx, fy - 1.0, fz - 1.0),
+                    grad(p[bb + 1], fx - 1.0, fy - 1.0, fz - 1.0),
+                ),
+            ),
+        )
+    }
+
+    /// Sum of `fractal.octaves` octaves, normalized to about `[-1, 1]`.
+    pub fn fbm(&self, point: DVec3, fractal: &Fractal) -> f64 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (fractal.frequency, 1.0);
+        for octave in 0..fractal.octaves {
+            // Offset each octave so their lattice points do not line up.
+            let offset = DVec3::splat(f64::from(octave) * 17.31);
+            sum += amplitude * self.sample(point * frequency + offset);
+            total += amplitude;
+            frequency *= fractal.lacunarity;
+            amplitude *= fractal.persistence;
+        }
+        if total > 0.0 { sum / total } else { 0.0 }
+    }
+}
+
+/// Quintic ease curve `6t⁵ − 15t⁴ + 10t³`.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Dot product with one of the twelve cube-edge gradients.
+fn grad(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..=3 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> impl Iterator<Item = DVec3> {
+        (0..2000).map(|i| {
+            let t = f64::from(i);
+            DVec3::new(t * 0.173 - 50.0, (t * 0.311).sin() * 40.0, t * 0.057)
+        })
+    }
+
+    #[test]
+    fn noise_is_seeded_and_bounded() {
+        let (a, b) = (Perlin::new(1), Perlin::new(2));
+        assert_eq!(a, Perlin::new(1));
+        let mut differs = false;
+        for point in points() {
+            let value = a.sample(point);
+            assert!(value.abs() <= 1.0 + 1e-9, "{value} at {point}");
+            assert_eq!(value, Perlin::new(1).sample(point));
+            differs |= value != b.sample(point);
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn noise_is_zero_on_lattice_and_continuous() {
+        let noise = Perlin::new(9);
+        assert_eq!(noise.sample(DVec3::new(3.0, -4.0, 7.0)), 0.0);
+        for point in points() {
+            let step = DVec3::splat(1e-6);
+            assert!((noise.sample(point) - noise.sample(point + step)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn fbm_is_normalized() {
+        let noise = Perlin::new(4);
+        let fractal = Fractal {
+            frequency: 2.0,
+            octaves: 6,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        };
+        let values: Vec<f64> = points().map(|p| noise.fbm(p * 0.01, &fractal)).collect();
+        assert!(values.iter().all(|v| v.abs() <= 1.0));
+        let spread = values.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+        assert!(spread > 0.2);
+    }
+}
@@ -0,0 +1,328 @@
+//! # Planets
+//!
+//! Seeded generation of individual planets. A planet's class fixes the
+//! ranges its physical traits are drawn from; mass is drawn log-uniformly
+//! in units of `PlanetConfig::default_mass`, the radius follows from the
+//! drawn bulk density and is clamped to the configured bounds, after which
+//! the density is recomputed so mass, radius and density always agree.
+
+use std::f64::consts::PI;
+
+use bevy::color::{Hsla, Srgba};
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::config::gameplay::PlanetConfig;
+
+/// Broad composition class of a generated planet.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanetClass {
+    Rocky,
+    Ocean,
+    Ice,
+    GasGiant,
+}
+
+impl PlanetClass {
+    pub const ALL: [PlanetClass; 4] = [
+        PlanetClass::Rocky,
+        PlanetClass::Ocean,
+        PlanetClass::Ice,
+        PlanetClass::GasGiant,
+    ];
+
+    /// Localization key of the class name.
+    pub fn key(self) -> &'static str {
+        match self {
+            PlanetClass::Rocky => "planet-class-rocky",
+            PlanetClass::Ocean => "planet-class-ocean",
+            PlanetClass::Ice => "planet-class-ice",
+            PlanetClass::GasGiant => "planet-class-gas-giant",
+        }
+    }
+
+    /// Picks a class for an orbit `distance` from a star whose frost line
+    /// is at `frost_line`: rock and water inside it, ice and gas beyond.
+    pub fn at_distance(rng: &mut ChaCha8Rng, distance: f64, frost_line: f64) -> PlanetClass {
+        let weights = if distance < frost_line {
+            [0.6, 0.35, 0.0, 0.05]
+        } else {
+            [0.1, 0.05, 0.4, 0.45]
+        };
+        let mut pick = rng.random_range(0.0..1.0);
+        for (class, weight) in PlanetClass::ALL.into_iter().zip(weights) {
+            if pick < weight {
+                return class;
+            }
+            pick -= weight;
+        }
+        PlanetClass::GasGiant
+    }
+
+    fn traits(self) -> &'static ClassTraits {
+        match self {
+            PlanetClass::Rocky => &ROCKY,
+            PlanetClass::Ocean => &OCEAN,
+            PlanetClass::Ice => &ICE,
+            PlanetClass::GasGiant => &GAS_GIANT,
+        }
+    }
+}
+
+/// Ranges the traits of one class are drawn from.
+struct ClassTraits {
+    /// Mass in units of `PlanetConfig::default_mass`, drawn log-uniformly.
+    mass: (f64, f64),
+    /// Bulk density (kg/m³).
+    density: (f64, f64),
+    /// Bond albedo.
+    albedo: (f32, f32),
+    /// Sidereal rotation period (hours), drawn log-uniformly.
+    rotation_hours: (f64, f64),
+    /// Base color hue (degrees), saturation and lightness.
+    hue: (f32, f32),
+    saturation: (f32, f32),
+    lightness: (f32, f32),
+}
+
+const ROCKY: ClassTraits = ClassTraits {
+    mass: (0.05, 5.0),
+    density: (3900.0, 5600.0),
+    albedo: (0.1, 0.35),
+    rotation_hours: (10.0, 500.0),
+    hue: (10.0, 40.0),
+    saturation: (0.2, 0.5),
+    lightness: (0.3, 0.55),
+};
+
+const OCEAN: ClassTraits = ClassTraits {
+    mass: (0.3, 8.0),
+    density: (3000.0, 5500.0),
+    albedo: (0.25, 0.4),
+    rotation_hours: (12.0, 48.0),
+    hue: (190.0, 230.0),
+    saturation: (0.5, 0.8),
+    lightness: (0.35, 0.55),
+};
+
+const ICE: ClassTraits = ClassTraits {
+    mass: (0.01, 3.0),
+    density: (1500.0, 2500.0),
+    albedo: (0.5, 0.9),
+    rotation_hours: (8.0, 100.0),
+    hue: (180.0, 220.0),
+    saturation: (0.05, 0.3),
+    lightness: (0.75, 0.92),
+};
+
+const GAS_GIANT: ClassTraits = ClassTraits {
+    mass: (10.0, 2000.0),
+    density: (600.0, 1600.0),
+    albedo: (0.3, 0.55),
+    rotation_hours: (9.0, 20.0),
+    hue: (25.0, 55.0),
+    saturation: (0.3, 0.6),
+    lightness: (0.5, 0.7),
+};
+
+/// Chance that the axial tilt is drawn from the wide range instead of the
+/// near-upright one.
+const STEEP_TILT_CHANCE: f64 = 0.2;
+/// Upper bound of the near-upright axial tilt range (rad).
+const UPRIGHT_TILT: f64 = 0.5;
+
+/// Three colors a planet is painted with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub base: Srgba,
+    pub secondary: Srgba,
+    pub accent: Srgba,
+}
+
+impl Palette {
+    /// Colors as hex strings, base first.
+    pub fn to_hex(&self) -> Vec<String> {
+        [self.base, self.secondary, self.accent]
+            .iter()
+            .map(Srgba::to_hex)
+            .collect()
+    }
+}
+
+/// A generated planet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratedPlanet {
+    /// Seed the planet was generated from; terrain reuses it.
+    pub seed: u64,
+    pub class: PlanetClass,
+    /// Mean radius (m).
+    pub radius: f64,
+    /// Bulk density (kg/m³).
+    pub density: f64,
+    /// Mass (kg).
+    pub mass: f64,
+    /// Bond albedo.
+    pub albedo: f32,
+    /// Angle between the pole and the orbit normal (rad); above π/2 the
+    /// planet spins retrograde.
+    pub axial_tilt: f64,
+    /// Sidereal rotation period (s).
+    pub rotation_period: f64,
+    pub palette: Palette,
+}
+
+/// Draws planets within the bounds of a `PlanetConfig`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanetGenerator {
+    /// Mass unit of the class mass ranges (kg).
+    pub reference_mass: f64,
+    /// Radius bounds (m).
+    pub min_radius: f64,
+    pub max_radius: f64,
+}
+
+impl PlanetGenerator {
+    pub fn new(config: &PlanetConfig) -> Self {
+        Self {
+            reference_mass: config.default_mass,
+            min_radius: f64::from(config.min_radius),
+            max_radius: f64::from(config.max_radius),
+        }
+    }
+
+    /// Generates a planet of `class`. The same seed always gives the same
+    /// planet.
+    pub fn generate(&self, class: PlanetClass, seed: u64) -> GeneratedPlanet {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let traits = class.traits();
+
+        let mass = self.reference_mass * log_uniform(&mut rng, traits.mass);
+        let drawn_density = rng.random_range(traits.density.0..=traits.density.1);
+        let radius = (3.0 * mass / (4.0 * PI * drawn_density))
+            .cbrt()
+            .clamp(self.min_radius, self.max_radius);
+        let density = mass / (4.0 / 3.0 * PI * radius.powi(3));
+
+        let albedo = rng.random_range(traits.albedo.0..=traits.albedo.1);
+        let axial_tilt = if rng.random_bool(STEEP_TILT_CHANCE) {
+            rng.random_range(UPRIGHT_TILT..=PI)
+        } else {
+            rng.random_range(0.0..UPRIGHT_TILT)
+        };
+        let rotation_period = log_uniform(&mut rng, traits.rotation_hours) * 3600.0;
+
+        GeneratedPlanet {
+            seed,
+            class,
+            radius,
+            density,
+            mass,
+            albedo,
+            axial_tilt,
+            rotation_period,
+            palette: palette(&mut rng, traits),
+        }
+    }
+}
+
+/// Value drawn uniformly in `ln` between the bounds of `range`.
+fn log_uniform(rng: &mut ChaCha8Rng, range: (f64, f64)) -> f64 {
+    rng.random_range(range.0.ln()..=range.1.ln()).exp()
+}
+
+/// Base color from the class ranges, a darker neighbouring hue and a
+/// lighter accent.
+fn palette(rng: &mut ChaCha8Rng, traits: &ClassTraits) -> Palette {
+    let hue = rng.random_range(traits.hue.0..=traits.hue.1);
+    let saturation = rng.random_range(traits.saturation.0..=traits.saturation.1);
+    let lightness = rng.random_range(traits.lightness.0..=traits.lightness.1);
+    let secondary_hue = (hue + rng.random_range(-15.0..=15.0)).rem_euclid(360.0);
+    let accent_hue = (hue + rng.random_range(-40.0..=40.0)).rem_euclid(360.0);
+
+    Palette {
+        base: Hsla::hsl(hue, saturation, lightness).into(),
+        secondary: Hsla::hsl(secondary_hue, saturation * 0.8, lightness * 0.75).into(),
+        accent: Hsla::hsl(accent_hue, saturation, (lightness + 0.2).min(0.95)).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_planet() {
+        let generator = PlanetGenerator::new(&PlanetConfig::default());
+        for class in PlanetClass::ALL {
+            assert_eq!(generator.generate(class, 7), generator.generate(class, 7));
+            assert_ne!(generator.generate(class, 7), generator.generate(class, 8));
+        }
+    }
+
+    #[test]
+    fn traits_stay_within_bounds() {
+        let config = PlanetConfig::default();
+        let generator = PlanetGenerator::new(&config);
+        for class in PlanetClass::ALL {
+            let traits = class.traits();
+            for seed in 0..200 {
+                let planet = generator.generate(class, seed);
+                let mass = planet.mass / config.default_mass;
+                assert!(mass >= traits.mass.0 * 0.999 && mass <= traits.mass.1 * 1.001);
+                assert!(planet.density >= traits.density.0 - 1e-6);
+                assert!(planet.density <= traits.density.1 + 1e-6);
+                assert!(planet.albedo >= traits.albedo.0 && planet.albedo <= traits.albedo.1);
+                assert!((0.0..=PI).contains(&planet.axial_tilt));
+                let hours = planet.rotation_period / 3600.0;
+                assert!(hours >= traits.rotation_hours.0 * 0.999);
+                assert!(hours <= traits.rotation_hours.1 * 1.001);
+            }
+        }
+    }
+
+    #[test]
+    fn radius_is_clamped_and_density_follows() {
+        let config = PlanetConfig {
+            min_radius: 5.0e6,
+            max_radius: 2.0e7,
+            ..PlanetConfig::default()
+        };
+        let generator = PlanetGenerator::new(&config);
+        for class in PlanetClass::ALL {
+            for seed in 0..50 {
+                let planet = generator.generate(class, seed);
+                assert!((5.0e6..=2.0e7).contains(&planet.radius));
+                let volume = 4.0 / 3.0 * PI * planet.radius.powi(3);
+                assert!((planet.density * volume / planet.mass - 1.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn classes_follow_the_frost_line() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for _ in 0..500 {
+            assert_ne!(
+                PlanetClass::at_distance(&mut rng, 1.0, 2.0),
+                PlanetClass::Ice
+            );
+        }
+        let outer: Vec<_> = (0..500)
+            .map(|_| PlanetClass::at_distance(&mut rng, 3.0, 2.0))
+            .collect();
+        for class in PlanetClass::ALL {
+            assert!(outer.contains(&class));
+        }
+    }
+
+    #[test]
+    fn gas_giants_resemble_jupiter() {
+        let generator = PlanetGenerator::new(&PlanetConfig::default());
+        let radii: Vec<f64> = (0..100)
+            .map(|seed| generator.generate(PlanetClass::GasGiant, seed).radius)
+            .collect();
+        // Saturn to a few Jupiter masses at gas-giant densities.
+        assert!(radii.iter().all(|r| (1.5e7..2.0e8).contains(r)));
+    }
+}
@@ -0,0 +1,192 @@
+//! # Planetary Systems
+//!
+//! Expands a scenario's `GeneratedSystemSpec` into ordinary bodies. One
+//! system seed draws every orbit, class and per-planet seed in order, so a
+//! seed fully reproduces the system.
+
+use std::f64::consts::TAU;
+
+use rand::{RngExt, SeedableRng, rngs::ChaCha8Rng};
+
+use super::planet::{GeneratedPlanet, PlanetClass, PlanetGenerator};
+use crate::config::gameplay::PlanetConfig;
+use crate::constants::{ASTRONOMICAL_UNIT, SOLAR_MASS};
+use crate::scenario::{
+    BodySpec, GeneratedSystemSpec, MotionSpec, OrbitSpec, Scenario, SurfaceSpec,
+};
+
+/// Water-ice frost line of a Sun-like star (m).
+const SOLAR_FROST_LINE: f64 = 2.7 * ASTRONOMICAL_UNIT;
+/// Largest orbital eccentricity drawn for a generated planet.
+const MAX_ECCENTRICITY: f64 = 0.1;
+/// Largest orbital inclination drawn for a generated planet (rad).
+const MAX_INCLINATION: f64 = 0.05;
+
+/// Frost line of a star of `mass`, scaled by the luminosity as `L ∝ M⁴`.
+pub fn frost_line(star_mass: f64) -> f64 {
+    SOLAR_FROST_LINE * (star_mass / SOLAR_MASS).powi(2)
+}
+
+/// Generates the planets of `spec` around a star of `star_mass`, innermost
+/// first, each with its orbit.
+pub fn generate(
+    spec: &GeneratedSystemSpec,
+    star_mass: f64,
+    config: &PlanetConfig,
+) -> Vec<(GeneratedPlanet, OrbitSpec)> {
+    let mut rng = ChaCha8Rng::seed_from_u64(spec.seed);
+    let generator = PlanetGenerator::new(config);
+    let frost_line = frost_line(star_mass);
+    let ratio = spec.outer_orbit / spec.inner_orbit;
+    let count = f64::from(spec.planets);
+
+    (0..spec.planets)
+        .map(|index| {
+            let slot = (f64::from(index) + rng.random_range(0.25..0.75)) / count;
+            let semi_major_axis = spec.inner_orbit * ratio.powf(slot);
+            let class = PlanetClass::at_distance(&mut rng, semi_major_axis, frost_line);
+            let orbit = OrbitSpec {
+                semi_major_axis,
+                eccentricity: MAX_ECCENTRICITY * rng.random_range(0.0f64..1.0).powi(2),
+                inclination: rng.random_range(0.0..MAX_INCLINATION),
+                longitude_of_ascending_node: rng.random_range(0.0..TAU),
+                argument_of_periapsis: rng.random_range(0.0..TAU),
+                mean_anomaly_at_epoch: rng.random_range(0.0..TAU),
+                epoch: 0.0,
+            };
+            (generator.generate(class, rng.random()), orbit)
+        })
+        .collect()
+}
+
+/// Appends the planets of `scenario.generated` to its bodies. Returns the
+/// number of planets added.
+///
+/// Expects a validated scenario; a missing parent adds nothing.
+pub fn populate(scenario: &mut Scenario, config: &PlanetConfig) -> usize {
+    let Some(spec) = &scenario.generated else {
+        return 0;
+    };
+    let Some(star_mass) = scenario
+        .bodies
+        .iter()
+        .find(|body| body.name == spec.parent)
+        .map(|body| body.mass)
+    else {
+        return 0;
+    };
+
+    let bodies: Vec<BodySpec> = generate(spec, star_mass, config)
+        .into_iter()
+        .zip('b'..='z')
+        .map(|((planet, orbit), letter)| BodySpec {
+            name: format!("{} {letter}", spec.parent),
+            mass: planet.mass,
+            radius: planet.radius,
+            parent: Some(spec.parent.clone()),
+            motion: MotionSpec::Orbit(orbit),
+            color: planet.palette.base.to_hex(),
+            rotation_period: Some(planet.rotation_period),
+            axial_tilt: planet.axial_tilt,
+            surface: Some(SurfaceSpec {
+                class: planet.class,
+                albedo: planet.albedo,
+                palette: planet.palette.to_hex(),
+                seed: planet.seed,
+            }),
+            ..Default::default()
+        })
+        .collect();
+    let added = bodies.len();
+    scenario.bodies.extend(bodies);
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use menu::settings::validation::Validate;
+    use std::path::Path;
+
+    fn scenario(seed: u64) -> Scenario {
+        Scenario {
+            bodies: vec![BodySpec {
+                name: "Star".to_string(),
+                mass: SOLAR_MASS,
+                radius: 7.0e8,
+                ..Default::default()
+            }],
+            generated: Some(GeneratedSystemSpec {
+                seed,
+                parent: "Star".to_string(),
+                planets: 8,
+                inner_orbit: 0.3 * ASTRONOMICAL_UNIT,
+                outer_orbit: 30.0 * ASTRONOMICAL_UNIT,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_seed_same_system() {
+        let config = PlanetConfig::default();
+        let (mut a, mut b, mut c) = (scenario(11), scenario(11), scenario(12));
+        assert_eq!(populate(&mut a, &config), 8);
+        populate(&mut b, &config);
+        populate(&mut c, &config);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn generated_systems_are_valid_and_ordered() {
+        let config = PlanetConfig::default();
+        for seed in 0..20 {
+            let mut system = scenario(seed);
+            populate(&mut system, &config);
+            system.validate().unwrap();
+
+            let orbits: Vec<f64> = system
+                .bodies
+                .iter()
+                .filter_map(|body| match &body.motion {
+                    MotionSpec::Orbit(orbit) => Some(orbit.semi_major_axis),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(orbits.len(), 8);
+            assert!(orbits.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(orbits[0] >= 0.3 * ASTRONOMICAL_UNIT);
+            assert!(orbits[7] <= 30.0 * ASTRONOMICAL_UNIT);
+            assert_eq!(system.bodies[1].name, "Star b");
+        }
+    }
+
+    #[test]
+    fn exploration_scenario_generates_its_planets() {
+        let mut exploration = crate::scenario::parse_scenario(
+            Path::new("scenarios/exploration.ron"),
+            include_str!("../../assets/scenarios/exploration.ron"),
+        )
+        .unwrap();
+        assert_eq!(populate(&mut exploration, &PlanetConfig::default()), 7);
+        exploration.validate().unwrap();
+    }
+
+    #[test]
+    fn ice_forms_beyond_the_frost_line() {
+        let config = PlanetConfig::default();
+        let spec = scenario(0).generated.unwrap();
+        for seed in 0..20 {
+            let spec = GeneratedSystemSpec {
+                seed,
+                ..spec.clone()
+            };
+            for (planet, orbit) in generate(&spec, SOLAR_MASS, &config) {
+                if planet.class == PlanetClass::Ice {
+                    assert!(orbit.semi_major_axis >= frost_line(SOLAR_MASS));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! # Terrain
+//!
+//! Noise-displaced cube-sphere surfaces split into a quadtree of chunks.
+//! Each of the six cube faces is the root of a quadtree; a chunk at level
+//! `l` covers `1 / 2^l` of its face along each axis. Chunks are refined
+//! nearest the camera first until the vertex budget is spent, and every
+//! chunk carries the same vertex count so budgets are exact.
+//!
+//! Chunk meshes are plain vertex arrays relative to the chunk's centre, so
+//! they can be built off the main thread and at any distance from the
+//! origin without losing `f32` precision.
+
+use std::f64::consts::FRAC_PI_2;
+
+use bevy::math::DVec3;
+
+use super::noise::{Fractal, Perlin};
+use super::planet::PlanetClass;
+use crate::config::gameplay::TerrainConfig;
+
+/// Faces of the cube the sphere is projected from.
+pub const CUBE_FACES: usize = 6;
+/// Largest accepted `TerrainConfig::resolution`.
+pub const MAX_TERRAIN_RESOLUTION: u32 = 129;
+/// Deepest accepted `TerrainConfig::max_depth`.
+pub const MAX_TERRAIN_DEPTH: u8 = 20;
+/// Depth of the crack-hiding skirt, as a fraction of the chunk width.
+const SKIRT_DEPTH: f64 = 0.05;
+
+/// Outward normal and the two in-plane axes of each cube face.
+const FACES: [[DVec3; 3]; CUBE_FACES] = [
+    [DVec3::X, DVec3::Y, DVec3::Z],
+    [DVec3::NEG_X, DVec3::Z, DVec3::Y],
+    [DVec3::Y, DVec3::Z, DVec3::X],
+    [DVec3::NEG_Y, DVec3::X, DVec3::Z],
+    [DVec3::Z, DVec3::X, DVec3::Y],
+    [DVec3::NEG_Z, DVec3::Y, DVec3::X],
+];
+
+/// One node of a face quadtree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChunkId {
+    pub face: u8,
+    pub level: u8,
+    /// Column and row within the face, each in `0..2^level`.
+    pub x: u32,
+    pub y: u32,
+}
+
+impl ChunkId {
+    /// The six face roots.
+    pub fn roots() -> [ChunkId; CUBE_FACES] {
+        std::array::from_fn(|face| ChunkId {
+            face: face as u8,
+            level: 0,
+            x: 0,
+            y: 0,
+        })
+    }
+
+    /// The four quadrants one level down.
+    pub fn children(self) -> [ChunkId; 4] {
+        [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(dx, dy)| ChunkId {
+            face: self.face,
+            level: self.level + 1,
+            x: self.x * 2 + dx,
+            y: self.y * 2 + dy,
+        })
+    }
+
+    /// Unit direction through face coordinates `(s, t)` of this chunk,
+    /// each in `[0, 1]`.
+    pub fn direction(self, s: f64, t: f64) -> DVec3 {
+        let scale = 1.0 / f64::from(1u32 << self.level);
+        let u = (f64::from(self.x) + s) * scale * 2.0 - 1.0;
+        let v = (f64::from(self.y) + t) * scale * 2.0 - 1.0;
+        let [normal, u_axis, v_axis] = FACES[self.face as usize];
+        cube_to_sphere(normal + u_axis * u + v_axis * v)
+    }
+
+    /// Approximate width of the chunk on a sphere of `radius`.
+    pub fn width(self, radius: f64) -> f64 {
+        radius * FRAC_PI_2 / f64::from(1u32 << self.level)
+    }
+}
+
+/// Projects a point on the unit cube onto the unit sphere with the
+/// area-equalizing mapping, which keeps chunks of one level similar in size.
+fn cube_to_sphere(p: DVec3) -> DVec3 {
+    let (x2, y2, z2) = (p.x * p.x, p.y * p.y, p.z * p.z);
+    DVec3::new(
+        p.x * (1.0 - y2 / 2.0 - z2 / 2.0 + y2 * z2 / 3.0).sqrt(),
+        p.y * (1.0 - z2 / 2.0 - x2 / 2.0 + z2 * x2 / 3.0).sqrt(),
+        p.z * (1.0 - x2 / 2.0 - y2 / 2.0 + x2 * y2 / 3.0).sqrt(),
+    )
+}
+
+/// Vertices in one chunk mesh: the `resolution²` grid plus its skirt.
+pub const fn vertices_per_chunk(resolution: u32) -> usize {
+    let n = resolution as usize;
+    n * n + 4 * (n - 1)
+}
+
+/// Indices in one chunk mesh: two triangles per grid cell and per skirt
+/// segment.
+pub const fn indices_per_chunk(resolution: u32) -> usize {
+    let n = resolution as usize;
+    6 * (n - 1) * (n - 1) + 6 * 4 * (n - 1)
+}
+
+/// Height field of one planet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainShape {
+    noise: Perlin,
+    fractal: Fractal,
+    /// Mean radius (m).
+    pub radius: f64,
+    /// Largest displacement (m).
+    pub amplitude: f64,
+    /// Clamp depressions to the mean radius, as a sea surface.
+    pub sea: bool,
+}
+
+impl TerrainShape {
+    /// Terrain of a planet of `class` and `radius`, or `None` for classes
+    /// without a solid surface.
+    pub fn new(class: PlanetClass, seed: u64, radius: f64, config: &TerrainConfig) -> Option<Self> {
+        let relief = match class {
+            PlanetClass::Rocky => 1.0,
+            PlanetClass::Ocean => 0.5,
+            PlanetClass::Ice => 0.3,
+            PlanetClass::GasGiant => return None,
+        };
+        Some(Self {
+            noise: Perlin::new(seed),
+            fractal: Fractal {
+                frequency: config.frequency,
+                octaves: config.octaves,
+                lacunarity: config.lacunarity,
+                persistence: config.persistence,
+            },
+            radius,
+            amplitude: radius * config.amplitude * relief,
+            sea: class == PlanetClass::Ocean,
+        })
+    }
+
+    /// Displacement of the surface along unit `direction`, within
+    /// `[-amplitude, amplitude]` (m).
+    pub fn height(&self, direction: DVec3) -> f64 {
+        let height = self.noise.fbm(direction, &self.fractal) * self.amplitude;
+        if self.sea { height.max(0.0) } else { height }
+    }
+
+    /// Surface point along unit `direction`, relative to the planet centre.
+    pub fn surface(&self, direction: DVec3) -> DVec3 {
+        direction * (self.radius + self.height(direction))
+    }
+}
+
+/// Vertex arrays of one chunk, relative to `center`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChunkMesh {
+    /// Chunk origin relative to the planet centre (m).
+    pub center: DVec3,
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Height of each vertex within `[-1, 1]` of the amplitude, for
+    /// coloring.
+    pub elevations: Vec<f32>,
+    pub indices: Vec<u32>,
+}
+
+/// Builds the mesh of chunk `id` with `resolution` vertices per edge.
+///
+/// Normals come from a grid one vertex wider than the chunk, so they match
+/// across chunk borders. A skirt hangs below the border to hide cracks
+/// against coarser neighbours.
+pub fn build_chunk(id: ChunkId, resolution: u32, shape: &TerrainShape) -> ChunkMesh {
+    let n = resolution as usize;
+    let step = 1.0 / (n - 1) as f64;
+    let center = shape.surface(id.direction(0.5, 0.5));
+
+    // Surface points on an (n + 2)² grid with a one-vertex border.
+    let wide = n + 2;
+    let grid: Vec<DVec3> = (0..wide * wide)
+        .map(|k| {
+            let (i, j) = (k % wide, k / wide);
+            let s = (i as f64 - 1.0) * step;
+            let t = (j as f64 - 1.0) * step;
+            shape.surface(id.direction(s, t))
+        })
+        .collect();
+    let at = |i: usize, j: usize| grid[(j + 1) * wide + i + 1];
+
+    let mut mesh = ChunkMesh {
+        center,
+        positions: Vec::with_capacity(vertices_per_chunk(resolution)),
+        normals: Vec::with_capacity(vertices_per_chunk(resolution)),
+        elevations: Vec::with_capacity(vertices_per_chunk(resolution)),
+        indices: Vec::with_capacity(indices_per_chunk(resolution)),
+    };
+    let elevation = |point: DVec3| {
+        if shape.amplitude > 0.0 {
+            ((point.length() - shape.radius) / shape.amplitude) as f32
+        } else {
+            0.0
+        }
+    };
+
+    for j in 0..n {
+        for i in 0..n {
+            let point = at(i, j);
+            let du = grid[(j + 1) * wide + i + 2] - grid[(j + 1) * wide + i];
+            let dv = grid[(j + 2) * wide + i + 1] - grid[j * wide + i + 1];
+            let mut normal = du.cross(dv).normalize_or(point.normalize());
+            if normal.dot(point) < 0.0 {
+                normal = -normal;
+            }
+            mesh.positions.push((point - center).as_vec3().to_array());
+            mesh.normals.push(normal.as_vec3().to_array());
+            mesh.elevations.push(elevation(point));
+        }
+    }
+
+    // Winding that faces outwards for this face's axes.
+    let outward = {
+        let (a, b, c) = (at(0, 0), at(1, 0), at(0, 1));
+        (b - a).cross(c - a).dot(a) > 0.0
+    };
+    let triangle = |indices: &mut Vec<u32>, a: usize, b: usize, c: usize| {
+        let (b, c) = if outward { (b, c) } else { (c, b) };
+        indices.extend([a as u32, b as u32, c as u32]);
+    };
+    for j in 0..n - 1 {
+        for i in 0..n - 1 {
+            let k = j * n + i;
+            triangle(&mut mesh.indices, k, k + 1, k + n);
+            triangle(&mut mesh.indices, k + 1, k + n + 1, k + n);
+        }
+    }
+
+    // Border loop in order around the chunk, then the skirt below it.
+    let border: Vec<usize> = (0..n - 1)
+        .chain((0..n - 1).map(|j| j * n + n - 1))
+        .chain((0..n - 1).map(|i| (n - 1) * n + n - 1 - i))
+        .chain((0..n - 1).map(|j| (n - 1 - j) * n))
+        .collect();
+    let skirt = id.width(shape.radius) * SKIRT_DEPTH;
+    let first_skirt = mesh.positions.len();
+    for &k in &border {
+        let point = at(k % n, k / n);
+        let lowered = point - point.normalize() * skirt;
+        mesh.positions.push((lowered - center).as_vec3().to_array());
+        mesh.normals.push(mesh.normals[k]);
+        mesh.elevations.push(mesh.elevations[k]);
+    }
+    for (index, &top) in border.iter().enumerate() {
+        let next = border[(index + 1) % border.len()];
+        let (bottom, next_bottom) = (
+            first_skirt + index,
+            first_skirt + (index + 1) % border.len(),
+        );
+        // Terrain materials are double-sided, so skirt winding is free.
+        triangle(&mut mesh.indices, top, bottom, next);
+        triangle(&mut mesh.indices, next, bottom, next_bottom);
+    }
+    mesh
+}
+
+/// Chunks to draw for a camera at `camera` (planet-local, m): the roots,
+/// split nearest-first while the camera is within
+/// `TerrainConfig::split_distance` chunk widths and the vertex budget
+/// allows.
+pub fn select_chunks(camera: DVec3, radius: f64, config: &TerrainConfig) -> Vec<ChunkId> {
+    let per_chunk = vertices_per_chunk(config.resolution);
+    let budget = config.vertex_budget as usize;
+    let mut leaves: Vec<ChunkId> = ChunkId::roots().to_vec();
+
+    // Splitting swaps one chunk for four.
+    while (leaves.len() + 3) * per_chunk <= budget {
+        let candidate = leaves
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| id.level < config.max_depth)
+            .map(|(index, id)| {
+                let width = id.width(radius);
+                let distance = camera.distance(id.direction(0.5, 0.5) * radius);
+                (index, distance / width)
+            })
+            .filter(|&(_, widths)| widths < config.split_distance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((index, _)) = candidate else {
+            break;
+        };
+        let parent = leaves.swap_remove(index);
+        leaves.extend(parent.children());
+    }
+    leaves.sort_unstable();
+    leaves
+}
+
+/// Number of chunks at each level in `chunks`, shallowest first.
+pub fn chunks_per_level(chunks: &[ChunkId]) -> Vec<usize> {
+    let depth = chunks.iter().map(|id| id.level).max().map_or(0, |l| l + 1);
+    let mut counts = vec![0; depth as usize];
+    for id in chunks {
+        counts[id.level as usize] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(class: PlanetClass) -> TerrainShape {
+        TerrainShape::new(class, 5, 6.4e6, &TerrainConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn every_level_meets_its_vertex_budget() {
+        let config = TerrainConfig::default();
+        let shape = shape(PlanetClass::Rocky);
+        let mut id = ChunkId::roots()[2];
+        for level in 0..=config.max_depth {
+            assert_eq!(id.level, level);
+            let mesh = build_chunk(id, config.resolution, &shape);
+            assert_eq!(mesh.positions.len(), vertices_per_chunk(config.resolution));
+            assert_eq!(mesh.normals.len(), mesh.positions.len());
+            assert_eq!(mesh.elevations.len(), mesh.positions.len());
+            assert_eq!(mesh.indices.len(), indices_per_chunk(config.resolution));
+            assert!(
+                mesh.indices
+                    .iter()
+                    .all(|&i| (i as usize) < mesh.positions.len())
+            );
+            id = id.children()[3];
+        }
+    }
+
+    #[test]
+    fn faces_cover_the_sphere_without_gaps() {
+        // Shared edges of neighbouring roots map to the same directions.
+        let roots = ChunkId::roots();
+        let corners: Vec<DVec3> = roots
+            .iter()
+            .flat_map(|id| {
+                [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)].map(|(s, t)| id.direction(s, t))
+            })
+            .collect();
+        for corner in &corners {
+            assert!((corner.length() - 1.0).abs() < 1e-12);
+            let shared = corners
+                .iter()
+                .filter(|c| c.distance(*corner) < 1e-12)
+                .count();
+            assert_eq!(shared, 3);
+        }
+    }
+
+    #[test]
+    fn children_tile_their_parent() {
+        let parent = ChunkId {
+            face: 4,
+            level: 3,
+            x: 5,
+            y: 2,
+        };
+        let [a, b, c, d] = parent.children();
+        assert_eq!(a.direction(0.0, 0.0), parent.direction(0.0, 0.0));
+        assert!(b.direction(1.0, 0.0).distance(parent.direction(1.0, 0.0)) < 1e-12);
+        assert!(c.direction(0.0, 1.0).distance(parent.direction(0.0, 1.0)) < 1e-12);
+        assert!(d.direction(1.0, 1.0).distance(parent.direction(1.0, 1.0)) < 1e-12);
+        assert!(a.direction(1.0, 1.0).distance(parent.direction(0.5, 0.5)) < 1e-12);
+    }
+
+    #[test]
+    fn displacement_stays_within_amplitude() {
+        for class in [PlanetClass::Rocky, PlanetClass::Ocean, PlanetClass::Ice] {
+            let shape = shape(class);
+            let mesh = build_chunk(ChunkId::roots()[0], 33, &shape);
+            for &elevation in &mesh.elevations[..33 * 33] {
+                assert!((-1.0..=1.0).contains(&elevation));
+                if class == PlanetClass::Ocean {
+                    assert!(elevation >= -1e-6);
+                }
+            }
+        }
+        let config = TerrainConfig::default();
+        assert!(TerrainShape::new(PlanetClass::GasGiant, 5, 7.0e7, &config).is_none());
+    }
+
+    #[test]
+    fn lod_refines_towards_the_camera_within_budget() {
+        let config = TerrainConfig::default();
+        let radius = 6.4e6;
+        let per_chunk = vertices_per_chunk(config.resolution);
+
+        let far = select_chunks(DVec3::X * radius * 100.0, radius, &config);
+        assert_eq!(far, ChunkId::roots().to_vec());
+
+        let near = select_chunks(DVec3::X * (radius + 100.0), radius, &config);
+        assert!(near.len() * per_chunk <= config.vertex_budget as usize);
+        let levels = chunks_per_level(&near);
+        assert_eq!(levels.len(), config.max_depth as usize + 1);
+        assert_eq!(levels.iter().sum::<usize>(), near.len());
+        let deepest = near.iter().max_by_key(|id| id.level).unwrap();
+        assert_eq!(deepest.face, 0);
+        assert!(deepest.direction(0.5, 0.5).dot(DVec3::X) > 0.999);
+
+        let tight = TerrainConfig {
+            vertex_budget: (12 * per_chunk) as u32,
+            ..config
+        };
+        let limited = select_chunks(DVec3::X * (radius + 100.0), radius, &tight);
+        assert_eq!(limited.len(), 12);
+    }
+}
@@ -0,0 +1,365 @@
+//! # Orbit Camera
+//!
+//! Orbit camera of the orbital view: the eye circles a centre point at a
+//! yaw, pitch and distance around the scene's +Z axis. The centre follows
+//! the focused body plus any pan offset, and focus changes ease through a
+//! fly-to transition. Everything here is plain maths driven by the camera
+//! systems, so it runs without a window.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::config::gameplay::{CameraBindings, CameraConfig};
+use crate::config::input::parse_key_code;
+
+/// Closest the pitch may get to the poles, so the up vector stays defined
+/// (radians).
+const POLE_MARGIN: f64 = 1e-3;
+
+/// Camera clearance above a focused body's surface, in body radii.
+const SURFACE_CLEARANCE: f64 = 1.05;
+
+/// Runtime state of the orbit camera.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct OrbitCamera {
+    /// Body the camera orbits; `None` orbits a fixed point.
+    pub focus: Option<Entity>,
+    /// Position of the focus, or the fixed point orbited without one.
+    pub anchor: DVec3,
+    /// Offset of the orbited point from the anchor.
+    pub pan: DVec3,
+    /// Angle around +Z from +X (radians).
+    pub yaw: f64,
+    /// Angle above the XY plane (radians).
+    pub pitch: f64,
+    /// Distance from the eye to the orbited point (m).
+    pub distance: f64,
+    /// Zoom bounds (m).
+    pub min_distance: f64,
+    pub max_distance: f64,
+    /// Radius of the focused body (m); zero without a focus.
+    pub surface: f64,
+    /// Closest approach allowed by the focused body's surface as drawn (m).
+    pub floor: f64,
+    /// Factor the focused body's radius is drawn at.
+    inflation: f64,
+    flight: Option<Flight>,
+}
+
+/// Fly-to transition in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Flight {
+    /// Orbited point and distance when the transition started.
+    from: DVec3,
+    from_distance: f64,
+    to_distance: f64,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl OrbitCamera {
+    /// Camera at `eye` orbiting `target`, with zoom bounds from `config`.
+    pub fn looking_at(eye: DVec3, target: DVec3, config: &CameraConfig) -> Self {
+        let offset = eye - target;
+        let distance = offset.length();
+        let (yaw, pitch) = if distance > 0.0 {
+            (
+                offset.y.atan2(offset.x).rem_euclid(TAU),
+                (offset.z / distance).asin(),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        let mut camera = Self {
+            focus: None,
+            anchor: target,
+            pan: DVec3::ZERO,
+            yaw,
+            pitch: 0.0,
+            distance: 0.0,
+            min_distance: config.min_distance,
+            max_distance: config.max_distance,
+            surface: 0.0,
+            floor: 0.0,
+            inflation: 1.0,
+            flight: None,
+        };
+        camera.rotate(0.0, pitch);
+        camera.distance = camera.clamp_distance(distance);
+        camera
+    }
+
+    /// Point the eye orbits.
+    pub fn center(&self) -> DVec3 {
+        self.anchor + self.pan
+    }
+
+    /// Unit vector from the orbited point to the eye.
+    pub fn direction(&self) -> DVec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        DVec3::new(cos_pitch * cos_yaw, cos_pitch * sin_yaw, sin_pitch)
+    }
+
+    pub fn eye(&self) -> DVec3 {
+        self.center() + self.direction() * self.distance
+    }
+
+    /// Camera transform at the render-space `eye`, looking at the orbited
+    /// point with +Z up.
+    pub fn transform(&self, eye: Vec3) -> Transform {
+        Transform::from_translation(eye).looking_to((-self.direction()).as_vec3(), Vec3::Z)
+    }
+
+    /// Whether a fly-to transition is running.
+    pub fn is_flying(&self) -> bool {
+        self.flight.is_some()
+    }
+
+    /// Turns the eye around the orbited point, keeping the pitch short of
+    /// the poles.
+    pub fn rotate(&mut self, yaw: f64, pitch: f64) {
+        let limit = FRAC_PI_2 - POLE_MARGIN;
+        self.yaw = (self.yaw + yaw).rem_euclid(TAU);
+        self.pitch = (self.pitch + pitch).clamp(-limit, limit);
+    }
+
+    /// Zooms by `steps` on a logarithmic scale: every step multiplies the
+    /// distance by `exp(-step)`, so positive steps move in.
+    /// During a fly-to the destination distance is zoomed instead.
+    pub fn zoom(&mut self, steps: f64, step: f64) {
+        let factor = (-steps * step).exp();
+        match self.flight {
+            Some(flight) => {
+                let to_distance = self.clamp_distance(flight.to_distance * factor);
+                if let Some(flight) = &mut self.flight {
+                    flight.to_distance = to_distance;
+                }
+            }
+            None => self.distance = self.clamp_distance(self.distance * factor),
+        }
+    }
+
+    /// Moves the orbited point across the view by fractions of the
+    /// distance: `right` along the screen's right, `up` along its up.
+    pub fn pan(&mut self, right: f64, up: f64) {
+        let back = self.direction();
+        let right_axis = DVec3::Z.cross(back).normalize_or(DVec3::X);
+        let up_axis = back.cross(right_axis);
+        self.pan += (right_axis * right + up_axis * up) * self.distance;
+    }
+
+    /// Starts a transition to orbit `focus` at `distance`, or to stay
+    /// around the current point without a focus. Panning is reset.
+    /// `surface` is the focus radius the camera must stay outside, before
+    /// any inflation.
+    pub fn fly_to(&mut self, focus: Option<Entity>, distance: f64, surface: f64, duration: f64) {
+        let from = self.center();
+        self.focus = focus;
+        self.pan = DVec3::ZERO;
+        self.surface = surface;
+        self.floor = surface * self.inflation * SURFACE_CLEARANCE;
+        if focus.is_none() {
+            self.anchor = from;
+        }
+        let to_distance = self.clamp_distance(distance);
+        if duration > 0.0 {
+            self.flight = Some(Flight {
+                from,
+                from_distance: self.distance,
+                to_distance,
+                elapsed: 0.0,
+                duration,
+            });
+        } else {
+            self.flight = None;
+            self.distance = to_distance;
+        }
+    }
+
+    /// Keeps the eye outside the focused body drawn at `inflation` times its
+    /// radius, moving it out if the body has grown past it.
+    pub fn set_inflation(&mut self, inflation: f64) {
+        if inflation == self.inflation {
+            return;
+        }
+        self.inflation = inflation;
+        self.floor = self.surface * inflation * SURFACE_CLEARANCE;
+        match self.flight {
+            Some(flight) => {
+                let to_distance = self.clamp_distance(flight.to_distance);
+                if let Some(flight) = &mut self.flight {
+                    flight.to_distance = to_distance;
+                }
+            }
+            None => self.distance = self.clamp_distance(self.distance),
+        }
+    }
+
+    /// Moves the anchor to the focus at `focus_position` and advances any
+    /// transition by `dt` seconds. The distance eases in `ln` space so the
+    /// zoom rate looks even from kilometres to astronomical units.
+    pub fn update(&mut self, focus_position: Option<DVec3>, dt: f64) {
+        if let Some(position) = focus_position {
+            self.anchor = position;
+        }
+        let Some(flight) = &mut self.flight else {
+            return;
+        };
+        flight.elapsed += dt;
+        let t = (flight.elapsed / flight.duration).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        let (from, to) = (flight.from_distance.ln(), flight.to_distance.ln());
+        self.distance = (from + (to - from) * eased).exp();
+        // Panning is reset, so the orbited point travels from the start to
+        // the moving anchor.
+        self.pan = (flight.from - self.anchor) * (1.0 - eased);
+        if t >= 1.0 {
+            self.flight = None;
+            self.pan = DVec3::ZERO;
+        }
+    }
+
+    fn clamp_distance(&self, distance: f64) -> f64 {
+        let min = self.min_distance.max(self.floor).min(self.max_distance);
+        distance.clamp(min, self.max_distance)
+    }
+}
+
+/// `CameraBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CameraHotkeys {
+    pub cycle_focus: Option<KeyCode>,
+    pub clear_focus: Option<KeyCode>,
+}
+
+impl From<&CameraBindings> for CameraHotkeys {
+    fn from(bindings: &CameraBindings) -> Self {
+        Self {
+            cycle_focus: parse_key_code(&bindings.cycle_focus),
+            clear_focus: parse_key_code(&bindings.clear_focus),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> OrbitCamera {
+        OrbitCamera::looking_at(
+            DVec3::new(0.0, -3.0e8, 4.0e8),
+            DVec3::ZERO,
+            &CameraConfig::default(),
+        )
+    }
+
+    #[test]
+    fn looking_at_round_trips_the_eye() {
+        let camera = camera();
+        assert!((camera.distance - 5.0e8).abs() < 1e-3);
+        assert!(camera.eye().distance(DVec3::new(0.0, -3.0e8, 4.0e8)) < 1e-3);
+        let forward = camera
+            .transform(camera.eye().as_vec3())
+            .forward()
+            .as_dvec3();
+        assert!(forward.dot(-camera.eye().normalize()) > 0.999_999);
+    }
+
+    #[test]
+    fn pitch_stops_short_of_the_poles() {
+        let mut camera = camera();
+        camera.rotate(0.0, 10.0);
+        assert!(camera.pitch < FRAC_PI_2);
+        assert!(
+            camera
+                .transform(camera.eye().as_vec3())
+                .translation
+                .is_finite()
+        );
+        assert!(
+            camera
+                .transform(camera.eye().as_vec3())
+                .rotation
+                .is_finite()
+        );
+        let yaw = camera.yaw;
+        camera.rotate(TAU * 3.0 + 1.0, -20.0);
+        assert!((camera.yaw - (yaw + 1.0).rem_euclid(TAU)).abs() < 1e-9);
+        assert!(camera.pitch > -FRAC_PI_2);
+    }
+
+    #[test]
+    fn zoom_is_logarithmic_and_bounded() {
+        let config = CameraConfig::default();
+        let mut camera = camera();
+        let start = camera.distance;
+        camera.zoom(1.0, 0.5);
+        camera.zoom(1.0, 0.5);
+        assert!((camera.distance / start - (-1.0f64).exp()).abs() < 1e-12);
+        camera.zoom(-2.0, 0.5);
+        assert!((camera.distance / start - 1.0).abs() < 1e-12);
+
+        camera.zoom(1000.0, 0.5);
+        assert_eq!(camera.distance, config.min_distance);
+        camera.zoom(-1000.0, 0.5);
+        assert_eq!(camera.distance, config.max_distance);
+    }
+
+    #[test]
+    fn pan_moves_across_the_view() {
+        let mut camera = camera();
+        let view = camera.transform(camera.eye().as_vec3());
+        let (right, up) = (view.right().as_dvec3(), view.up().as_dvec3());
+        camera.pan(0.1, -0.2);
+        let expected = (right * 0.1 - up * 0.2) * camera.distance;
+        assert!(camera.pan.distance(expected) < camera.distance * 1e-6);
+        assert!(camera.eye().distance(camera.center()) - camera.distance < 1e-3);
+    }
+
+    #[test]
+    fn fly_to_eases_onto_a_moving_focus() {
+        let mut camera = camera();
+        camera.pan(0.3, 0.0);
+        let start = camera.center();
+        let focus = Entity::from_raw_u32(7).unwrap();
+        camera.fly_to(Some(focus), 2.0e7, 6.4e6, 2.0);
+        assert!(camera.is_flying());
+        camera.update(Some(DVec3::new(1.0e9, 0.0, 0.0)), 0.0);
+        assert!(camera.center().distance(start) < 1e-3);
+
+        camera.update(Some(DVec3::new(1.0e9, 0.0, 0.0)), 1.0);
+        let halfway = (5.0e8f64.ln() + 2.0e7f64.ln()) / 2.0;
+        assert!((camera.distance.ln() - halfway).abs() < 1e-9);
+
+        let target = DVec3::new(1.1e9, 2.0e8, 0.0);
+        camera.update(Some(target), 1.5);
+        assert!(!camera.is_flying());
+        assert_eq!(camera.center(), target);
+        assert!((camera.distance - 2.0e7).abs() < 1e-3);
+
+        // The focused body's surface bounds the zoom.
+        camera.zoom(1000.0, 0.5);
+        assert!((camera.distance - 6.4e6 * SURFACE_CLEARANCE).abs() < 1e-3);
+    }
+
+    #[test]
+    fn inflated_bodies_push_the_camera_out() {
+        let mut camera = camera();
+        let focus = Entity::from_raw_u32(7).unwrap();
+        camera.fly_to(Some(focus), 2.0e7, 6.4e6, 0.0);
+        camera.set_inflation(10.0);
+        assert!((camera.distance - 6.4e7 * SURFACE_CLEARANCE).abs() < 1e-3);
+
+        // Deflating lowers the floor again without moving the camera.
+        camera.set_inflation(2.0);
+        assert!((camera.distance - 6.4e7 * SURFACE_CLEARANCE).abs() < 1e-3);
+        camera.zoom(1000.0, 0.5);
+        assert!((camera.distance - 1.28e7 * SURFACE_CLEARANCE).abs() < 1e-3);
+        // Later flights keep to the inflated surface.
+        camera.fly_to(Some(focus), 1.0e7, 6.4e6, 0.0);
+        assert!((camera.distance - 1.28e7 * SURFACE_CLEARANCE).abs() < 1e-3);
+    }
+}
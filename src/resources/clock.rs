@@ -0,0 +1,253 @@
+//! # Simulation Clock
+//!
+//! The simulated calendar that physics, orbits and rotation advance against.
+
+use bevy::prelude::*;
+
+use crate::astro::JulianDate;
+use crate::config::gameplay::ClockBindings;
+use crate::config::input::parse_key_code;
+use crate::constants::{MAX_TIME_SCALE, MIN_TIME_SCALE};
+
+/// Simulated time, decoupled from wall-clock `Time`.
+///
+/// `epoch` counts TT seconds since J2000.0 (2000-01-01 12:00:00 TT). Every
+/// fixed step the clock advances by the real step length times `time_scale`,
+/// backwards when reversed and not at all while paused. Pausing here is
+/// independent of the pause menu, which stops the fixed schedule entirely.
+///
+/// While synced, the clock follows the system clock instead; any manual
+/// control ends the sync. Engaging the sync jumps to the system time rather
+/// than stepping across the gap.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SimClock {
+    /// Current simulated time in TT seconds since J2000.0.
+    pub epoch: f64,
+    time_scale: f64,
+    synced: bool,
+    /// Whether the next `sync_to` is the jump that engages the sync.
+    sync_pending: bool,
+    paused: bool,
+    reversed: bool,
+    pending_steps: u32,
+    delta: f64,
+}
+
+impl Default for SimClock {
+    fn default() -> Self {
+        Self::new(0.0, MIN_TIME_SCALE)
+    }
+}
+
+impl SimClock {
+    /// Creates a running, forward clock at `epoch` seconds since J2000.0.
+    pub fn new(epoch: f64, time_scale: f64) -> Self {
+        Self {
+            epoch,
+            time_scale: time_scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE),
+            synced: false,
+            sync_pending: false,
+            paused: false,
+            reversed: false,
+            pending_steps: 0,
+            delta: 0.0,
+        }
+    }
+
+    /// Current time as a TT Julian date.
+    pub fn julian_date(&self) -> JulianDate {
+        JulianDate::from_j2000_seconds(self.epoch)
+    }
+
+    /// Current multiplier of simulated over real time.
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Sets the multiplier, clamped to `MIN_TIME_SCALE..=MAX_TIME_SCALE`.
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        if time_scale.is_nan() {
+            return;
+        }
+        self.synced = false;
+        self.time_scale = time_scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+    }
+
+    /// Signed simulated seconds the last tick advanced by.
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    /// Follows the system clock in real time. Clears pause, reverse and scale.
+    pub fn set_synced(&mut self, synced: bool) {
+        self.sync_pending = synced && !self.synced;
+        self.synced = synced;
+        if synced {
+            self.paused = false;
+            self.reversed = false;
+            self.pending_steps = 0;
+            self.time_scale = MIN_TIME_SCALE;
+        }
+    }
+
+    /// Moves to `epoch` while synced, recording the step as `delta`. The
+    /// first call after engaging the sync jumps with a zero `delta`.
+    pub fn sync_to(&mut self, epoch: f64) {
+        self.delta = if self.sync_pending {
+            0.0
+        } else {
+            epoch - self.epoch
+        };
+        self.sync_pending = false;
+        self.epoch = epoch;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.synced = false;
+        self.paused = paused;
+        self.pending_steps = 0;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.set_paused(!self.paused);
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.synced = false;
+        self.reversed = reversed;
+    }
+
+    pub fn toggle_reverse(&mut self) {
+        self.set_reversed(!self.reversed);
+    }
+
+    /// Pauses the clock and queues a single tick to run on the next step.
+    pub fn step_once(&mut self) {
+        self.synced = false;
+        self.paused = true;
+        self.pending_steps += 1;
+    }
+
+    /// Moves the clock to `epoch` TT seconds since J2000.0.
+    ///
+    /// On-rails bodies are re-evaluated at the new epoch on the next step;
+    /// integrated bodies keep their current state.
+    pub fn jump_to(&mut self, epoch: f64) {
+        self.synced = false;
+        self.epoch = epoch;
+    }
+
+    /// Advances by one real step of `real_dt` seconds and returns the signed
+    /// simulated delta.
+    pub fn tick(&mut self, real_dt: f64) -> f64 {
+        let running = if self.paused {
+            if self.pending_steps == 0 {
+                false
+            } else {
+                self.pending_steps -= 1;
+                true
+            }
+        } else {
+            true
+        };
+
+        self.delta = if running {
+            let sign = if self.reversed { -1.0 } else { 1.0 };
+            sign * real_dt * self.time_scale
+        } else {
+            0.0
+        };
+        self.epoch += self.delta;
+        self.delta
+    }
+}
+
+/// `ClockBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ClockHotkeys {
+    pub toggle_pause: Option<KeyCode>,
+    pub reverse: Option<KeyCode>,
+    pub step: Option<KeyCode>,
+    pub faster: Option<KeyCode>,
+    pub slower: Option<KeyCode>,
+    pub jump: Option<KeyCode>,
+    pub sync: Option<KeyCode>,
+}
+
+impl From<&ClockBindings> for ClockHotkeys {
+    fn from(bindings: &ClockBindings) -> Self {
+        Self {
+            toggle_pause: parse_key_code(&bindings.toggle_pause),
+            reverse: parse_key_code(&bindings.reverse),
+            step: parse_key_code(&bindings.step),
+            faster: parse_key_code(&bindings.faster),
+            slower: parse_key_code(&bindings.slower),
+            jump: parse_key_code(&bindings.jump),
+            sync: parse_key_code(&bindings.sync),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_scales_reverses_and_pauses() {
+        let mut clock = SimClock::new(0.0, 100.0);
+        assert_eq!(clock.tick(0.5), 50.0);
+
+        clock.toggle_reverse();
+        assert_eq!(clock.tick(0.5), -50.0);
+        assert_eq!(clock.epoch, 0.0);
+
+        clock.toggle_pause();
+        assert_eq!(clock.tick(0.5), 0.0);
+        assert_eq!(clock.delta(), 0.0);
+    }
+
+    #[test]
+    fn single_step_runs_exactly_one_tick() {
+        let mut clock = SimClock::new(0.0, 10.0);
+        clock.step_once();
+        assert!(clock.is_paused());
+        assert_eq!(clock.tick(1.0), 10.0);
+        assert_eq!(clock.tick(1.0), 0.0);
+        assert_eq!(clock.epoch, 10.0);
+    }
+
+    #[test]
+    fn time_scale_is_clamped() {
+        let mut clock = SimClock::default();
+        clock.set_time_scale(1.0e9);
+        assert_eq!(clock.time_scale(), MAX_TIME_SCALE);
+        clock.set_time_scale(0.01);
+        assert_eq!(clock.time_scale(), MIN_TIME_SCALE);
+    }
+
+    #[test]
+    fn manual_control_ends_sync() {
+        let mut clock = SimClock::new(0.0, 1000.0);
+        clock.set_synced(true);
+        assert_eq!(clock.time_scale(), MIN_TIME_SCALE);
+        // Engaging the sync jumps; later syncs step.
+        clock.sync_to(1.0e9);
+        assert_eq!(clock.delta(), 0.0);
+        clock.sync_to(1.0e9 + 5.0);
+        assert_eq!(clock.delta(), 5.0);
+        clock.toggle_reverse();
+        assert!(!clock.is_synced());
+    }
+}
@@ -0,0 +1,167 @@
+//! # HUD
+//!
+//! Heads-up display state: whether it is shown, the selected body and the
+//! figures the info panel reports for it.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::components::OrbitalElements;
+use crate::config::gameplay::{HudBindings, HudConfig};
+use crate::config::input::parse_key_code;
+
+/// Whether the HUD is shown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hud {
+    pub visible: bool,
+}
+
+impl From<&HudConfig> for Hud {
+    fn from(config: &HudConfig) -> Self {
+        Self {
+            visible: config.visible,
+        }
+    }
+}
+
+/// Body picked by the user, if any.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Selection(pub Option<Entity>);
+
+/// `HudBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HudHotkeys {
+    pub toggle_hud: Option<KeyCode>,
+}
+
+impl From<&HudBindings> for HudHotkeys {
+    fn from(bindings: &HudBindings) -> Self {
+        Self {
+            toggle_hud: parse_key_code(&bindings.toggle_hud),
+        }
+    }
+}
+
+/// Position, velocity and mass of a body in the scene frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kinematics {
+    pub position: DVec3,
+    pub velocity: DVec3,
+    pub mass: f64,
+}
+
+/// Figures shown in the info panel. Motion is relative to the parent when
+/// there is one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyInfo {
+    /// Mass (kg).
+    pub mass: f64,
+    /// Radius (m).
+    pub radius: f64,
+    /// Distance from the camera to the body's centre (m).
+    pub camera_distance: f64,
+    /// Distance between the centres of the body and its parent (m).
+    pub parent_distance: Option<f64>,
+    /// Speed (m/s).
+    pub speed: f64,
+    /// Period of the osculating orbit around the parent (s); `None` when
+    /// unbound.
+    pub period: Option<f64>,
+    pub eccentricity: Option<f64>,
+}
+
+impl BodyInfo {
+    /// Measures `body` against `parent` with the osculating two-body orbit
+    /// under gravitational constant `g`.
+    pub fn measure(
+        body: Kinematics,
+        radius: f64,
+        parent: Option<Kinematics>,
+        camera: DVec3,
+        g: f64,
+    ) -> Self {
+        let camera_distance = body.position.distance(camera);
+        let Some(parent) = parent else {
+            return Self {
+                mass: body.mass,
+                radius,
+                camera_distance,
+                parent_distance: None,
+                speed: body.velocity.length(),
+                period: None,
+                eccentricity: None,
+            };
+        };
+
+        let position = body.position - parent.position;
+        let velocity = body.velocity - parent.velocity;
+        let mu = g * (body.mass + parent.mass);
+        let elements = OrbitalElements::from_state(position, velocity, mu, 0.0);
+        Self {
+            mass: body.mass,
+            radius,
+            camera_distance,
+            parent_distance: Some(position.length()),
+            speed: velocity.length(),
+            period: elements.period(mu),
+            eccentricity: Some(elements.eccentricity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::TAU;
+
+    const G: f64 = 6.67430e-11;
+
+    #[test]
+    fn circular_orbit_reports_its_period() {
+        let earth = Kinematics {
+            position: DVec3::new(1.0e11, 0.0, 0.0),
+            velocity: DVec3::new(0.0, 3.0e4, 0.0),
+            mass: 5.972e24,
+        };
+        let (radius, moon_mass) = (3.844e8, 7.342e22);
+        let mu = G * (earth.mass + moon_mass);
+        let speed = (mu / radius).sqrt();
+        let moon = Kinematics {
+            position: earth.position + DVec3::new(0.0, radius, 0.0),
+            velocity: earth.velocity + DVec3::new(-speed, 0.0, 0.0),
+            mass: moon_mass,
+        };
+
+        let info = BodyInfo::measure(moon, 1.737e6, Some(earth), earth.position, G);
+        assert!((info.camera_distance - radius).abs() < 1e-3);
+        assert!((info.parent_distance.unwrap() - radius).abs() < 1e-3);
+        assert!((info.speed - speed).abs() < 1e-9);
+        assert!(info.eccentricity.unwrap() < 1e-9);
+        let period = TAU * (radius.powi(3) / mu).sqrt();
+        assert!((info.period.unwrap() / period - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unbound_and_parentless_bodies_have_no_period() {
+        let sun = Kinematics {
+            position: DVec3::ZERO,
+            velocity: DVec3::ZERO,
+            mass: 1.989e30,
+        };
+        let comet = Kinematics {
+            position: DVec3::new(1.5e11, 0.0, 0.0),
+            velocity: DVec3::new(0.0, 6.0e4, 0.0),
+            mass: 1.0e13,
+        };
+        let info = BodyInfo::measure(comet, 5.0e3, Some(sun), DVec3::ZERO, G);
+        assert!(info.eccentricity.unwrap() > 1.0);
+        assert_eq!(info.period, None);
+
+        let info = BodyInfo::measure(sun, 6.96e8, None, DVec3::new(0.0, 0.0, 1.0e9), G);
+        assert_eq!(
+            (info.parent_distance, info.period, info.eccentricity),
+            (None, None, None)
+        );
+        assert_eq!(info.camera_distance, 1.0e9);
+    }
+}
@@ -3,7 +3,37 @@
 //! ECS resource definitions for the game layer.
 //! Resources provide global state accessible to all systems.
 
-// Future game resources will be added here as the game evolves.
-// Examples:
-//   - Score { value: u32, high_score: u32 }
-//   - LevelData { current_level: u32 }
+pub mod camera;
+pub mod clock;
+pub mod hud;
+pub mod moon;
+pub mod observer;
+pub mod orbit_display;
+pub mod origin;
+pub mod scale;
+pub mod sky;
+pub mod spacecraft;
+
+pub use camera::{CameraHotkeys, OrbitCamera};
+pub use clock::{ClockHotkeys, SimClock};
+pub use hud::{BodyInfo, Hud, HudHotkeys, Kinematics, Selection};
+pub use moon::MoonPhase;
+pub use observer::{ObserverGizmos, ObserverHotkeys, ObserverView, OrbitalCameraPose};
+pub use orbit_display::{
+    NodeMarker, OrbitGizmos, OrbitHotkeys, OrbitOverlay, PredictedPaths, TrailClock,
+};
+pub use origin::FloatingOrigin;
+pub use scale::{ViewScale, ViewScaleHotkeys};
+pub use sky::{ConstellationFigures, ConstellationGizmos, SkyHotkeys, SkyOverlay};
+pub use spacecraft::{ActiveSpacecraft, SpacecraftHotkeys};
+
+use bevy::prelude::*;
+
+use crate::scenario::Scenario;
+
+/// The scenario the game world is built from.
+///
+/// Loaded when entering `AppState::Loading` and removed when leaving the game,
+/// so edits to the file are picked up on the next session.
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveScenario(pub Scenario);
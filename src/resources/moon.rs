@@ -0,0 +1,79 @@
+//! # Moon Phase
+//!
+//! Phase, illumination and libration of the Moon at the simulation date,
+//! for the HUD.
+
+use bevy::prelude::*;
+use fluent_bundle::FluentArgs;
+
+use crate::astro::{JulianDate, Libration, Phase, PhaseName, lunar};
+use localization::Localization;
+
+/// The Moon as seen from the Earth, refreshed every fixed step.
+///
+/// Computed from the lunar theory alone, so it is available in every
+/// scenario whether or not it has a Moon body.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct MoonPhase {
+    pub phase: Phase,
+    pub libration: Libration,
+}
+
+impl MoonPhase {
+    /// Phase and libration at `date` (TT).
+    pub fn at(date: JulianDate) -> Self {
+        Self {
+            phase: lunar::phase(date),
+            libration: lunar::libration(date),
+        }
+    }
+
+    pub fn name(&self) -> PhaseName {
+        self.phase.name()
+    }
+
+    /// Illuminated fraction of the disc in `[0, 1]`.
+    pub fn illumination(&self) -> f64 {
+        self.phase.illuminated_fraction
+    }
+
+    /// Localized phase name.
+    pub fn localized_name(&self, localization: &Localization) -> String {
+        localization.t(self.name().key())
+    }
+
+    /// Localized "phase, N% illuminated" line.
+    pub fn label(&self, localization: &Localization) -> String {
+        let mut args = FluentArgs::new();
+        args.set("phase", self.localized_name(localization));
+        args.set("illumination", (self.illumination() * 100.0).round());
+        localization.t_with_args("moon-phase-label", Some(&args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phase_names_are_localized() {
+        for locale in ["en-US", "ru-RU"] {
+            let path = format!(
+                "{}/assets/locales/{locale}/text/moon.ftl",
+                env!("CARGO_MANIFEST_DIR")
+            );
+            let ftl = std::fs::read_to_string(&path).expect("moon.ftl should exist");
+            let keys = PhaseName::ALL
+                .iter()
+                .map(|name| name.key())
+                .chain(["moon-phase-label"]);
+            for key in keys {
+                assert!(
+                    ftl.lines()
+                        .any(|line| line.starts_with(&format!("{key} ="))),
+                    "{locale} is missing {key}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! # Observer View
+//!
+//! Where the surface observer stands and looks.
+
+use std::f64::consts::{PI, TAU};
+
+use bevy::math::{DMat3, DVec3};
+use bevy::prelude::*;
+
+use crate::astro::{Horizontal, Observer};
+use crate::config::gameplay::{ObserverBindings, ObserverConfig};
+use crate::config::input::parse_key_code;
+
+/// Highest and lowest altitude the look keys can reach (radians).
+const MAX_LOOK_ALTITUDE: f64 = 89.0 * PI / 180.0;
+
+/// Runtime state of the observer view.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ObserverView {
+    /// Scenario name of the body stood on.
+    pub body: String,
+    pub observer: Observer,
+    /// Direction the camera looks.
+    pub look: Horizontal,
+    /// Whether the alt-az grid and cardinal points are drawn.
+    pub grid: bool,
+    /// East, north and zenith axes in the scene frame, as columns.
+    pub basis: DMat3,
+    /// Eye position in the scene.
+    pub eye: DVec3,
+}
+
+impl From<&ObserverConfig> for ObserverView {
+    fn from(config: &ObserverConfig) -> Self {
+        Self {
+            body: config.body.clone(),
+            observer: Observer::new(config.latitude.to_radians(), config.longitude.to_radians()),
+            look: Horizontal {
+                azimuth: PI,
+                altitude: 20f64.to_radians(),
+            },
+            grid: config.show_grid,
+            basis: DMat3::IDENTITY,
+            eye: DVec3::ZERO,
+        }
+    }
+}
+
+impl ObserverView {
+    /// Turns the view, keeping the altitude just short of the zenith and nadir.
+    pub fn turn(&mut self, azimuth: f64, altitude: f64) {
+        self.look.azimuth = (self.look.azimuth + azimuth).rem_euclid(TAU);
+        self.look.altitude =
+            (self.look.altitude + altitude).clamp(-MAX_LOOK_ALTITUDE, MAX_LOOK_ALTITUDE);
+    }
+
+    /// Converts a horizontal (east, north, zenith) vector into the scene frame.
+    pub fn to_scene(&self, horizontal: DVec3) -> DVec3 {
+        self.basis * horizontal
+    }
+
+    /// Scene-frame direction of the view.
+    pub fn look_direction(&self) -> DVec3 {
+        self.to_scene(self.look.to_vector())
+    }
+
+    /// Scene-frame zenith.
+    pub fn zenith(&self) -> DVec3 {
+        self.basis.z_axis
+    }
+}
+
+/// Camera pose of the orbital view, restored when leaving the observer view.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct OrbitalCameraPose(pub Transform);
+
+/// `ObserverBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ObserverHotkeys {
+    pub toggle_view: Option<KeyCode>,
+    pub toggle_grid: Option<KeyCode>,
+    pub look_left: Option<KeyCode>,
+    pub look_right: Option<KeyCode>,
+    pub look_up: Option<KeyCode>,
+    pub look_down: Option<KeyCode>,
+}
+
+impl From<&ObserverBindings> for ObserverHotkeys {
+    fn from(bindings: &ObserverBindings) -> Self {
+        Self {
+            toggle_view: parse_key_code(&bindings.toggle_view),
+            toggle_grid: parse_key_code(&bindings.toggle_grid),
+            look_left: parse_key_code(&bindings.look_left),
+            look_right: parse_key_code(&bindings.look_right),
+            look_up: parse_key_code(&bindings.look_up),
+            look_down: parse_key_code(&bindings.look_down),
+        }
+    }
+}
+
+/// Gizmo group for the alt-az grid.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ObserverGizmos;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_wraps_azimuth_and_clamps_altitude() {
+        let mut view = ObserverView::from(&ObserverConfig::default());
+        view.turn(PI + 0.5, 0.0);
+        assert!((view.look.azimuth - 0.5).abs() < 1e-12);
+
+        view.turn(0.0, 10.0);
+        assert_eq!(view.look.altitude, MAX_LOOK_ALTITUDE);
+        view.turn(0.0, -20.0);
+        assert_eq!(view.look.altitude, -MAX_LOOK_ALTITUDE);
+    }
+}
@@ -0,0 +1,125 @@
+//! # Orbit Display
+//!
+//! Which orbit overlays are shown, the frame they are drawn in and the
+//! latest predicted trajectories.
+
+use bevy::math::DVec3;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::tasks::Task;
+
+use crate::config::gameplay::{OrbitBindings, OrbitDisplayConfig};
+use crate::config::input::parse_key_code;
+
+/// Requested state of the orbit overlays.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct OrbitOverlay {
+    pub trails: bool,
+    pub predictions: bool,
+    /// Scenario name of the body paths are drawn relative to; `None` draws
+    /// them in the scene frame.
+    pub reference: Option<String>,
+}
+
+impl From<&OrbitDisplayConfig> for OrbitOverlay {
+    fn from(config: &OrbitDisplayConfig) -> Self {
+        Self {
+            trails: config.show_trails,
+            predictions: config.show_predictions,
+            reference: (!config.reference.is_empty()).then(|| config.reference.clone()),
+        }
+    }
+}
+
+impl OrbitOverlay {
+    /// Selects the body after the current reference in `names`, then the
+    /// scene frame after the last one.
+    pub fn cycle_reference(&mut self, names: &[&str]) {
+        let next = match &self.reference {
+            None => 0,
+            Some(current) => names
+                .iter()
+                .position(|name| name == current)
+                .map_or(0, |index| index + 1),
+        };
+        self.reference = names.get(next).map(|name| name.to_string());
+    }
+}
+
+/// Gizmo group for orbit trails and predicted trajectories.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct OrbitGizmos;
+
+/// Simulated time of the last trail sample. Every trail is sampled at the
+/// same instants, so trails line up sample by sample from the newest.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct TrailClock {
+    pub last_sample: Option<f64>,
+}
+
+/// Background prediction yielding each body's path and the maneuver nodes
+/// of the free ones.
+pub type PredictionTask = Task<(Vec<(Entity, Vec<DVec3>)>, Vec<(Entity, NodeMarker)>)>;
+
+/// Predicted future positions of the bodies, in the scene frame. On-rails
+/// bodies follow their theories.
+#[derive(Resource, Default)]
+pub struct PredictedPaths {
+    pub paths: HashMap<Entity, Vec<DVec3>>,
+    /// Where each spacecraft's upcoming nodes fall on its path, in time
+    /// order.
+    pub nodes: HashMap<Entity, Vec<NodeMarker>>,
+    /// Prediction running on the async compute pool.
+    pub pending: Option<PredictionTask>,
+    /// Real time since the last prediction started (s).
+    pub since_refresh: f32,
+}
+
+/// A maneuver node on a predicted path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeMarker {
+    pub position: DVec3,
+    /// Fractional index into the path the node falls at.
+    pub sample: f64,
+}
+
+/// `OrbitBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct OrbitHotkeys {
+    pub toggle_trails: Option<KeyCode>,
+    pub toggle_predictions: Option<KeyCode>,
+    pub cycle_reference: Option<KeyCode>,
+}
+
+impl From<&OrbitBindings> for OrbitHotkeys {
+    fn from(bindings: &OrbitBindings) -> Self {
+        Self {
+            toggle_trails: parse_key_code(&bindings.toggle_trails),
+            toggle_predictions: parse_key_code(&bindings.toggle_predictions),
+            cycle_reference: parse_key_code(&bindings.cycle_reference),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_reference_through_bodies_and_scene() {
+        let names = ["earth", "moon"];
+        let mut overlay = OrbitOverlay::from(&OrbitDisplayConfig::default());
+        assert_eq!(overlay.reference, None);
+
+        overlay.cycle_reference(&names);
+        assert_eq!(overlay.reference.as_deref(), Some("earth"));
+        overlay.cycle_reference(&names);
+        assert_eq!(overlay.reference.as_deref(), Some("moon"));
+        overlay.cycle_reference(&names);
+        assert_eq!(overlay.reference, None);
+
+        overlay.reference = Some("pluto".to_string());
+        overlay.cycle_reference(&names);
+        assert_eq!(overlay.reference.as_deref(), Some("earth"));
+    }
+}
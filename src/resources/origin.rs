@@ -0,0 +1,48 @@
+//! # Floating Origin
+//!
+//! Simulation positions are kept in double precision as `WorldPosition`;
+//! the render `Transform` only holds the offset from a floating origin that
+//! follows the camera focus. Offsets near the camera stay small, so `f32`
+//! keeps them precise even when the scene spans the whole Solar System.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+/// Scene-frame point that render space is centred on (m).
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct FloatingOrigin(pub DVec3);
+
+impl FloatingOrigin {
+    /// Render translation of the scene-frame `position`.
+    pub fn to_render(&self, position: DVec3) -> Vec3 {
+        (position - self.0).as_vec3()
+    }
+
+    /// Scene-frame position of the render `translation`.
+    pub fn to_world(&self, translation: Vec3) -> DVec3 {
+        self.0 + translation.as_dvec3()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Neptune's mean distance from the Sun (m).
+    const NEPTUNE_DISTANCE: f64 = 4.495e12;
+
+    #[test]
+    fn offsets_near_the_origin_keep_sub_metre_precision() {
+        let neptune = DVec3::new(NEPTUNE_DISTANCE, -1.2e11, 3.4e10);
+        let probe = neptune + DVec3::new(1234.25, -0.5, 0.125);
+        let origin = FloatingOrigin(neptune);
+
+        let offset = origin.to_render(probe) - origin.to_render(neptune);
+        assert!(offset.as_dvec3().distance(probe - neptune) < 1e-3);
+        assert!(origin.to_world(origin.to_render(probe)).distance(probe) < 1e-3);
+
+        // Without the origin the same offset is lost to f32 rounding.
+        let naive = probe.as_vec3() - neptune.as_vec3();
+        assert!(naive.as_dvec3().distance(probe - neptune) > 1.0);
+    }
+}
@@ -0,0 +1,279 @@
+//! # View Scale
+//!
+//! Remapping of rendered distances and radii so that bodies stay visible
+//! across the Solar System. Distances are compressed radially around the
+//! floating origin, which keeps every direction seen from the camera focus
+//! unchanged. Switching scale blends the old mapping into the new one.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+
+use crate::config::gameplay::{ScaleMode, ViewScaleBindings, ViewScaleConfig};
+use crate::config::input::parse_key_code;
+use crate::resources::FloatingOrigin;
+
+/// Rendered distance of a true distance `distance` under `mode`, true to
+/// scale up to `reference`.
+pub fn remap(mode: ScaleMode, distance: f64, reference: f64) -> f64 {
+    if distance <= reference {
+        return distance;
+    }
+    match mode {
+        ScaleMode::Linear => distance,
+        ScaleMode::Logarithmic => reference * (1.0 + (distance / reference).ln()),
+        ScaleMode::SquareRoot => (distance * reference).sqrt(),
+    }
+}
+
+/// Current view scale and any blend towards it.
+///
+/// The observer view stands the eye on a body's true surface, so it
+/// suspends the scale: everything is drawn true to scale until it ends.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ViewScale {
+    /// Mode being shown, or blended towards.
+    pub mode: ScaleMode,
+    /// Radius factor being shown, or blended towards.
+    pub inflation: f64,
+    pub reference_distance: f64,
+    /// Whether the scale is suspended for the observer view.
+    pub suspended: bool,
+    blend: Option<Blend>,
+}
+
+/// Blend in progress, starting from the mapping shown when it began.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Blend {
+    /// Weight of each of `ScaleMode::ALL` at the start.
+    weights: [f64; 3],
+    ln_inflation: f64,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl From<&ViewScaleConfig> for ViewScale {
+    fn from(config: &ViewScaleConfig) -> Self {
+        Self {
+            mode: config.mode,
+            inflation: config.radius_inflation,
+            reference_distance: config.reference_distance,
+            suspended: false,
+            blend: None,
+        }
+    }
+}
+
+impl ViewScale {
+    /// Starts blending to `mode` over `duration` seconds.
+    pub fn set_mode(&mut self, mode: ScaleMode, duration: f64) {
+        self.start_blend(duration);
+        self.mode = mode;
+    }
+
+    /// Starts blending radii to `inflation` over `duration` seconds.
+    pub fn set_inflation(&mut self, inflation: f64, duration: f64) {
+        self.start_blend(duration);
+        self.inflation = inflation;
+    }
+
+    /// Whether a blend is running.
+    pub fn is_blending(&self) -> bool {
+        self.blend.is_some()
+    }
+
+    /// Advances any blend by `dt` seconds.
+    pub fn update(&mut self, dt: f64) {
+        let Some(blend) = &mut self.blend else {
+            return;
+        };
+        blend.elapsed += dt;
+        if blend.elapsed >= blend.duration {
+            self.blend = None;
+        }
+    }
+
+    /// Rendered distance of the true `distance` from the focus.
+    pub fn distance(&self, distance: f64) -> f64 {
+        if self.suspended {
+            return distance;
+        }
+        let weights = self.weights();
+        ScaleMode::ALL
+            .iter()
+            .zip(weights)
+            .filter(|(_, weight)| *weight != 0.0)
+            .map(|(&mode, weight)| remap(mode, distance, self.reference_distance) * weight)
+            .sum()
+    }
+
+    /// Rendered offset of the true `offset` from the focus, in the same
+    /// direction.
+    pub fn apply(&self, offset: DVec3) -> DVec3 {
+        let distance = offset.length();
+        if self.suspended || distance <= self.reference_distance {
+            return offset;
+        }
+        offset * (self.distance(distance) / distance)
+    }
+
+    /// Render translation of the scene-frame `position`, remapped around
+    /// `origin`.
+    pub fn to_render(&self, origin: &FloatingOrigin, position: DVec3) -> Vec3 {
+        self.apply(position - origin.0).as_vec3()
+    }
+
+    /// Factor body radii are drawn at right now. Blends in `ln` space, so
+    /// each doubling takes as long as the last.
+    pub fn radius_scale(&self) -> f64 {
+        if self.suspended {
+            return 1.0;
+        }
+        match self.blend {
+            Some(blend) => {
+                let eased = blend.eased();
+                (blend.ln_inflation + (self.inflation.ln() - blend.ln_inflation) * eased).exp()
+            }
+            None => self.inflation,
+        }
+    }
+
+    /// Weight of each of `ScaleMode::ALL` right now; they sum to one.
+    fn weights(&self) -> [f64; 3] {
+        let target = ScaleMode::ALL.map(|mode| f64::from(u8::from(mode == self.mode)));
+        match self.blend {
+            Some(blend) => {
+                let eased = blend.eased();
+                let mut weights = blend.weights;
+                for (weight, target) in weights.iter_mut().zip(target) {
+                    *weight += (target - *weight) * eased;
+                }
+                weights
+            }
+            None => target,
+        }
+    }
+
+    /// Freezes the mapping shown now as the start of a new blend.
+    fn start_blend(&mut self, duration: f64) {
+        self.blend = (duration > 0.0).then(|| Blend {
+            weights: self.weights(),
+            ln_inflation: self.radius_scale().ln(),
+            elapsed: 0.0,
+            duration,
+        });
+    }
+}
+
+impl Blend {
+    fn eased(&self) -> f64 {
+        let t = (self.elapsed / self.duration).min(1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+/// `ViewScaleBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ViewScaleHotkeys {
+    pub cycle_mode: Option<KeyCode>,
+    pub inflate_radii: Option<KeyCode>,
+    pub deflate_radii: Option<KeyCode>,
+}
+
+impl From<&ViewScaleBindings> for ViewScaleHotkeys {
+    fn from(bindings: &ViewScaleBindings) -> Self {
+        Self {
+            cycle_mode: parse_key_code(&bindings.cycle_mode),
+            inflate_radii: parse_key_code(&bindings.inflate_radii),
+            deflate_radii: parse_key_code(&bindings.deflate_radii),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ASTRONOMICAL_UNIT as AU;
+
+    fn scale(mode: ScaleMode) -> ViewScale {
+        ViewScale::from(&ViewScaleConfig { mode, ..default() })
+    }
+
+    #[test]
+    fn modes_compress_only_beyond_the_reference() {
+        for mode in ScaleMode::ALL {
+            let scale = scale(mode);
+            assert_eq!(scale.distance(3.844e8), 3.844e8);
+            let offset = DVec3::new(0.0, -3.0e8, 4.0e8);
+            assert_eq!(scale.apply(offset), offset);
+        }
+
+        let (log, sqrt) = (scale(ScaleMode::Logarithmic), scale(ScaleMode::SquareRoot));
+        assert_eq!(scale(ScaleMode::Linear).distance(30.0 * AU), 30.0 * AU);
+        assert!((sqrt.distance(100.0e9) - 1.0e10).abs() < 1e-3);
+        assert!((log.distance(1.0e9 * std::f64::consts::E) - 2.0e9).abs() < 1e-3);
+        // Order is kept, so no planet overtakes another.
+        let radii = [0.39, 0.72, 1.0, 1.52, 5.2, 9.5, 19.2, 30.1].map(|a| a * AU);
+        for mode in [ScaleMode::Logarithmic, ScaleMode::SquareRoot] {
+            let mapped = radii.map(|r| scale(mode).distance(r));
+            assert!(mapped.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(mapped[7] < 30.1 * AU / 10.0);
+        }
+
+        let direction = DVec3::new(3.0, 4.0, 12.0).normalize();
+        let mapped = log.apply(direction * 30.0 * AU);
+        assert!(mapped.normalize().dot(direction) > 1.0 - 1e-12);
+    }
+
+    #[test]
+    fn switching_blends_from_what_is_shown() {
+        let mut scale = scale(ScaleMode::Linear);
+        let d = 5.0 * AU;
+        scale.set_mode(ScaleMode::SquareRoot, 2.0);
+        assert!(scale.is_blending());
+        assert_eq!(scale.distance(d), d);
+
+        scale.update(1.0);
+        let sqrt = remap(ScaleMode::SquareRoot, d, scale.reference_distance);
+        let halfway = scale.distance(d);
+        assert!((halfway - (d + sqrt) / 2.0).abs() < 1e-3);
+
+        // Interrupting carries on from the halfway mapping.
+        scale.set_mode(ScaleMode::Logarithmic, 2.0);
+        assert!((scale.distance(d) - halfway).abs() < 1e-3);
+        scale.update(2.5);
+        assert!(!scale.is_blending());
+        let log = remap(ScaleMode::Logarithmic, d, scale.reference_distance);
+        assert!((scale.distance(d) - log).abs() < 1e-3);
+    }
+
+    #[test]
+    fn inflation_blends_geometrically() {
+        let mut scale = scale(ScaleMode::Linear);
+        assert_eq!(scale.radius_scale(), 1.0);
+        scale.set_inflation(100.0, 1.0);
+        scale.update(0.5);
+        assert!((scale.radius_scale() - 10.0).abs() < 1e-9);
+        scale.update(0.5);
+        assert_eq!(scale.radius_scale(), 100.0);
+
+        scale.set_inflation(1.0, 0.0);
+        assert!(!scale.is_blending());
+        assert_eq!(scale.radius_scale(), 1.0);
+    }
+
+    #[test]
+    fn suspended_scales_draw_true_to_scale() {
+        let mut scale = ViewScale::from(&ViewScaleConfig {
+            mode: ScaleMode::Logarithmic,
+            radius_inflation: 50.0,
+            ..default()
+        });
+        scale.suspended = true;
+        let offset = DVec3::new(0.0, 30.0 * AU, 0.0);
+        assert_eq!(scale.apply(offset), offset);
+        assert_eq!(scale.radius_scale(), 1.0);
+        scale.suspended = false;
+        assert!(scale.apply(offset).length() < offset.length());
+        assert_eq!(scale.radius_scale(), 50.0);
+    }
+}
@@ -0,0 +1,97 @@
+//! # Sky Overlay
+//!
+//! Which sky culture is shown and which of its layers are visible.
+
+use bevy::prelude::*;
+
+use crate::astro::skyculture::ConstellationFigure;
+use crate::config::gameplay::{SkyBindings, SkyCultureConfig};
+use crate::config::input::parse_key_code;
+
+/// Requested state of the constellation overlay.
+///
+/// Changing `culture` switches the loaded `SkyCulture` on the next frame;
+/// the layer flags take effect immediately.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SkyOverlay {
+    pub culture: String,
+    pub lines: bool,
+    pub boundaries: bool,
+    pub names: bool,
+}
+
+impl From<&SkyCultureConfig> for SkyOverlay {
+    fn from(config: &SkyCultureConfig) -> Self {
+        Self {
+            culture: config.culture.clone(),
+            lines: config.show_lines,
+            boundaries: config.show_boundaries,
+            names: config.show_names,
+        }
+    }
+}
+
+impl SkyOverlay {
+    /// Selects the culture after the current one in `cultures`, wrapping
+    /// around. An unlisted current culture moves to the first entry.
+    pub fn cycle_culture(&mut self, cultures: &[String]) {
+        let next = cultures
+            .iter()
+            .position(|culture| *culture == self.culture)
+            .map_or(0, |index| (index + 1) % cultures.len());
+        if let Some(culture) = cultures.get(next) {
+            self.culture = culture.clone();
+        }
+    }
+}
+
+/// Figures of the loaded culture, resolved against the star catalog.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConstellationFigures(pub Vec<ConstellationFigure>);
+
+/// Gizmo group for constellation lines and boundaries.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ConstellationGizmos;
+
+/// `SkyBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SkyHotkeys {
+    pub cycle_culture: Option<KeyCode>,
+    pub toggle_lines: Option<KeyCode>,
+    pub toggle_boundaries: Option<KeyCode>,
+    pub toggle_names: Option<KeyCode>,
+}
+
+impl From<&SkyBindings> for SkyHotkeys {
+    fn from(bindings: &SkyBindings) -> Self {
+        Self {
+            cycle_culture: parse_key_code(&bindings.cycle_culture),
+            toggle_lines: parse_key_code(&bindings.toggle_lines),
+            toggle_boundaries: parse_key_code(&bindings.toggle_boundaries),
+            toggle_names: parse_key_code(&bindings.toggle_names),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_through_cultures() {
+        let cultures = ["western", "chinese"].map(str::to_string);
+        let mut overlay = SkyOverlay::from(&SkyCultureConfig::default());
+
+        overlay.cycle_culture(&cultures);
+        assert_eq!(overlay.culture, "chinese");
+        overlay.cycle_culture(&cultures);
+        assert_eq!(overlay.culture, "western");
+
+        overlay.culture = "norse".to_string();
+        overlay.cycle_culture(&cultures);
+        assert_eq!(overlay.culture, "western");
+
+        overlay.cycle_culture(&[]);
+        assert_eq!(overlay.culture, "western");
+    }
+}
@@ -0,0 +1,63 @@
+//! # Spacecraft
+//!
+//! Which spacecraft the maneuver hotkeys edit, and the hotkeys themselves.
+
+use bevy::prelude::*;
+
+use crate::config::gameplay::SpacecraftBindings;
+use crate::config::input::parse_key_code;
+
+/// Spacecraft spawned last. Maneuver hotkeys edit it unless another
+/// spacecraft is selected.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveSpacecraft(pub Option<Entity>);
+
+impl ActiveSpacecraft {
+    /// Spacecraft being planned: the selected body when `is_craft` holds
+    /// for it, else the active one.
+    pub fn target(
+        &self,
+        selected: Option<Entity>,
+        is_craft: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        selected.filter(|&entity| is_craft(entity)).or(self.0)
+    }
+}
+
+/// `SpacecraftBindings` resolved to key codes. Unknown names stay unbound.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SpacecraftHotkeys {
+    pub spawn: Option<KeyCode>,
+    pub add_node: Option<KeyCode>,
+    pub remove_node: Option<KeyCode>,
+    pub next_node: Option<KeyCode>,
+    pub prograde: Option<KeyCode>,
+    pub retrograde: Option<KeyCode>,
+    pub normal: Option<KeyCode>,
+    pub anti_normal: Option<KeyCode>,
+    pub radial_out: Option<KeyCode>,
+    pub radial_in: Option<KeyCode>,
+    pub later: Option<KeyCode>,
+    pub earlier: Option<KeyCode>,
+    pub toggle_execution: Option<KeyCode>,
+}
+
+impl From<&SpacecraftBindings> for SpacecraftHotkeys {
+    fn from(bindings: &SpacecraftBindings) -> Self {
+        Self {
+            spawn: parse_key_code(&bindings.spawn),
+            add_node: parse_key_code(&bindings.add_node),
+            remove_node: parse_key_code(&bindings.remove_node),
+            next_node: parse_key_code(&bindings.next_node),
+            prograde: parse_key_code(&bindings.prograde),
+            retrograde: parse_key_code(&bindings.retrograde),
+            normal: parse_key_code(&bindings.normal),
+            anti_normal: parse_key_code(&bindings.anti_normal),
+            radial_out: parse_key_code(&bindings.radial_out),
+            radial_in: parse_key_code(&bindings.radial_in),
+            later: parse_key_code(&bindings.later),
+            earlier: parse_key_code(&bindings.earlier),
+            toggle_execution: parse_key_code(&bindings.toggle_execution),
+        }
+    }
+}
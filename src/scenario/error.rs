@@ -0,0 +1,79 @@
+//! # Scenario Errors
+//!
+//! Errors raised while reading a scenario, formatted for the error screen.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Failure to load a scenario file.
+#[derive(Debug, Error)]
+pub enum ScenarioError {
+    /// The file could not be read.
+    #[error("Failed to read scenario {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The file is not valid scenario RON.
+    #[error("{0}")]
+    Parse(ParseContext),
+    /// The file parsed but describes an invalid scenario.
+    #[error("Invalid scenario {path:?}:\n- {}", .errors.join("\n- "))]
+    Invalid { path: PathBuf, errors: Vec<String> },
+}
+
+/// Location and source excerpt of a RON syntax or schema error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseContext {
+    pub path: PathBuf,
+    /// 1-based line of the error.
+    pub line: usize,
+    /// 1-based column of the error.
+    pub column: usize,
+    pub message: String,
+    /// The offending source line, if available.
+    pub excerpt: Option<String>,
+}
+
+impl ParseContext {
+    /// Builds a context from a `ron` error and the source it was parsed from.
+    pub fn from_ron(path: PathBuf, source: &str, error: &ron::error::SpannedError) -> Self {
+        let line = error.span.start.line;
+        let column = error.span.start.col;
+        Self {
+            path,
+            line,
+            column,
+            message: error.code.to_string(),
+            excerpt: line
+                .checked_sub(1)
+                .and_then(|index| source.lines().nth(index))
+                .map(str::to_string),
+        }
+    }
+}
+
+impl fmt::Display for ParseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse scenario {:?} at line {}, column {}: {}",
+            self.path, self.line, self.column, self.message
+        )?;
+        if let Some(excerpt) = &self.excerpt {
+            let gutter = self.line.to_string();
+            writeln!(f)?;
+            writeln!(f, "{gutter} | {excerpt}")?;
+            write!(
+                f,
+                "{} | {}^",
+                " ".repeat(gutter.len()),
+                " ".repeat(self.column.saturating_sub(1))
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+//! # Scenario Format
+//!
+//! Serde model of a `.ron` scenario file. Vectors are written as `(x, y, z)`
+//! tuples in SI units (metres, kilograms, seconds); angles are in radians.
+
+use bevy::color::Srgba;
+use menu::settings::validation::Validate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::astro::Planet;
+use crate::procgen::PlanetClass;
+
+/// A complete scenario: bodies, lights and the initial camera.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Scenario {
+    /// Human-readable scenario name.
+    pub name: String,
+    /// Optional free-form description.
+    pub description: String,
+    /// Shift initial velocities so the total momentum of N-body bodies is zero.
+    pub zero_momentum: bool,
+    /// Bodies in spawn order; parents must be declared before their children.
+    pub bodies: Vec<BodySpec>,
+    /// Scene lights.
+    pub lights: Vec<LightSpec>,
+    /// Initial camera placement.
+    pub camera: CameraSpec,
+    /// Planets generated from a seed and appended to `bodies` on load.
+    pub generated: Option<GeneratedSystemSpec>,
+}
+
+/// A single celestial body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BodySpec {
+    /// Unique body name.
+    pub name: String,
+    /// Mass (kg).
+    pub mass: f64,
+    /// Mean radius (m).
+    pub radius: f64,
+    /// Name of the body this one's initial state is relative to.
+    pub parent: Option<String>,
+    /// Initial state, relative to `parent` when set.
+    pub motion: MotionSpec,
+    /// Follow the orbit analytically instead of integrating it. An `Orbit`
+    /// motion (which requires `parent`) is followed around the parent; a
+    /// `State` motion at rest pins the body where it starts. `Ephemeris`
+    /// and `Lunar` bodies always follow their theory.
+    pub on_rails: bool,
+    /// Base color as a hex string, e.g. `"#4f7dd1"`.
+    pub color: String,
+    /// Optional texture path relative to the assets directory.
+    pub texture: Option<String>,
+    /// Emissive strength multiplier applied to `color` (stars glow).
+    pub emissive: f32,
+    /// Sidereal rotation period (s). Negative values spin retrograde.
+    pub rotation_period: Option<f64>,
+    /// Tilt of the rotation pole from local +Z about +X (rad).
+    pub axial_tilt: f64,
+    /// Surface description of a generated planet.
+    pub surface: Option<SurfaceSpec>,
+}
+
+impl BodySpec {
+    /// Whether the body is placed analytically rather than integrated.
+    pub fn is_kinematic(&self) -> bool {
+        self.on_rails || matches!(self.motion, MotionSpec::Ephemeris(_) | MotionSpec::Lunar)
+    }
+}
+
+impl Default for BodySpec {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            mass: 0.0,
+            radius: 0.0,
+            parent: None,
+            motion: MotionSpec::default(),
+            on_rails: false,
+            color: "#ffffff".to_string(),
+            texture: None,
+            emissive: 0.0,
+            rotation_period: None,
+            axial_tilt: 0.0,
+            surface: None,
+        }
+    }
+}
+
+/// Class, albedo and colors of a generated planet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SurfaceSpec {
+    pub class: PlanetClass,
+    /// Bond albedo.
+    pub albedo: f32,
+    /// Hex colors, base first.
+    pub palette: Vec<String>,
+    /// Seed for surface detail.
+    pub seed: u64,
+}
+
+/// Initial motion of a body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum MotionSpec {
+    /// Cartesian state vector.
+    State {
+        position: [f64; 3],
+        #[serde(default)]
+        velocity: [f64; 3],
+    },
+    /// Keplerian elements around `parent`.
+    Orbit(OrbitSpec),
+    /// Heliocentric VSOP87 position of a planet at the simulation epoch,
+    /// relative to `parent` (the Sun) when set.
+    Ephemeris(Planet),
+    /// Geocentric position of the Moon from the lunar theory at the
+    /// simulation epoch, relative to `parent` (the Earth) when set.
+    Lunar,
+}
+
+impl Default for MotionSpec {
+    fn default() -> Self {
+        Self::State {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+        }
+    }
+}
+
+/// Keplerian elements, mirroring `OrbitalElements`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct OrbitSpec {
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub longitude_of_ascending_node: f64,
+    pub argument_of_periapsis: f64,
+    pub mean_anomaly_at_epoch: f64,
+    pub epoch: f64,
+}
+
+/// A planetary system generated around an existing body.
+///
+/// Orbits are spread geometrically between `inner_orbit` and `outer_orbit`
+/// and named after the parent, `"<parent> b"` outwards.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GeneratedSystemSpec {
+    /// Seed of the whole system; the same seed gives the same planets.
+    pub seed: u64,
+    /// Name of the body the planets orbit.
+    pub parent: String,
+    /// Number of planets.
+    pub planets: u32,
+    /// Semi-major axis bounds (m).
+    pub inner_orbit: f64,
+    pub outer_orbit: f64,
+}
+
+impl Default for GeneratedSystemSpec {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            parent: String::new(),
+            planets: 6,
+            inner_orbit: 5.0e10,
+            outer_orbit: 3.0e12,
+        }
+    }
+}
+
+/// A light source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LightSpec {
+    /// Omnidirectional light at a point.
+    Point {
+        position: [f64; 3],
+        intensity: f32,
+        range: f32,
+        #[serde(default = "white")]
+        color: String,
+        #[serde(default)]
+        shadows: bool,
+    },
+    /// Parallel rays, e.g. sunlight from a distant star.
+    Directional {
+        /// Direction the light travels in.
+        direction: [f64; 3],
+        illuminance: f32,
+        #[serde(default = "white")]
+        color: String,
+        #[serde(default)]
+        shadows: bool,
+    },
+}
+
+/// Initial camera placement.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CameraSpec {
+    pub position: [f64; 3],
+    pub target: [f64; 3],
+    pub up: [f64; 3],
+    /// Vertical field of view (degrees).
+    pub fov: f32,
+    /// Far culling distance (m).
+    pub far: f32,
+}
+
+impl Default for CameraSpec {
+    fn default() -> Self {
+        Self {
+            position: [0.0, 0.0, 20.0],
+            target: [0.0; 3],
+            up: [0.0, 1.0, 0.0],
+            fov: 45.0,
+            far: 1.0e13,
+        }
+    }
+}
+
+/// Generated planets are lettered `b` to `z`.
+pub const MAX_GENERATED_PLANETS: u32 = 25;
+
+fn white() -> String {
+    "#ffffff".to_string()
+}
+
+impl Validate for Scenario {
+    type Error = Vec<String>;
+
+    /// Checks cross-references and physical ranges, collecting every problem.
+    fn validate(&self) -> Result<(), Self::Error> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for body in &self.bodies {
+            let name = &body.name;
+            if name.is_empty() {
+                errors.push("body with empty name".to_string());
+            }
+            if !seen.insert(name.as_str()) {
+                errors.push(format!("duplicate body name '{name}'"));
+            }
+            if !(body.mass.is_finite() && body.mass > 0.0) {
+                errors.push(format!("body '{name}': mass must be positive"));
+            }
+            if !(body.radius.is_finite() && body.radius > 0.0) {
+                errors.push(format!("body '{name}': radius must be positive"));
+            }
+            if let Some(parent) = &body.parent
+                && (parent == name || !seen.contains(parent.as_str()))
+            {
+                errors.push(format!(
+                    "body '{name}': parent '{parent}' must be declared before it"
+                ));
+            }
+            if let MotionSpec::Orbit(orbit) = &body.motion {
+                if body.parent.is_none() {
+                    errors.push(format!("body '{name}': orbit motion requires a parent"));
+                }
+                if orbit.eccentricity < 0.0 || orbit.eccentricity == 1.0 {
+                    errors.push(format!(
+                        "body '{name}': eccentricity {} is not supported",
+                        orbit.eccentricity
+                    ));
+                }
+                if (orbit.eccentricity < 1.0) != (orbit.semi_major_axis > 0.0) {
+                    errors.push(format!(
+                        "body '{name}': semi_major_axis must be positive for ellipses and negative for hyperbolas"
+                    ));
+                }
+            } else if body.on_rails
+                && !matches!(body.motion, MotionSpec::State { velocity, .. } if velocity == [0.0; 3])
+            {
+                errors.push(format!(
+                    "body '{name}': on_rails requires an orbit or a state at rest"
+                ));
+            }
+            if body.rotation_period == Some(0.0) {
+                errors.push(format!("body '{name}': rotation_period must be non-zero"));
+            }
+            if Srgba::hex(&body.color).is_err() {
+                errors.push(format!("body '{name}': invalid color '{}'", body.color));
+            }
+            if let Some(surface) = &body.surface {
+                if !(0.0..=1.0).contains(&surface.albedo) {
+                    errors.push(format!("body '{name}': albedo must be within [0, 1]"));
+                }
+                for color in surface.palette.iter().filter(|c| Srgba::hex(c).is_err()) {
+                    errors.push(format!("body '{name}': invalid palette color '{color}'"));
+                }
+            }
+        }
+
+        for (index, light) in self.lights.iter().enumerate() {
+            let color = match light {
+                LightSpec::Point { color, .. } | LightSpec::Directional { color, .. } => color,
+            };
+            if Srgba::hex(color).is_err() {
+                errors.push(format!("light #{index}: invalid color '{color}'"));
+            }
+        }
+
+        if let Some(generated) = &self.generated {
+            if !seen.contains(generated.parent.as_str()) {
+                errors.push(format!(
+                    "generated system: parent '{}' is not a body",
+                    generated.parent
+                ));
+            }
+            if !(1..=MAX_GENERATED_PLANETS).contains(&generated.planets) {
+                errors.push(format!(
+                    "generated system: planets must be between 1 and {MAX_GENERATED_PLANETS}"
+                ));
+            }
+            if !(generated.inner_orbit > 0.0 && generated.inner_orbit < generated.outer_orbit) {
+                errors.push(
+                    "generated system: orbits must satisfy 0 < inner_orbit < outer_orbit"
+                        .to_string(),
+                );
+            }
+        }
+
+        if !(self.camera.fov > 0.0 && self.camera.fov < 180.0) {
+            errors.push(format!("camera fov {} out of range (0, 180)", self.camera.fov));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
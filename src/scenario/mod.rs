@@ -0,0 +1,114 @@
+//! # Scenarios
+//!
+//! Data-driven world descriptions loaded from `assets/scenarios/*.ron`.
+
+pub mod error;
+pub mod format;
+
+pub use error::{ParseContext, ScenarioError};
+pub use format::{
+    BodySpec, CameraSpec, GeneratedSystemSpec, LightSpec, MotionSpec, OrbitSpec, Scenario,
+    SurfaceSpec,
+};
+
+use menu::settings::validation::Validate;
+use std::path::Path;
+
+/// Parses and validates scenario RON text. `path` is only used for messages.
+pub fn parse_scenario(path: &Path, source: &str) -> Result<Scenario, ScenarioError> {
+    let scenario = ron::from_str::<Scenario>(source).map_err(|e| {
+        ScenarioError::Parse(ParseContext::from_ron(path.to_path_buf(), source, &e))
+    })?;
+
+    scenario
+        .validate()
+        .map_err(|errors| ScenarioError::Invalid {
+            path: path.to_path_buf(),
+            errors,
+        })?;
+
+    Ok(scenario)
+}
+
+/// Reads, parses and validates a scenario file.
+pub fn load_scenario(path: &Path) -> Result<Scenario, ScenarioError> {
+    let source = std::fs::read_to_string(path).map_err(|source| ScenarioError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_scenario(path, &source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "scenarios/test.ron";
+
+    #[test]
+    fn bundled_scenarios_are_valid() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/scenarios");
+        for entry in std::fs::read_dir(dir).expect("scenarios directory exists") {
+            let path = entry.unwrap().path();
+            if let Err(e) = load_scenario(&path) {
+                panic!("{e}");
+            }
+        }
+    }
+
+    #[test]
+    fn parse_error_reports_line_and_column() {
+        let source = "Scenario(\n    name: \"x\",\n    bodies: [ (mass: oops) ],\n)";
+        let Err(ScenarioError::Parse(context)) = parse_scenario(Path::new(PATH), source) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(context.line, 3);
+        assert_eq!(context.excerpt.as_deref(), Some("    bodies: [ (mass: oops) ],"));
+        let message = ScenarioError::Parse(context).to_string();
+        assert!(message.contains("line 3"), "{message}");
+        assert!(message.lines().last().unwrap().trim_end().ends_with('^'));
+    }
+
+    #[test]
+    fn dangling_parent_is_rejected() {
+        let source = r#"Scenario(bodies: [
+            (name: "moon", mass: 1.0, radius: 1.0, parent: Some("earth")),
+            (name: "earth", mass: 1.0, radius: 1.0),
+        ])"#;
+        let Err(ScenarioError::Invalid { errors, .. }) = parse_scenario(Path::new(PATH), source)
+        else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("earth"));
+    }
+
+    #[test]
+    fn only_resting_states_are_pinned() {
+        let source = r#"Scenario(bodies: [
+            (name: "sun", mass: 1.0, radius: 1.0, on_rails: true),
+            (name: "rogue", mass: 1.0, radius: 1.0, on_rails: true,
+                motion: State(position: (1.0, 0.0, 0.0), velocity: (0.0, 1.0, 0.0))),
+        ])"#;
+        let Err(ScenarioError::Invalid { errors, .. }) = parse_scenario(Path::new(PATH), source)
+        else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("rogue"));
+    }
+
+    #[test]
+    fn generated_system_is_validated() {
+        let source = r#"Scenario(
+            bodies: [(name: "star", mass: 1.0, radius: 1.0)],
+            generated: Some((parent: "sun", planets: 0, inner_orbit: 2.0, outer_orbit: 1.0)),
+        )"#;
+        let Err(ScenarioError::Invalid { errors, .. }) = parse_scenario(Path::new(PATH), source)
+        else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(errors.len(), 3);
+    }
+}
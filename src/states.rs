@@ -0,0 +1,20 @@
+//! # Game States
+//!
+//! Sub-states of `AppState::InGame`.
+
+use bevy::prelude::*;
+use launcher::states::AppState;
+
+/// How the world is viewed while in game.
+///
+/// Exists only while `AppState::InGame` is active and starts in `Orbital`
+/// every time the game is entered.
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum ViewMode {
+    /// Free camera from the scenario, looking at the system from outside.
+    #[default]
+    Orbital,
+    /// Standing on a body's surface with the sky turning overhead.
+    Observer,
+}
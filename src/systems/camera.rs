@@ -0,0 +1,172 @@
+//! # Camera Systems
+//!
+//! Orbit camera of the orbital view: mouse rotate, pan and zoom, focus
+//! hotkeys and the per-frame camera placement. Mouse sensitivity and
+//! inversion come from `UserSettings.controls`, speeds and bounds from
+//! `GameplayConfig.camera`.
+
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::math::{DVec2, DVec3};
+use bevy::prelude::*;
+
+use crate::components::{CelestialBody, Radius, WorldPosition};
+use crate::config::GameplayConfig;
+use crate::resources::{ActiveScenario, CameraHotkeys, FloatingOrigin, OrbitCamera, ViewScale};
+use launcher::config::UserSettings;
+use launcher::config::settings::ControlsSettings;
+
+/// Places the orbit camera where the scenario camera starts.
+pub fn reset_orbit_camera(
+    mut commands: Commands,
+    scenario: Option<Res<ActiveScenario>>,
+    config: Res<GameplayConfig>,
+) {
+    let spec = scenario
+        .map(|scenario| scenario.0.camera.clone())
+        .unwrap_or_default();
+    commands.insert_resource(OrbitCamera::looking_at(
+        DVec3::from_array(spec.position),
+        DVec3::from_array(spec.target),
+        &config.camera,
+    ));
+}
+
+/// Rotates with the left mouse button, pans with the right or middle one,
+/// zooms with the wheel and applies the focus hotkeys.
+///
+/// Focus cycles through the scenario bodies in file order, then back to no
+/// focus, which leaves the camera orbiting the point it was looking at.
+/// Focus distances follow the radius the body is drawn at.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_camera_input(
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<CameraHotkeys>,
+    settings: Option<Res<UserSettings>>,
+    config: Res<GameplayConfig>,
+    scenario: Option<Res<ActiveScenario>>,
+    bodies: Query<(Entity, &Name, &Radius), With<CelestialBody>>,
+    scale: Res<ViewScale>,
+    mut camera: ResMut<OrbitCamera>,
+) {
+    let controls = settings
+        .map(|settings| settings.controls.clone())
+        .unwrap_or_default();
+    let camera_config = &config.camera;
+    // Settings files are loaded without the menu's validation.
+    let sensitivity = if controls.mouse_sensitivity.is_nan() {
+        ControlsSettings::default().mouse_sensitivity
+    } else {
+        controls.mouse_sensitivity.clamp(
+            ControlsSettings::MIN_MOUSE_SENSITIVITY,
+            ControlsSettings::MAX_MOUSE_SENSITIVITY,
+        )
+    };
+    let sensitivity = f64::from(sensitivity);
+    let delta = motion.delta.as_dvec2() * sensitivity;
+    let flip_y = if controls.invert_y { -1.0 } else { 1.0 };
+
+    if delta != DVec2::ZERO {
+        if mouse.pressed(MouseButton::Left) {
+            let speed = f64::from(camera_config.rotate_speed);
+            camera.rotate(-delta.x * speed, delta.y * speed * flip_y);
+        } else if mouse.any_pressed([MouseButton::Right, MouseButton::Middle]) {
+            let speed = f64::from(camera_config.pan_speed);
+            camera.pan(-delta.x * speed, delta.y * speed);
+        }
+    }
+
+    let lines = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / MouseScrollUnit::SCROLL_UNIT_CONVERSION_FACTOR,
+    };
+    if lines != 0.0 {
+        let steps = if controls.invert_wheel { -lines } else { lines };
+        camera.zoom(f64::from(steps), f64::from(camera_config.zoom_step));
+    }
+
+    let duration = f64::from(camera_config.fly_duration);
+    if hotkeys
+        .clear_focus
+        .is_some_and(|key| keyboard.just_pressed(key))
+    {
+        let distance = camera.distance;
+        camera.fly_to(None, distance, 0.0, duration);
+    }
+    if hotkeys
+        .cycle_focus
+        .is_some_and(|key| keyboard.just_pressed(key))
+        && let Some(scenario) = scenario
+    {
+        let order: Vec<(Entity, &Name, &Radius)> = scenario
+            .0
+            .bodies
+            .iter()
+            .filter_map(|spec| {
+                bodies
+                    .iter()
+                    .find(|(_, name, _)| name.as_str() == spec.name)
+            })
+            .collect();
+        let next = match camera.focus {
+            None => 0,
+            Some(focus) => order
+                .iter()
+                .position(|(entity, ..)| *entity == focus)
+                .map_or(0, |index| index + 1),
+        };
+        match order.get(next) {
+            Some(&(entity, name, radius)) => {
+                info!("[Camera] Focusing {}", name);
+                camera.fly_to(
+                    Some(entity),
+                    radius.0 * scale.inflation * camera_config.focus_radii,
+                    radius.0,
+                    duration,
+                );
+            }
+            None => {
+                let distance = camera.distance;
+                camera.fly_to(None, distance, 0.0, duration);
+            }
+        }
+    }
+}
+
+/// Follows the focused body, advances fly-to transitions in real time and
+/// moves the camera. A focus that no longer exists (e.g. after a merge) is
+/// dropped where it was last seen.
+///
+/// The floating origin is recentred on the orbited point, so the focus is
+/// always drawn close to render-space zero. The eye is remapped by the
+/// `ViewScale` like everything else, so the view frames the scaled scene,
+/// and kept outside the focus as inflated right now.
+pub fn update_orbit_camera(
+    mut orbit: ResMut<OrbitCamera>,
+    mut origin: ResMut<FloatingOrigin>,
+    scale: Res<ViewScale>,
+    time: Res<Time<Real>>,
+    bodies: Query<&WorldPosition, With<CelestialBody>>,
+    mut camera: Query<&mut Transform, With<Camera3d>>,
+) {
+    let focus_position = match orbit.focus {
+        Some(focus) => match bodies.get(focus) {
+            Ok(position) => Some(position.0),
+            Err(_) => {
+                let distance = orbit.distance;
+                orbit.fly_to(None, distance, 0.0, 0.0);
+                None
+            }
+        },
+        None => None,
+    };
+    orbit.set_inflation(scale.radius_scale());
+    orbit.update(focus_position, time.delta_secs_f64());
+    origin.0 = orbit.center();
+
+    if let Ok(mut camera) = camera.single_mut() {
+        *camera = orbit.transform(scale.to_render(&origin, orbit.eye()));
+    }
+}
@@ -0,0 +1,195 @@
+//! # Clock Systems
+//!
+//! Advances `SimClock` and maps its hotkeys.
+
+use bevy::prelude::*;
+
+use crate::astro::time::Calendar;
+use crate::astro::{CalendarDate, JulianDate, LeapSecondTable, TimeScale};
+use crate::config::GameplayConfig;
+use crate::resources::{ClockHotkeys, SimClock};
+
+/// Converts a UTC date string to TT seconds since J2000.0.
+fn epoch_from_utc_date(date: &str, leap_seconds: &LeapSecondTable) -> Option<f64> {
+    let date = date.parse::<CalendarDate>().ok()?;
+    let utc = JulianDate::from_calendar(&date, Calendar::Reform).ok()?;
+    Some(
+        leap_seconds
+            .convert(utc, TimeScale::Utc, TimeScale::Tt)
+            .j2000_seconds(),
+    )
+}
+
+/// Resets the clock to the configured start date and time scale.
+pub fn reset_sim_clock(
+    mut commands: Commands,
+    config: Res<GameplayConfig>,
+    leap_seconds: Res<LeapSecondTable>,
+) {
+    let epoch = epoch_from_utc_date(&config.clock.start_date, &leap_seconds).unwrap_or_default();
+    let mut clock = SimClock::new(epoch, config.clock.time_scale);
+    if config.clock.sync_to_system {
+        clock.set_synced(true);
+        clock.sync_to(leap_seconds.now_tt().j2000_seconds());
+    }
+    commands.insert_resource(clock);
+}
+
+/// Advances the clock by one fixed step. Runs before any system reading it.
+pub fn advance_sim_clock(
+    mut clock: ResMut<SimClock>,
+    time: Res<Time>,
+    leap_seconds: Res<LeapSecondTable>,
+) {
+    if clock.is_synced() {
+        clock.sync_to(leap_seconds.now_tt().j2000_seconds());
+    } else {
+        clock.tick(time.delta_secs_f64());
+    }
+}
+
+/// Applies clock hotkeys pressed this frame.
+pub fn handle_clock_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<ClockHotkeys>,
+    config: Res<GameplayConfig>,
+    leap_seconds: Res<LeapSecondTable>,
+    mut clock: ResMut<SimClock>,
+) {
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.just_pressed(key));
+
+    if pressed(hotkeys.toggle_pause) {
+        clock.toggle_pause();
+        info!(
+            "[Clock] {}",
+            if clock.is_paused() { "Paused" } else { "Resumed" }
+        );
+    }
+    if pressed(hotkeys.reverse) {
+        clock.toggle_reverse();
+        info!("[Clock] Reversed: {}", clock.is_reversed());
+    }
+    if pressed(hotkeys.step) {
+        clock.step_once();
+    }
+    if pressed(hotkeys.faster) {
+        let scale = clock.time_scale() * config.clock.scale_factor;
+        clock.set_time_scale(scale);
+        info!("[Clock] Time scale: {}x", clock.time_scale());
+    }
+    if pressed(hotkeys.slower) {
+        let scale = clock.time_scale() / config.clock.scale_factor;
+        clock.set_time_scale(scale);
+        info!("[Clock] Time scale: {}x", clock.time_scale());
+    }
+    if pressed(hotkeys.jump)
+        && let Some(epoch) = epoch_from_utc_date(&config.clock.jump_date, &leap_seconds)
+    {
+        clock.jump_to(epoch);
+        info!("[Clock] Jumped to {} UTC", config.clock.jump_date);
+    }
+    if pressed(hotkeys.sync) {
+        let synced = !clock.is_synced();
+        clock.set_synced(synced);
+        info!("[Clock] Synced to system clock: {}", synced);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hotkeys_drive_the_clock() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let config = GameplayConfig::default();
+        app.insert_resource(ClockHotkeys::from(&config.clock.bindings));
+        app.insert_resource(config);
+        app.init_resource::<LeapSecondTable>();
+        app.insert_resource(SimClock::new(123.0, 10.0));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.add_systems(Update, handle_clock_hotkeys);
+
+        let press = |app: &mut App, key: KeyCode| {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.reset_all();
+            input.press(key);
+            app.update();
+        };
+
+        press(&mut app, KeyCode::Equal);
+        press(&mut app, KeyCode::Space);
+        press(&mut app, KeyCode::KeyR);
+        press(&mut app, KeyCode::Home);
+
+        let clock = app.world().resource::<SimClock>();
+        assert_eq!(clock.time_scale(), 100.0);
+        assert!(clock.is_paused());
+        assert!(clock.is_reversed());
+        // The empty table puts UTC on TAI, so J2000 UTC is 32.184 s TT early.
+        assert!((clock.epoch - 32.184).abs() < 1e-3);
+    }
+
+    #[test]
+    fn parses_dates_relative_to_j2000() {
+        use crate::astro::time::TT_MINUS_TAI;
+        use crate::constants::SECONDS_PER_DAY;
+
+        // The empty table puts UTC on TAI, a fixed offset from TT.
+        let table = LeapSecondTable::default();
+        let epoch = |date| epoch_from_utc_date(date, &table).map(|tt| tt - TT_MINUS_TAI);
+        let close = |date, expected: f64| (epoch(date).unwrap() - expected).abs() < 1e-3;
+
+        assert!(close("2000-01-01T12:00:00", 0.0));
+        assert!(close("2000-01-01", -SECONDS_PER_DAY / 2.0));
+        assert!(close("2000-03-01T12:00", 60.0 * SECONDS_PER_DAY));
+        assert!(close("1999-12-31T12:00:00", -SECONDS_PER_DAY));
+        assert_eq!(epoch("2000-13-01"), None);
+        assert_eq!(epoch("yesterday"), None);
+    }
+
+    #[test]
+    fn engaging_sync_jumps_without_stepping_bodies() {
+        use bevy::math::DVec3;
+
+        use crate::components::{Acceleration, Mass, Velocity, WorldPosition};
+        use crate::systems::physics::step_bodies;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let config = GameplayConfig::default();
+        app.insert_resource(ClockHotkeys::from(&config.clock.bindings));
+        app.insert_resource(config);
+        app.init_resource::<LeapSecondTable>();
+        app.insert_resource(SimClock::new(0.0, 1.0));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.add_systems(
+            Update,
+            (handle_clock_hotkeys, advance_sim_clock, step_bodies).chain(),
+        );
+        let body = app
+            .world_mut()
+            .spawn((
+                WorldPosition(DVec3::ZERO),
+                Velocity(DVec3::new(1.0e3, 0.0, 0.0)),
+                Acceleration::default(),
+                Mass(1.0),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyN);
+        app.update();
+
+        let clock = app.world().resource::<SimClock>();
+        assert!(clock.is_synced());
+        assert!(clock.epoch > 8.0e8);
+        assert_eq!(clock.delta(), 0.0);
+        assert_eq!(
+            app.world().get::<WorldPosition>(body).unwrap().0,
+            DVec3::ZERO
+        );
+    }
+}
@@ -2,20 +2,30 @@
 //!
 //! Core gameplay logic systems that run during the InGame state.
 
+use std::f32::consts::TAU;
+
 use bevy::prelude::*;
 
-use crate::components::Rotates;
+use crate::components::{Rotates, RotationPeriod};
 
 use crate::config::GameplayConfig;
+use crate::resources::SimClock;
 
 /// Rotates entities marked with the `Rotates` component.
+///
+/// Bodies with a `RotationPeriod` spin about their local +Z pole at that rate;
+/// others use `PlanetConfig::rotation_speed` about +Y. Both advance by the
+/// `SimClock` delta, so rotation pauses, reverses and scales with the clock.
 pub fn rotate_planet(
-    mut query: Query<&mut Transform, With<Rotates>>,
-    time: Res<Time>,
+    mut query: Query<(&mut Transform, Option<&RotationPeriod>), With<Rotates>>,
+    clock: Res<SimClock>,
     config: Res<GameplayConfig>,
 ) {
-    let delta = time.delta_secs() * config.planets.rotation_speed;
-    for mut transform in &mut query {
-        transform.rotate_y(delta);
+    let dt = clock.delta() as f32;
+    for (mut transform, period) in &mut query {
+        match period {
+            Some(period) => transform.rotate_local_z(TAU / period.0 as f32 * dt),
+            None => transform.rotate_y(dt * config.planets.rotation_speed),
+        }
     }
 }
@@ -0,0 +1,486 @@
+//! # HUD Systems
+//!
+//! Body selection by clicking and the info panel describing the selected
+//! body. Picking uses Bevy's mesh picking, falling back to the body centres
+//! on screen for bodies too small or distant to hit. Driven by
+//! `GameplayConfig.hud`.
+
+use bevy::math::DVec3;
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use fluent_bundle::FluentArgs;
+
+use crate::components::{
+    CelestialBody, InfoClass, InfoField, InfoPanel, InfoTitle, InfoValue, ManeuverPanel, Mass,
+    OrbitParent, Radius, ScaleLabel, Surface, Velocity, WorldPosition,
+};
+use crate::config::GameplayConfig;
+use crate::constants::{ASTRONOMICAL_UNIT, SECONDS_PER_DAY};
+use crate::resources::{
+    BodyInfo, Hud, HudHotkeys, Kinematics, ObserverView, OrbitCamera, Selection, ViewScale,
+};
+use localization::{Localization, LocalizedText};
+use theme::Theme;
+
+/// Farthest the cursor may move between press and release for the press
+/// to count as a click rather than a camera drag (px).
+const CLICK_SLOP: f32 = 4.0;
+
+/// Distances from this far on are shown in astronomical units (m).
+const AU_DISPLAY_THRESHOLD: f64 = 0.01 * ASTRONOMICAL_UNIT;
+
+/// Spawns the info panel, hidden until a body is selected.
+pub fn spawn_info_panel(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    localization: Res<Localization>,
+    config: Res<GameplayConfig>,
+) {
+    let text = |size: f32, bold: bool, color: Color| {
+        (
+            TextFont {
+                font: if bold {
+                    theme.fonts.bold.clone()
+                } else {
+                    theme.fonts.main.clone()
+                },
+                font_size: size,
+                ..default()
+            },
+            TextColor(color),
+        )
+    };
+
+    let panel = commands
+        .spawn((
+            Node {
+                width: Val::Px(config.hud.panel_width),
+                position_type: PositionType::Absolute,
+                top: theme.sizes.margin,
+                right: theme.sizes.margin,
+                padding: UiRect::all(theme.sizes.padding),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BackgroundColor(theme.colors.surface),
+            Visibility::Hidden,
+            InfoPanel,
+        ))
+        .id();
+
+    commands.spawn((
+        Text::default(),
+        text(theme.sizes.font_h2, true, theme.colors.accent),
+        InfoTitle,
+        ChildOf(panel),
+    ));
+    commands.spawn((
+        Text::default(),
+        text(theme.sizes.font_body, false, theme.colors.text_secondary),
+        Node {
+            display: Display::None,
+            ..default()
+        },
+        InfoClass,
+        ChildOf(panel),
+    ));
+    for field in InfoField::ALL {
+        let row = commands
+            .spawn((
+                Node {
+                    justify_content: JustifyContent::SpaceBetween,
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                ChildOf(panel),
+            ))
+            .id();
+        commands.spawn((
+            Text::new(localization.t(field.key())),
+            text(theme.sizes.font_body, false, theme.colors.text_secondary),
+            LocalizedText(field.key().to_string()),
+            ChildOf(row),
+        ));
+        commands.spawn((
+            Text::default(),
+            text(theme.sizes.font_body, true, theme.colors.text_primary),
+            InfoValue(field),
+            ChildOf(row),
+        ));
+    }
+}
+
+/// Spawns the view-scale label in the bottom-left corner.
+pub fn spawn_scale_label(mut commands: Commands, theme: Res<Theme>) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font: theme.fonts.main.clone(),
+            font_size: theme.sizes.font_body,
+            ..default()
+        },
+        TextColor(theme.colors.text_secondary),
+        Node {
+            position_type: PositionType::Absolute,
+            left: theme.sizes.margin,
+            bottom: theme.sizes.margin,
+            padding: UiRect::all(theme.sizes.padding),
+            ..default()
+        },
+        BackgroundColor(theme.colors.surface),
+        Pickable::IGNORE,
+        ScaleLabel,
+    ));
+}
+
+/// Removes the info panel, scale label and maneuver panel when leaving the
+/// game.
+#[allow(clippy::type_complexity)]
+pub fn despawn_hud(
+    mut commands: Commands,
+    panels: Query<Entity, Or<(With<InfoPanel>, With<ScaleLabel>, With<ManeuverPanel>)>>,
+    mut selection: ResMut<Selection>,
+) {
+    for entity in &panels {
+        commands.entity(entity).despawn();
+    }
+    selection.0 = None;
+}
+
+/// Shows or hides the HUD.
+pub fn handle_hud_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<HudHotkeys>,
+    mut hud: ResMut<Hud>,
+) {
+    if hotkeys
+        .toggle_hud
+        .is_some_and(|key| keyboard.just_pressed(key))
+    {
+        hud.visible = !hud.visible;
+    }
+}
+
+/// Selects the body under the cursor on a left click, or clears the
+/// selection when the click lands on empty space. Presses that turn into
+/// camera drags and clicks on UI are ignored.
+///
+/// The nearest mesh hit wins; terrain chunks select the body they belong
+/// to. Without a hit, the body whose centre is closest to the cursor within
+/// `HudConfig::pick_radius` is taken.
+#[allow(clippy::too_many_arguments)]
+pub fn pick_body(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    hover: Option<Res<HoverMap>>,
+    bodies: Query<(Entity, &GlobalTransform), With<CelestialBody>>,
+    ancestors: Query<&ChildOf>,
+    ui: Query<(), With<Node>>,
+    config: Res<GameplayConfig>,
+    mut press: Local<Option<Vec2>>,
+    mut selection: ResMut<Selection>,
+) {
+    let Some(cursor) = window.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    if mouse.just_pressed(MouseButton::Left) {
+        *press = Some(cursor);
+    }
+    if !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(start) = press.take() else {
+        return;
+    };
+    if start.distance(cursor) > CLICK_SLOP {
+        return;
+    }
+
+    let hits = hover
+        .as_ref()
+        .and_then(|hover| hover.get(&PointerId::Mouse));
+    if let Some(hits) = hits {
+        if hits.keys().any(|&entity| ui.contains(entity)) {
+            return;
+        }
+        let hit = hits
+            .iter()
+            .filter_map(|(&entity, hit)| {
+                std::iter::once(entity)
+                    .chain(ancestors.iter_ancestors(entity))
+                    .find(|&entity| bodies.contains(entity))
+                    .map(|body| (body, hit.depth))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((body, _)) = hit {
+            selection.0 = Some(body);
+            return;
+        }
+    }
+
+    let Ok((camera, camera_transform)) = camera.single() else {
+        return;
+    };
+    let eye = camera_transform.translation();
+    let on_screen = bodies.iter().filter_map(|(entity, transform)| {
+        let position = transform.translation();
+        let point = camera.world_to_viewport(camera_transform, position).ok()?;
+        Some((entity, point, eye.distance(position)))
+    });
+    selection.0 = nearest_on_screen(cursor, on_screen, config.hud.pick_radius);
+}
+
+/// Candidate closest to `cursor` within `radius` pixels, preferring the
+/// one nearer the camera when two are equally close on screen.
+pub fn nearest_on_screen(
+    cursor: Vec2,
+    candidates: impl Iterator<Item = (Entity, Vec2, f32)>,
+    radius: f32,
+) -> Option<Entity> {
+    candidates
+        .map(|(entity, point, depth)| (entity, point.distance(cursor), depth))
+        .filter(|(_, distance, _)| *distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+        .map(|(entity, ..)| entity)
+}
+
+/// Shows the panel while the HUD is visible and a body is selected, and
+/// refreshes its figures every frame.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_info_panel(
+    hud: Res<Hud>,
+    mut selection: ResMut<Selection>,
+    localization: Res<Localization>,
+    config: Res<GameplayConfig>,
+    orbit: Option<Res<OrbitCamera>>,
+    observer: Option<Res<ObserverView>>,
+    bodies: Query<(
+        &Name,
+        &WorldPosition,
+        &Velocity,
+        &Mass,
+        &Radius,
+        Option<&OrbitParent>,
+        Option<&Surface>,
+    )>,
+    mut panel: Query<&mut Visibility, With<InfoPanel>>,
+    mut title: Query<&mut Text, (With<InfoTitle>, Without<InfoClass>, Without<InfoValue>)>,
+    mut class: Query<(&mut Text, &mut Node), (With<InfoClass>, Without<InfoValue>)>,
+    mut values: Query<(&mut Text, &InfoValue), (Without<InfoTitle>, Without<InfoClass>)>,
+) {
+    if selection.0.is_some_and(|entity| !bodies.contains(entity)) {
+        selection.0 = None;
+    }
+    let Ok(mut visibility) = panel.single_mut() else {
+        return;
+    };
+    let shown = hud.visible && selection.0.is_some();
+    visibility.set_if_neq(if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    let Some(Ok((name, position, velocity, mass, radius, parent, surface))) = selection
+        .0
+        .filter(|_| shown)
+        .map(|entity| bodies.get(entity))
+    else {
+        return;
+    };
+
+    let kinematics = |position: &WorldPosition, velocity: &Velocity, mass: &Mass| Kinematics {
+        position: position.0,
+        velocity: velocity.0,
+        mass: mass.0,
+    };
+    let parent = parent
+        .and_then(|parent| bodies.get(parent.0).ok())
+        .map(|(_, position, velocity, mass, ..)| kinematics(position, velocity, mass));
+    let eye = match (observer, orbit) {
+        (Some(observer), _) => observer.eye,
+        (None, Some(orbit)) => orbit.eye(),
+        (None, None) => DVec3::ZERO,
+    };
+    let info = BodyInfo::measure(
+        kinematics(position, velocity, mass),
+        radius.0,
+        parent,
+        eye,
+        config.physics.gravitational_constant,
+    );
+
+    if let Ok(mut title) = title.single_mut() {
+        let text = body_name(&localization, name.as_str());
+        if title.0 != text {
+            title.0 = text;
+        }
+    }
+    if let Ok((mut text, mut node)) = class.single_mut() {
+        match surface {
+            Some(surface) => {
+                text.0 = localization.t(surface.class.key());
+                node.display = Display::Flex;
+            }
+            None => node.display = Display::None,
+        }
+    }
+    for (mut text, value) in &mut values {
+        text.0 = field_text(&localization, &info, value.0);
+    }
+}
+
+/// Shows the view-scale label while the HUD is visible and keeps it on the
+/// mode and radius inflation being shown or blended towards.
+pub fn update_scale_label(
+    hud: Res<Hud>,
+    scale: Res<ViewScale>,
+    localization: Res<Localization>,
+    mut label: Query<(&mut Text, &mut Visibility), With<ScaleLabel>>,
+) {
+    let Ok((mut text, mut visibility)) = label.single_mut() else {
+        return;
+    };
+    visibility.set_if_neq(if hud.visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    if !hud.visible {
+        return;
+    }
+    let label = scale_text(&localization, &scale);
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+/// Scale label for `scale`: the distance mapping with the distance it
+/// stays true to, and the radius inflation when radii are enlarged.
+pub fn scale_text(localization: &Localization, scale: &ViewScale) -> String {
+    let mut args = FluentArgs::new();
+    let (unit, value) = distance_quantity(scale.reference_distance);
+    let mut unit_args = FluentArgs::new();
+    unit_args.set("value", value);
+    args.set("distance", localization.t_with_args(unit, Some(&unit_args)));
+    let mut text = localization.t_with_args(scale.mode.key(), Some(&args));
+    if scale.inflation != 1.0 {
+        args.set("factor", format!("{}", scale.inflation));
+        text.push_str(" · ");
+        text.push_str(&localization.t_with_args("scale-radii", Some(&args)));
+    }
+    text
+}
+
+/// Localized name of the body called `name` in its scenario, or `name`
+/// itself when no translation exists.
+pub fn body_name(localization: &Localization, name: &str) -> String {
+    let key = format!("body-{}", name.to_lowercase().replace(' ', "-"));
+    if localization.has(&key) {
+        localization.t(&key)
+    } else {
+        name.to_string()
+    }
+}
+
+fn field_text(localization: &Localization, info: &BodyInfo, field: InfoField) -> String {
+    let quantity = match field {
+        InfoField::Mass => Some(("unit-kg", format!("{:.4e}", info.mass))),
+        InfoField::Radius => Some(distance_quantity(info.radius)),
+        InfoField::CameraDistance => Some(distance_quantity(info.camera_distance)),
+        InfoField::ParentDistance => info.parent_distance.map(distance_quantity),
+        InfoField::Velocity => Some(("unit-km-s", format!("{:.3}", info.speed / 1000.0))),
+        InfoField::Period => info.period.map(period_quantity),
+        InfoField::Eccentricity => {
+            return info
+                .eccentricity
+                .map_or_else(|| localization.t("info-none"), |e| format!("{e:.4}"));
+        }
+    };
+    match quantity {
+        Some((unit, value)) => {
+            let mut args = FluentArgs::new();
+            args.set("value", value);
+            localization.t_with_args(unit, Some(&args))
+        }
+        None => localization.t("info-none"),
+    }
+}
+
+/// Unit key and value of a distance in metres: kilometres, or
+/// astronomical units from a hundredth of one.
+pub fn distance_quantity(metres: f64) -> (&'static str, String) {
+    if metres >= AU_DISPLAY_THRESHOLD {
+        ("unit-au", format!("{:.4}", metres / ASTRONOMICAL_UNIT))
+    } else if metres >= 1.0e5 {
+        ("unit-km", format!("{:.0}", metres / 1000.0))
+    } else {
+        ("unit-km", format!("{:.2}", metres / 1000.0))
+    }
+}
+
+/// Unit key and value of a period in seconds: hours under two days, days
+/// under two years, years beyond.
+pub fn period_quantity(seconds: f64) -> (&'static str, String) {
+    let days = seconds / SECONDS_PER_DAY;
+    if days < 2.0 {
+        ("unit-hours", format!("{:.2}", seconds / 3600.0))
+    } else if days < 2.0 * 365.25 {
+        ("unit-days", format!("{days:.2}"))
+    } else {
+        ("unit-years", format!("{:.2}", days / 365.25))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_fallback_takes_the_closest_centre_in_range() {
+        let [a, b, c] = [1, 2, 3].map(|i| Entity::from_raw_u32(i).unwrap());
+        let cursor = Vec2::new(100.0, 100.0);
+        let candidates = [
+            (a, Vec2::new(108.0, 100.0), 1.0e9),
+            (b, Vec2::new(103.0, 104.0), 5.0e9),
+            (c, Vec2::new(100.0, 130.0), 1.0),
+        ];
+        assert_eq!(
+            nearest_on_screen(cursor, candidates.into_iter(), 12.0),
+            Some(b)
+        );
+        assert_eq!(nearest_on_screen(cursor, candidates.into_iter(), 4.0), None);
+
+        // Overlapping centres resolve to the body nearer the camera.
+        let overlapping = [(a, cursor, 2.0e9), (b, cursor, 1.0e9)];
+        assert_eq!(
+            nearest_on_screen(cursor, overlapping.into_iter(), 1.0),
+            Some(b)
+        );
+    }
+
+    #[test]
+    fn quantities_pick_readable_units() {
+        assert_eq!(distance_quantity(1.737e6), ("unit-km", "1737".to_string()));
+        assert_eq!(distance_quantity(5.0e3), ("unit-km", "5.00".to_string()));
+        assert_eq!(
+            distance_quantity(ASTRONOMICAL_UNIT * 5.2),
+            ("unit-au", "5.2000".to_string())
+        );
+        assert_eq!(
+            period_quantity(3600.0 * 10.0),
+            ("unit-hours", "10.00".to_string())
+        );
+        assert_eq!(
+            period_quantity(27.32 * SECONDS_PER_DAY),
+            ("unit-days", "27.32".to_string())
+        );
+        assert_eq!(
+            period_quantity(11.86 * 365.25 * SECONDS_PER_DAY),
+            ("unit-years", "11.86".to_string())
+        );
+    }
+}
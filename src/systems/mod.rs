@@ -3,6 +3,21 @@
 //! ECS system functions for the game layer.
 //! Systems are plain functions that process entities via queries.
 
+pub mod camera;
 pub mod cleanup;
+pub mod clock;
 pub mod gameplay;
+pub mod hud;
+pub mod moon;
+pub mod observer;
+pub mod orbit;
+pub mod orbit_display;
+pub mod origin;
+pub mod physics;
+pub mod scale;
+pub mod scenario;
 pub mod setup;
+pub mod sky;
+pub mod spacecraft;
+pub mod stars;
+pub mod terrain;
@@ -0,0 +1,36 @@
+//! # Moon Systems
+//!
+//! Keeps the `MoonPhase` resource in step with the simulation clock.
+
+use bevy::prelude::*;
+
+use crate::resources::{MoonPhase, SimClock};
+
+/// Recomputes the Moon's phase and libration for the current epoch.
+pub fn update_moon_phase(clock: Res<SimClock>, mut moon: ResMut<MoonPhase>) {
+    *moon = MoonPhase::at(clock.julian_date());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::{JulianDate, PhaseName};
+
+    #[test]
+    fn moon_phase_follows_the_clock() {
+        // Full moon of 2000-01-21 04:41 TT.
+        let full = JulianDate(2_451_564.695_185);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(SimClock::new(full.j2000_seconds(), 1.0));
+        app.init_resource::<MoonPhase>();
+        app.add_systems(Update, update_moon_phase);
+
+        app.world_mut().run_schedule(Update);
+
+        let moon = app.world().resource::<MoonPhase>();
+        assert_eq!(moon.name(), PhaseName::Full);
+        assert!(moon.illumination() > 0.999);
+        assert_eq!(*moon, MoonPhase::at(full));
+    }
+}
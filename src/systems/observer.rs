@@ -0,0 +1,455 @@
+//! # Observer View Systems
+//!
+//! Stands the camera on a body's surface: the horizontal frame follows
+//! sidereal time, a ground disc hides everything below the horizon and an
+//! alt-az grid with cardinal points can be toggled.
+
+use std::f64::consts::TAU;
+
+use bevy::math::{DMat3, DVec3};
+use bevy::prelude::*;
+use fluent_bundle::FluentArgs;
+
+use crate::astro::frames::Frame;
+use crate::astro::{Horizontal, JulianDate, LeapSecondTable, Observer, TimeScale};
+use crate::components::{
+    CelestialBody, GameWorldRoot, HorizonLabel, HorizonLabels, ObserverGround, Radius,
+    WorldPosition,
+};
+use crate::config::GameplayConfig;
+use crate::resources::{
+    FloatingOrigin, ObserverGizmos, ObserverHotkeys, ObserverView, OrbitalCameraPose, SimClock,
+    ViewScale,
+};
+use crate::states::ViewMode;
+use localization::{Localization, LocalizedText};
+use theme::Theme;
+
+/// Altitudes of the grid's parallels (degrees).
+const GRID_ALTITUDES: [f64; 6] = [0.0, 15.0, 30.0, 45.0, 60.0, 75.0];
+
+/// Azimuth spacing of the grid's meridians (degrees).
+const GRID_AZIMUTH_STEP: f64 = 30.0;
+
+/// Line segments per grid circle.
+const GRID_RESOLUTION: usize = 96;
+
+/// The grid and labels sit just inside the star sphere.
+const GRID_RADIUS: f32 = 0.98;
+
+/// Cardinal points as (Fluent key, azimuth in degrees).
+const CARDINAL_POINTS: [(&str, f64); 4] = [
+    ("observer-north", 0.0),
+    ("observer-east", 90.0),
+    ("observer-south", 180.0),
+    ("observer-west", 270.0),
+];
+
+/// Altitude the cardinal labels float at, clear of the ground (degrees).
+const CARDINAL_ALTITUDE: f64 = 2.0;
+
+/// East, north and zenith axes of `observer` in the scene's ecliptic frame
+/// at the UT `date`.
+pub fn horizon_basis(observer: Observer, date: JulianDate) -> DMat3 {
+    Frame::EclipticJ2000.from_icrs() * Frame::Horizontal { observer, date }.to_icrs()
+}
+
+/// Switches between the orbital and observer views.
+pub fn toggle_view_mode(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<ObserverHotkeys>,
+    mode: Res<State<ViewMode>>,
+    mut next_mode: ResMut<NextState<ViewMode>>,
+) {
+    if hotkeys
+        .toggle_view
+        .is_some_and(|key| keyboard.just_pressed(key))
+    {
+        next_mode.set(match mode.get() {
+            ViewMode::Orbital => ViewMode::Observer,
+            ViewMode::Observer => ViewMode::Orbital,
+        });
+    }
+}
+
+/// Saves the orbital camera, suspends the view scale and spawns the ground
+/// and cardinal labels.
+#[allow(clippy::too_many_arguments)]
+pub fn enter_observer_view(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<GameplayConfig>,
+    localization: Res<Localization>,
+    theme: Res<Theme>,
+    bodies: Query<&Name, With<CelestialBody>>,
+    camera: Query<&Transform, With<Camera3d>>,
+    root: Query<Entity, With<GameWorldRoot>>,
+    mut scale: ResMut<ViewScale>,
+    mut next_mode: ResMut<NextState<ViewMode>>,
+) {
+    let observer = &config.observer;
+    let mut args = FluentArgs::new();
+    args.set("body", observer.body.clone());
+    if !bodies.iter().any(|name| name.as_str() == observer.body) {
+        warn!(
+            "{}",
+            localization.t_with_args("log-observer-missing-body", Some(&args))
+        );
+        next_mode.set(ViewMode::Orbital);
+        return;
+    }
+    args.set("latitude", observer.latitude);
+    args.set("longitude", observer.longitude);
+    info!(
+        "{}",
+        localization.t_with_args("log-observer-enter", Some(&args))
+    );
+
+    if let Ok(camera) = camera.single() {
+        commands.insert_resource(OrbitalCameraPose(*camera));
+    }
+    commands.insert_resource(ObserverView::from(observer));
+    // The eye stands on the true surface, inside any inflated body.
+    scale.suspended = true;
+
+    if let Ok(root) = root.single() {
+        let [r, g, b, a] = observer.ground_color;
+        commands.spawn((
+            Mesh3d(meshes.add(Circle::new(observer.ground_radius))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: LinearRgba::new(r, g, b, a).into(),
+                unlit: true,
+                ..default()
+            })),
+            Transform::default(),
+            ObserverGround,
+            ChildOf(root),
+        ));
+    }
+
+    let labels = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Pickable::IGNORE,
+            if observer.show_grid {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+            HorizonLabels,
+        ))
+        .id();
+    for (key, azimuth) in CARDINAL_POINTS {
+        let direction = Horizontal {
+            azimuth: azimuth.to_radians(),
+            altitude: CARDINAL_ALTITUDE.to_radians(),
+        }
+        .to_vector();
+        commands.spawn((
+            Text::new(localization.t(key)),
+            TextFont {
+                font: theme.fonts.bold.clone(),
+                font_size: theme.sizes.font_body,
+                ..default()
+            },
+            TextColor(theme.colors.accent),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+            LocalizedText(key.to_string()),
+            HorizonLabel {
+                direction: direction.as_vec3(),
+            },
+            ChildOf(labels),
+        ));
+    }
+}
+
+/// Restores the orbital camera and view scale and removes the observer
+/// scenery.
+#[allow(clippy::type_complexity)]
+pub fn exit_observer_view(
+    mut commands: Commands,
+    pose: Option<Res<OrbitalCameraPose>>,
+    mut camera: Query<&mut Transform, With<Camera3d>>,
+    scenery: Query<Entity, Or<(With<ObserverGround>, With<HorizonLabels>)>>,
+    mut scale: ResMut<ViewScale>,
+) {
+    scale.suspended = false;
+    if let (Some(pose), Ok(mut camera)) = (pose, camera.single_mut()) {
+        *camera = pose.0;
+    }
+    for entity in &scenery {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<OrbitalCameraPose>();
+    commands.remove_resource::<ObserverView>();
+}
+
+/// Applies look and grid hotkeys in the observer view.
+pub fn handle_observer_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<ObserverHotkeys>,
+    config: Res<GameplayConfig>,
+    time: Res<Time>,
+    mut view: ResMut<ObserverView>,
+    mut labels: Query<&mut Visibility, With<HorizonLabels>>,
+) {
+    let held = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.pressed(key));
+    let axis = |negative, positive| f64::from(i8::from(held(positive)) - i8::from(held(negative)));
+
+    let step = f64::from(config.observer.look_speed) * time.delta_secs_f64();
+    let azimuth = axis(hotkeys.look_left, hotkeys.look_right) * step;
+    let altitude = axis(hotkeys.look_down, hotkeys.look_up) * step;
+    if azimuth != 0.0 || altitude != 0.0 {
+        view.turn(azimuth, altitude);
+    }
+
+    if hotkeys
+        .toggle_grid
+        .is_some_and(|key| keyboard.just_pressed(key))
+    {
+        view.grid = !view.grid;
+        for mut visibility in &mut labels {
+            *visibility = if view.grid {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Render position of the horizontal `direction` on the sky sphere around
+/// the eye.
+fn sky_point(view: &ObserverView, origin: &FloatingOrigin, direction: DVec3, radius: f64) -> Vec3 {
+    origin.to_render(view.eye + view.to_scene(direction) * radius)
+}
+
+/// Places the camera and ground at the observer for the current clock epoch.
+///
+/// The floating origin is recentred on the eye. Runs before the celestial
+/// sphere is centred, so the sky is drawn around the new eye position in
+/// the same frame.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_observer_camera(
+    mut view: ResMut<ObserverView>,
+    clock: Res<SimClock>,
+    leap_seconds: Res<LeapSecondTable>,
+    config: Res<GameplayConfig>,
+    mut origin: ResMut<FloatingOrigin>,
+    bodies: Query<(&Name, &WorldPosition, &Radius), With<CelestialBody>>,
+    mut camera: Query<&mut Transform, (With<Camera3d>, Without<CelestialBody>)>,
+    mut ground: Query<
+        &mut Transform,
+        (
+            With<ObserverGround>,
+            Without<Camera3d>,
+            Without<CelestialBody>,
+        ),
+    >,
+) {
+    let Some((_, body, radius)) = bodies
+        .iter()
+        .find(|(name, _, _)| name.as_str() == view.body)
+    else {
+        return;
+    };
+
+    let ut = leap_seconds.convert(
+        JulianDate::from_j2000_seconds(clock.epoch),
+        TimeScale::Tt,
+        TimeScale::Utc,
+    );
+    view.basis = horizon_basis(view.observer, ut);
+    let zenith = view.zenith();
+    view.eye = body.0 + zenith * (radius.0 + config.observer.eye_height);
+    origin.0 = view.eye;
+
+    if let Ok(mut camera) = camera.single_mut() {
+        *camera =
+            Transform::default().looking_to(view.look_direction().as_vec3(), zenith.as_vec3());
+    }
+    if let Ok(mut ground) = ground.single_mut() {
+        ground.translation = origin.to_render(view.eye - zenith * config.observer.eye_height);
+        ground.rotation = Quat::from_mat3(&view.basis.as_mat3());
+    }
+}
+
+/// Draws the alt-az grid around the eye.
+pub fn draw_alt_az_grid(
+    mut gizmos: Gizmos<ObserverGizmos>,
+    view: Res<ObserverView>,
+    origin: Res<FloatingOrigin>,
+    config: Res<GameplayConfig>,
+) {
+    if !view.grid {
+        return;
+    }
+    let radius = f64::from(config.stars.sphere_radius * GRID_RADIUS);
+    let [r, g, b, a] = config.observer.grid_color;
+    let color = LinearRgba::new(r, g, b, a);
+    let place = |azimuth: f64, altitude: f64| {
+        sky_point(
+            &view,
+            &origin,
+            Horizontal { azimuth, altitude }.to_vector(),
+            radius,
+        )
+    };
+
+    for altitude in GRID_ALTITUDES.map(f64::to_radians) {
+        gizmos.linestrip(
+            (0..=GRID_RESOLUTION).map(|i| place(TAU * i as f64 / GRID_RESOLUTION as f64, altitude)),
+            color,
+        );
+    }
+    let meridians = (360.0 / GRID_AZIMUTH_STEP) as usize;
+    for m in 0..meridians {
+        let azimuth = (m as f64 * GRID_AZIMUTH_STEP).to_radians();
+        gizmos.linestrip(
+            (0..=GRID_RESOLUTION / 4)
+                .map(|i| place(azimuth, TAU / 4.0 * i as f64 / (GRID_RESOLUTION / 4) as f64)),
+            color,
+        );
+    }
+}
+
+/// Pins the cardinal-point labels to the horizon on screen.
+pub fn position_horizon_labels(
+    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    view: Res<ObserverView>,
+    origin: Res<FloatingOrigin>,
+    config: Res<GameplayConfig>,
+    mut labels: Query<(&HorizonLabel, &mut Node, &mut Visibility)>,
+) {
+    let Ok((camera, camera_transform)) = camera.single() else {
+        return;
+    };
+    let radius = f64::from(config.stars.sphere_radius * GRID_RADIUS);
+
+    for (label, mut node, mut visibility) in &mut labels {
+        let position = sky_point(&view, &origin, label.direction.as_dvec3(), radius);
+        match camera.world_to_viewport(camera_transform, position) {
+            Ok(screen) => {
+                node.left = Val::Px(screen.x);
+                node.top = Val::Px(screen.y);
+                *visibility = Visibility::Inherited;
+            }
+            Err(_) => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::time::J2000;
+    use bevy::state::app::StatesPlugin;
+    use launcher::states::AppState;
+
+    #[test]
+    fn horizon_zenith_tracks_latitude_and_sidereal_time() {
+        let date = JulianDate(J2000.0 + 1234.5);
+        // At the north pole the zenith is the celestial pole, whatever the time.
+        let pole = horizon_basis(Observer::new(90f64.to_radians(), 0.0), date).z_axis;
+        let expected = Frame::EclipticJ2000.from_icrs()
+            * Frame::EquatorialOfDate(date).to_icrs()
+            * bevy::math::DVec3::Z;
+        assert!((pole - expected).length() < 1e-9);
+
+        // On the equator the zenith turns once per sidereal day.
+        let equator = Observer::new(0.0, 0.0);
+        let sidereal_day = 0.997_269_566;
+        let a = horizon_basis(equator, date).z_axis;
+        let b = horizon_basis(equator, JulianDate(date.0 + sidereal_day)).z_axis;
+        let c = horizon_basis(equator, JulianDate(date.0 + sidereal_day / 2.0)).z_axis;
+        assert!(a.angle_between(b) < 1e-5);
+        assert!((a.angle_between(c) - std::f64::consts::PI).abs() < 1e-5);
+    }
+
+    #[test]
+    fn horizon_points_are_placed_around_the_eye() {
+        let config = GameplayConfig::default();
+        let mut app = App::new();
+        app.insert_resource(ObserverView::from(&config.observer));
+        app.init_resource::<SimClock>();
+        app.init_resource::<LeapSecondTable>();
+        app.init_resource::<FloatingOrigin>();
+        app.insert_resource(config.clone());
+        app.world_mut().spawn((
+            Name::new(config.observer.body.clone()),
+            CelestialBody,
+            WorldPosition(DVec3::new(1.496e11, 2.0e10, 0.0)),
+            Radius(6.371e6),
+        ));
+        app.add_systems(Update, update_observer_camera);
+        app.update();
+
+        let world = app.world();
+        let view = world.resource::<ObserverView>();
+        let origin = world.resource::<FloatingOrigin>();
+        let radius = f64::from(config.stars.sphere_radius * GRID_RADIUS);
+        for (_, azimuth) in CARDINAL_POINTS {
+            let direction = Horizontal {
+                azimuth: azimuth.to_radians(),
+                altitude: CARDINAL_ALTITUDE.to_radians(),
+            }
+            .to_vector();
+            // The camera sits at render zero, so the point's render
+            // translation is its direction from the eye.
+            let point = sky_point(view, origin, direction, radius).as_dvec3();
+            assert!(point.angle_between(view.to_scene(direction)) < 1e-6);
+            assert!((point.length() / radius - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn hotkey_toggles_view_mode() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin));
+        app.init_state::<AppState>();
+        app.add_sub_state::<ViewMode>();
+        app.insert_resource(ObserverHotkeys::from(
+            &GameplayConfig::default().observer.bindings,
+        ));
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.add_systems(Update, toggle_view_mode.run_if(in_state(AppState::InGame)));
+
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        app.update();
+        assert_eq!(
+            *app.world().resource::<State<ViewMode>>().get(),
+            ViewMode::Orbital
+        );
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyO);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+        assert_eq!(
+            *app.world().resource::<State<ViewMode>>().get(),
+            ViewMode::Observer
+        );
+
+        app.world_mut()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::MainMenu);
+        app.update();
+        assert!(app.world().get_resource::<State<ViewMode>>().is_none());
+    }
+}
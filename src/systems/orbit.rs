@@ -0,0 +1,277 @@
+//! # Orbit Systems
+//!
+//! Analytic ("on rails") propagation of bodies with `OrbitalElements`, an
+//! `EphemerisBody` theory or the lunar theory.
+
+use bevy::math::DVec3;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+
+use crate::astro::{Ephemeris, lunar};
+use crate::components::{
+    EphemerisBody, LunarBody, OnRails, OrbitalElements, Velocity, WorldPosition,
+};
+use crate::resources::SimClock;
+
+/// Guards against cycles in misconfigured primary chains.
+const MAX_PRIMARY_DEPTH: usize = 16;
+
+/// Places every ephemeris body at its VSOP87 position for the `SimClock`
+/// epoch, offset by its primary (the Sun) when it has one.
+///
+/// Runs before `propagate_on_rails` so moons on rails follow their planet.
+pub fn propagate_ephemeris(
+    mut bodies: Query<(&EphemerisBody, &OnRails, &mut WorldPosition, &mut Velocity)>,
+    primaries: Query<(&WorldPosition, &Velocity), Without<EphemerisBody>>,
+    ephemeris: Res<Ephemeris>,
+    clock: Res<SimClock>,
+) {
+    for (body, on_rails, mut world, mut velocity) in &mut bodies {
+        let Some((position, body_velocity)) = ephemeris.state(body.0, clock.epoch) else {
+            continue;
+        };
+        let (origin, drift) = on_rails
+            .primary
+            .and_then(|primary| primaries.get(primary).ok())
+            .map_or((DVec3::ZERO, DVec3::ZERO), |(primary, velocity)| {
+                (primary.0, velocity.0)
+            });
+        world.0 = origin + position;
+        velocity.0 = drift + body_velocity;
+    }
+}
+
+/// Places every lunar-theory body at the Moon's geocentric position for the
+/// `SimClock` epoch, offset by its primary (the Earth) when it has one.
+///
+/// Runs after `propagate_ephemeris` so the Moon follows an ephemeris Earth.
+pub fn propagate_lunar(
+    mut bodies: Query<(&OnRails, &mut WorldPosition, &mut Velocity), With<LunarBody>>,
+    primaries: Query<(&WorldPosition, &Velocity), Without<LunarBody>>,
+    clock: Res<SimClock>,
+) {
+    if bodies.is_empty() {
+        return;
+    }
+    let (position, moon_velocity) = lunar::state(clock.epoch);
+    for (on_rails, mut world, mut velocity) in &mut bodies {
+        let (origin, drift) = on_rails
+            .primary
+            .and_then(|primary| primaries.get(primary).ok())
+            .map_or((DVec3::ZERO, DVec3::ZERO), |(primary, velocity)| {
+                (primary.0, velocity.0)
+            });
+        world.0 = origin + position;
+        velocity.0 = drift + moon_velocity;
+    }
+}
+
+/// Places every on-rails body on its Keplerian orbit at the `SimClock` epoch.
+///
+/// Primaries that are themselves on rails are resolved first, so moons of
+/// on-rails planets follow their planet within the same step. A body whose
+/// chain cannot be resolved stays put and is reported once.
+pub fn propagate_on_rails(
+    mut rails: Query<(Entity, &OrbitalElements, &OnRails, &mut WorldPosition, &mut Velocity)>,
+    free: Query<(&WorldPosition, &Velocity), Without<OrbitalElements>>,
+    clock: Res<SimClock>,
+    mut reported: Local<HashSet<Entity>>,
+) {
+    let t = clock.epoch;
+
+    let relative: HashMap<Entity, (DVec3, DVec3, Option<Entity>)> = rails
+        .iter()
+        .map(|(entity, elements, on_rails, _, _)| {
+            let (r, v) = elements.state_at(on_rails.gravitational_parameter, t);
+            (entity, (r, v, on_rails.primary))
+        })
+        .collect();
+
+    let absolute = |entity: Entity| -> Option<(DVec3, DVec3)> {
+        let (mut r, mut v, mut primary) = *relative.get(&entity)?;
+        for _ in 0..MAX_PRIMARY_DEPTH {
+            let Some(parent) = primary else {
+                return Some((r, v));
+            };
+            if let Some(&(pr, pv, next)) = relative.get(&parent) {
+                r += pr;
+                v += pv;
+                primary = next;
+            } else {
+                let (position, velocity) = free.get(parent).ok()?;
+                return Some((r + position.0, v + velocity.0));
+            }
+        }
+        None
+    };
+
+    let resolved: Vec<(Entity, Option<(DVec3, DVec3)>)> = relative
+        .keys()
+        .map(|&entity| (entity, absolute(entity)))
+        .collect();
+
+    for (entity, state) in resolved {
+        let Some((position, velocity)) = state else {
+            if reported.insert(entity) {
+                warn!("[Orbit] Could not resolve primary chain for {:?}", entity);
+            }
+            continue;
+        };
+        if let Ok((_, _, _, mut world, mut body_velocity)) = rails.get_mut(entity) {
+            world.0 = position;
+            body_velocity.0 = velocity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::Planet;
+    use crate::astro::vsop87::{PlanetTheory, Truncation};
+
+    #[test]
+    fn ephemeris_body_follows_its_primary() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(SimClock::new(86_400.0 * 100.0, 1.0));
+        let earth = PlanetTheory::parse(
+            Planet::Earth,
+            include_str!("../../assets/ephemeris/VSOP87D.ear"),
+            &Truncation::default(),
+        )
+        .unwrap();
+        let ephemeris = Ephemeris::from_theories(vec![earth]);
+        let (heliocentric, speed) = ephemeris.state(Planet::Earth, 86_400.0 * 100.0).unwrap();
+        app.insert_resource(ephemeris);
+        app.add_systems(Update, propagate_ephemeris);
+
+        let sun = app
+            .world_mut()
+            .spawn((
+                WorldPosition(DVec3::new(1.0e9, 0.0, 0.0)),
+                Velocity(DVec3::new(0.0, 10.0, 0.0)),
+            ))
+            .id();
+        let body = app
+            .world_mut()
+            .spawn((
+                EphemerisBody(Planet::Earth),
+                OnRails {
+                    primary: Some(sun),
+                    gravitational_parameter: 1.0,
+                },
+                WorldPosition::default(),
+                Velocity::default(),
+            ))
+            .id();
+
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        let expected = DVec3::new(1.0e9, 0.0, 0.0) + heliocentric;
+        assert_eq!(world.get::<WorldPosition>(body).unwrap().0, expected);
+        let velocity = world.get::<Velocity>(body).unwrap().0;
+        assert!((velocity - speed - DVec3::new(0.0, 10.0, 0.0)).length() < 1e-9);
+    }
+
+    #[test]
+    fn lunar_body_follows_its_primary() {
+        let epoch = 86_400.0 * 1000.0;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(SimClock::new(epoch, 1.0));
+        app.add_systems(Update, propagate_lunar);
+
+        let earth = app
+            .world_mut()
+            .spawn((
+                WorldPosition(DVec3::new(0.0, 2.0e9, 0.0)),
+                Velocity(DVec3::new(5.0, 0.0, 0.0)),
+            ))
+            .id();
+        let moon = app
+            .world_mut()
+            .spawn((
+                LunarBody,
+                OnRails {
+                    primary: Some(earth),
+                    gravitational_parameter: 1.0,
+                },
+                WorldPosition::default(),
+                Velocity::default(),
+            ))
+            .id();
+
+        app.world_mut().run_schedule(Update);
+
+        let (geocentric, speed) = lunar::state(epoch);
+        let world = app.world();
+        let expected = DVec3::new(0.0, 2.0e9, 0.0) + geocentric;
+        assert_eq!(world.get::<WorldPosition>(moon).unwrap().0, expected);
+        let velocity = world.get::<Velocity>(moon).unwrap().0;
+        assert!((velocity - speed - DVec3::new(5.0, 0.0, 0.0)).length() < 1e-9);
+    }
+
+    #[test]
+    fn moon_follows_on_rails_planet() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.init_resource::<SimClock>();
+        app.add_systems(Update, propagate_on_rails);
+
+        let circular = |radius: f64| OrbitalElements {
+            semi_major_axis: radius,
+            eccentricity: 0.0,
+            inclination: 0.0,
+            longitude_of_ascending_node: 0.0,
+            argument_of_periapsis: 0.0,
+            mean_anomaly_at_epoch: 0.0,
+            epoch: 0.0,
+        };
+
+        let star = app
+            .world_mut()
+            .spawn((
+                WorldPosition(DVec3::new(5.0, 0.0, 0.0)),
+                Velocity::default(),
+            ))
+            .id();
+        let planet = app
+            .world_mut()
+            .spawn((
+                circular(100.0),
+                OnRails {
+                    primary: Some(star),
+                    gravitational_parameter: 1.0,
+                },
+                WorldPosition::default(),
+                Velocity::default(),
+            ))
+            .id();
+        let moon = app
+            .world_mut()
+            .spawn((
+                circular(10.0),
+                OnRails {
+                    primary: Some(planet),
+                    gravitational_parameter: 1.0,
+                },
+                WorldPosition::default(),
+                Velocity::default(),
+            ))
+            .id();
+
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        assert_eq!(
+            world.get::<WorldPosition>(planet).unwrap().0,
+            DVec3::new(105.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            world.get::<WorldPosition>(moon).unwrap().0,
+            DVec3::new(115.0, 0.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,485 @@
+//! # Orbit Display Systems
+//!
+//! Records orbit trails, refreshes predicted trajectories off the main
+//! thread and draws both as fading gizmo lines, optionally relative to a
+//! chosen reference body. Driven by `GameplayConfig.orbits`.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, futures::check_ready};
+
+use crate::astro::Ephemeris;
+use crate::components::{
+    EphemerisBody, LunarBody, ManeuverPlan, Mass, OnRails, OrbitParent, OrbitalElements,
+    Spacecraft, Trail, Velocity, WorldPosition,
+};
+use crate::config::GameplayConfig;
+use crate::physics::maneuver::{self, ManeuverNode};
+use crate::physics::prediction::{
+    self, Attractor, BurnReference, PlannedBurn, PredictionState, Primary, Rail,
+};
+use crate::resources::{
+    ActiveScenario, FloatingOrigin, NodeMarker, OrbitGizmos, OrbitHotkeys, OrbitOverlay,
+    PredictedPaths, SimClock, TrailClock, ViewScale,
+};
+
+/// Applies orbit-overlay hotkeys pressed this frame. The reference cycles
+/// through the scenario bodies in file order, then back to the scene frame.
+pub fn handle_orbit_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<OrbitHotkeys>,
+    scenario: Option<Res<ActiveScenario>>,
+    mut overlay: ResMut<OrbitOverlay>,
+) {
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.just_pressed(key));
+
+    if pressed(hotkeys.toggle_trails) {
+        overlay.trails = !overlay.trails;
+    }
+    if pressed(hotkeys.toggle_predictions) {
+        overlay.predictions = !overlay.predictions;
+    }
+    if pressed(hotkeys.cycle_reference)
+        && let Some(scenario) = scenario
+    {
+        let names: Vec<&str> = scenario.0.bodies.iter().map(|b| b.name.as_str()).collect();
+        overlay.cycle_reference(&names);
+        match &overlay.reference {
+            Some(name) => info!("[Orbits] Drawing paths relative to {}", name),
+            None => info!("[Orbits] Drawing paths in the scene frame"),
+        }
+    }
+}
+
+/// Samples every body's position once `OrbitDisplayConfig::trail_spacing`
+/// of simulated time has passed since the last sample.
+///
+/// All trails are sampled together, so their newest samples share an
+/// instant. A clock jump longer than a whole trail clears every trail
+/// instead of drawing a line across the gap.
+pub fn record_trails(
+    mut commands: Commands,
+    mut bodies: Query<(Entity, &WorldPosition, Option<&mut Trail>), With<Mass>>,
+    mut trail_clock: ResMut<TrailClock>,
+    clock: Res<SimClock>,
+    config: Res<GameplayConfig>,
+) {
+    let orbits = &config.orbits;
+    let capacity = orbits.trail_length as usize;
+    if let Some(last) = trail_clock.last_sample {
+        let elapsed = (clock.epoch - last).abs();
+        if elapsed < orbits.trail_spacing {
+            return;
+        }
+        if elapsed > orbits.trail_spacing * capacity as f64 {
+            for (_, _, trail) in &mut bodies {
+                if let Some(mut trail) = trail {
+                    trail.clear();
+                }
+            }
+        }
+    }
+    trail_clock.last_sample = Some(clock.epoch);
+
+    for (entity, position, trail) in &mut bodies {
+        let position = position.0;
+        match trail {
+            Some(mut trail) => trail.push(position),
+            None => {
+                let mut trail = Trail::new(capacity);
+                trail.push(position);
+                commands.entity(entity).insert(trail);
+            }
+        }
+    }
+}
+
+/// Collects the latest prediction and starts the next one every
+/// `OrbitDisplayConfig::prediction_interval` of real time, or as soon as
+/// possible once a maneuver plan changes.
+///
+/// The prediction integrates a copy of the free bodies on the async compute
+/// pool, with on-rails bodies moved along their theories. Their paths are
+/// kept too, as references to draw other paths against. Spacecraft burn
+/// their upcoming nodes, armed or not, cut short where the delta-v budget
+/// runs out.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_predictions(
+    mut predicted: ResMut<PredictedPaths>,
+    overlay: Res<OrbitOverlay>,
+    bodies: Query<
+        (
+            Entity,
+            &WorldPosition,
+            &Velocity,
+            &Mass,
+            Option<(&Spacecraft, &ManeuverPlan, &OrbitParent)>,
+        ),
+        Without<OnRails>,
+    >,
+    rails: Query<(
+        Entity,
+        &WorldPosition,
+        &Mass,
+        &OnRails,
+        Option<&OrbitalElements>,
+        Option<&EphemerisBody>,
+        Has<LunarBody>,
+    )>,
+    edited: Query<(), Changed<ManeuverPlan>>,
+    ephemeris: Res<Ephemeris>,
+    clock: Res<SimClock>,
+    config: Res<GameplayConfig>,
+    time: Res<Time>,
+) {
+    if !overlay.predictions {
+        if !predicted.paths.is_empty() || predicted.pending.is_some() {
+            *predicted = PredictedPaths::default();
+        }
+        return;
+    }
+
+    if let Some(task) = predicted.pending.as_mut()
+        && let Some((paths, nodes)) = check_ready(task)
+    {
+        predicted.paths = paths.into_iter().collect();
+        predicted.nodes.clear();
+        for (entity, marker) in nodes {
+            predicted.nodes.entry(entity).or_default().push(marker);
+        }
+        predicted.pending = None;
+    }
+
+    let orbits = &config.orbits;
+    predicted.since_refresh += time.delta_secs();
+    if !edited.is_empty() {
+        predicted.since_refresh = predicted.since_refresh.max(orbits.prediction_interval);
+    }
+    if predicted.pending.is_some()
+        || (!predicted.paths.is_empty() && predicted.since_refresh < orbits.prediction_interval)
+    {
+        return;
+    }
+    predicted.since_refresh = 0.0;
+
+    let mut entities = Vec::with_capacity(bodies.iter().len());
+    let mut state = PredictionState {
+        epoch: clock.epoch,
+        ..Default::default()
+    };
+    for (entity, position, velocity, mass, _) in &bodies {
+        entities.push(entity);
+        state.positions.push(position.0);
+        state.velocities.push(velocity.0);
+        state.masses.push(mass.0);
+    }
+    let attractors: Vec<Entity> = rails.iter().map(|(entity, ..)| entity).collect();
+    for (_, position, mass, on_rails, elements, planet, lunar) in &rails {
+        let primary = match on_rails.primary {
+            None => Some(Primary::Origin),
+            Some(primary) => entities
+                .iter()
+                .position(|&e| e == primary)
+                .map(Primary::Free)
+                .or_else(|| {
+                    attractors
+                        .iter()
+                        .position(|&e| e == primary)
+                        .map(Primary::Attractor)
+                }),
+        };
+        let rail = if lunar {
+            Some(Rail::Lunar)
+        } else if let Some(planet) = planet {
+            ephemeris.theory(planet.0).cloned().map(Rail::Ephemeris)
+        } else {
+            elements.map(|elements| Rail::Kepler(*elements, on_rails.gravitational_parameter))
+        };
+        // Bodies their propagation cannot place stay where they are.
+        state.attractors.push(match (rail, primary) {
+            (Some(rail), Some(primary)) => Attractor {
+                mass: mass.0,
+                rail,
+                primary,
+            },
+            _ => Attractor::fixed(position.0, mass.0),
+        });
+    }
+
+    let horizon = orbits.prediction_horizon;
+    let mut burners = Vec::new();
+    for (body, (entity, .., craft)) in bodies.iter().enumerate() {
+        let Some((craft, plan, parent)) = craft else {
+            continue;
+        };
+        let reference = if let Some(index) = entities.iter().position(|&e| e == parent.0) {
+            BurnReference::Free(index)
+        } else if let Some(index) = attractors.iter().position(|&e| e == parent.0) {
+            BurnReference::Attractor(index)
+        } else {
+            BurnReference::Scene
+        };
+        let nodes = plan.nodes().iter().enumerate().map(|(index, node)| {
+            if index == 0 {
+                node.scaled(1.0 - plan.progress)
+            } else {
+                *node
+            }
+        });
+        for node in maneuver::within_budget(nodes, craft.remaining()) {
+            let offset = (node.epoch - clock.epoch).max(0.0);
+            if offset > horizon {
+                break;
+            }
+            burners.push(entity);
+            state.burns.push(PlannedBurn {
+                body,
+                node: ManeuverNode {
+                    epoch: offset,
+                    ..node
+                },
+                reference,
+            });
+        }
+    }
+
+    let physics = config.physics.clone();
+    let points = orbits.prediction_points as usize;
+    predicted.pending = Some(AsyncComputeTaskPool::get().spawn(async move {
+        let prediction = prediction::predict(&physics, &state, horizon, points);
+        let nodes = burners
+            .into_iter()
+            .zip(prediction.burns)
+            .filter_map(|(entity, burn)| {
+                burn.map(|(position, sample)| (entity, NodeMarker { position, sample }))
+            })
+            .collect();
+        let paths = entities
+            .into_iter()
+            .zip(prediction.paths)
+            .chain(attractors.into_iter().zip(prediction.attractor_paths))
+            .collect();
+        (paths, nodes)
+    }));
+}
+
+/// Draws trails fading towards their oldest sample and predicted paths
+/// fading towards the horizon, in the frame of `OrbitOverlay::reference`.
+///
+/// Trails take their body's material color; predictions use
+/// `OrbitDisplayConfig::prediction_color`. Predicted paths of on-rails
+/// bodies are only used as references. Paths are kept in the scene frame
+/// and moved into render space, remapped by the `ViewScale`, only when
+/// drawn.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn draw_orbits(
+    mut gizmos: Gizmos<OrbitGizmos>,
+    overlay: Res<OrbitOverlay>,
+    predicted: Res<PredictedPaths>,
+    config: Res<GameplayConfig>,
+    origin: Res<FloatingOrigin>,
+    scale: Res<ViewScale>,
+    bodies: Query<
+        (
+            Entity,
+            &Name,
+            &WorldPosition,
+            Option<&Trail>,
+            Option<&MeshMaterial3d<StandardMaterial>>,
+            Has<OnRails>,
+        ),
+        With<Mass>,
+    >,
+    materials: Res<Assets<StandardMaterial>>,
+) {
+    if !overlay.trails && !overlay.predictions {
+        return;
+    }
+    let reference = overlay.reference.as_deref().and_then(|name| {
+        bodies
+            .iter()
+            .find(|(_, body_name, ..)| body_name.as_str() == name)
+    });
+    let place = |point: DVec3| scale.to_render(&origin, point);
+    let reference_entity = reference.map(|(entity, ..)| entity);
+    let anchor = reference.map(|(_, _, position, ..)| position.0);
+
+    if overlay.trails {
+        let reference_trail: Option<Vec<DVec3>> = reference
+            .and_then(|(_, _, _, trail, ..)| trail)
+            .map(|trail| trail.iter().collect());
+        for (entity, _, position, trail, material, _) in &bodies {
+            let Some(trail) = trail else {
+                continue;
+            };
+            if Some(entity) == reference_entity || trail.is_empty() {
+                continue;
+            }
+            let mut path: Vec<DVec3> = trail.iter().collect();
+            if let (Some(reference), Some(anchor)) = (&reference_trail, anchor) {
+                path = relative_to(&path, reference, anchor);
+            }
+            path.push(position.0);
+
+            let color = material
+                .and_then(|material| materials.get(&material.0))
+                .map_or(Color::WHITE, |material| material.base_color);
+            draw_faded(&mut gizmos, &place, &path, color, false);
+        }
+    }
+
+    if overlay.predictions {
+        let [r, g, b, a] = config.orbits.prediction_color;
+        let color = Color::from(LinearRgba::new(r, g, b, a));
+        // Every reference, on rails or not, moves along its own predicted
+        // path, which the other paths are drawn relative to.
+        let reference_path = reference_entity.and_then(|entity| predicted.paths.get(&entity));
+        for (&entity, path) in &predicted.paths {
+            if Some(entity) == reference_entity || !matches!(bodies.get(entity), Ok((.., false))) {
+                continue;
+            }
+            match (reference_path, anchor) {
+                (Some(reference), Some(anchor)) => {
+                    draw_faded(
+                        &mut gizmos,
+                        &place,
+                        &relative_to(path, reference, anchor),
+                        color,
+                        true,
+                    );
+                }
+                _ => draw_faded(&mut gizmos, &place, path, color, true),
+            }
+        }
+    }
+}
+
+/// Re-expresses `path` relative to a body that followed `reference` and is
+/// now at `anchor`. Both are aligned from their last point; points with no
+/// matching reference point are dropped.
+pub fn relative_to(path: &[DVec3], reference: &[DVec3], anchor: DVec3) -> Vec<DVec3> {
+    let path = &path[path.len().saturating_sub(reference.len())..];
+    let reference = &reference[reference.len() - path.len()..];
+    path.iter()
+        .zip(reference)
+        .map(|(point, origin)| *point - *origin + anchor)
+        .collect()
+}
+
+/// Position along `path` at the fractional index `sample`, linearly
+/// interpolated and clamped to its ends.
+pub fn point_at(path: &[DVec3], sample: f64) -> DVec3 {
+    let Some(last) = path.len().checked_sub(1) else {
+        return DVec3::ZERO;
+    };
+    let sample = sample.clamp(0.0, last as f64);
+    let index = (sample.floor() as usize).min(last.saturating_sub(1));
+    match path.get(index + 1) {
+        Some(&next) => path[index].lerp(next, sample - index as f64),
+        None => path[index],
+    }
+}
+
+/// Draws the scene-frame `path`, moved into render space by `place`, with
+/// alpha rising from zero at the start, or falling to zero at the end when
+/// `fade_out` is set.
+fn draw_faded(
+    gizmos: &mut Gizmos<OrbitGizmos>,
+    place: &impl Fn(DVec3) -> Vec3,
+    path: &[DVec3],
+    color: Color,
+    fade_out: bool,
+) {
+    if path.len() < 2 {
+        return;
+    }
+    let alpha = color.alpha();
+    let last = (path.len() - 1) as f32;
+    gizmos.linestrip_gradient(path.iter().enumerate().map(|(i, point)| {
+        let t = i as f32 / last;
+        let fade = if fade_out { 1.0 - t } else { t };
+        (place(*point), color.with_alpha(alpha * fade))
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trail_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let mut config = GameplayConfig::default();
+        config.orbits.trail_length = 4;
+        config.orbits.trail_spacing = 10.0;
+        app.insert_resource(config);
+        app.init_resource::<SimClock>();
+        app.init_resource::<TrailClock>();
+        app.add_systems(Update, record_trails);
+        let body = app
+            .world_mut()
+            .spawn((WorldPosition::default(), Mass(1.0)))
+            .id();
+        (app, body)
+    }
+
+    fn advance(app: &mut App, body: Entity, epoch: f64) {
+        app.world_mut().resource_mut::<SimClock>().epoch = epoch;
+        app.world_mut().get_mut::<WorldPosition>(body).unwrap().0.x = epoch;
+        app.world_mut().run_schedule(Update);
+    }
+
+    fn samples(app: &App, body: Entity) -> Vec<f64> {
+        let trail = app.world().get::<Trail>(body).unwrap();
+        trail.iter().map(|point| point.x).collect()
+    }
+
+    #[test]
+    fn trails_sample_at_the_configured_spacing() {
+        let (mut app, body) = trail_app();
+        for epoch in [0.0, 4.0, 10.0, 15.0, 21.0, 30.0, 40.0] {
+            advance(&mut app, body, epoch);
+        }
+        assert_eq!(samples(&app, body), [0.0, 10.0, 21.0, 40.0]);
+
+        advance(&mut app, body, 50.0);
+        assert_eq!(samples(&app, body), [10.0, 21.0, 40.0, 50.0]);
+    }
+
+    #[test]
+    fn clock_jumps_clear_trails() {
+        let (mut app, body) = trail_app();
+        for epoch in [0.0, 10.0, 20.0] {
+            advance(&mut app, body, epoch);
+        }
+        assert_eq!(samples(&app, body), [0.0, 10.0, 20.0]);
+
+        advance(&mut app, body, 1000.0);
+        assert_eq!(samples(&app, body), [1000.0]);
+        advance(&mut app, body, 980.0);
+        assert_eq!(samples(&app, body), [1000.0, 980.0]);
+    }
+
+    #[test]
+    fn relative_paths_align_from_the_newest_point() {
+        let path = [1.0, 2.0, 3.0].map(|x| DVec3::new(x, 0.0, 0.0));
+        let reference = [10.0, 20.0].map(|y| DVec3::new(0.0, y, 0.0));
+        let anchor = DVec3::new(0.0, 25.0, 0.0);
+        assert_eq!(
+            relative_to(&path, &reference, anchor),
+            [DVec3::new(2.0, 15.0, 0.0), DVec3::new(3.0, 5.0, 0.0)]
+        );
+        assert_eq!(relative_to(&path[..1], &reference, anchor).len(), 1);
+        assert!(relative_to(&path, &[], anchor).is_empty());
+    }
+
+    #[test]
+    fn points_between_samples_are_interpolated() {
+        let path = [0.0, 10.0, 30.0].map(|x| DVec3::new(x, 0.0, 0.0));
+        assert_eq!(point_at(&path, 0.5).x, 5.0);
+        assert_eq!(point_at(&path, 1.25).x, 15.0);
+        assert_eq!(point_at(&path, 2.0).x, 30.0);
+        assert_eq!(point_at(&path, 7.0).x, 30.0);
+        assert_eq!(point_at(&path[..1], 0.5).x, 0.0);
+    }
+}
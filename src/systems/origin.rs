@@ -0,0 +1,113 @@
+//! # Floating Origin Systems
+//!
+//! Derives render transforms from `WorldPosition` once the cameras have
+//! recentred the `FloatingOrigin` for the frame, remapped by the
+//! `ViewScale`.
+
+use bevy::prelude::*;
+
+use crate::components::{MeshRadius, Radius, WorldPosition};
+use crate::resources::{FloatingOrigin, ViewScale};
+
+/// Moves every entity with a `WorldPosition` to its remapped offset from
+/// the floating origin, and scales body meshes to their current radius
+/// times the radius inflation. The observer view suspends both, so its eye
+/// is not buried in an inflated body. Rotation stays with its own systems.
+#[allow(clippy::type_complexity)]
+pub fn sync_render_transforms(
+    origin: Res<FloatingOrigin>,
+    scale: Res<ViewScale>,
+    mut entities: Query<(
+        &WorldPosition,
+        &mut Transform,
+        Option<(&Radius, &MeshRadius)>,
+    )>,
+) {
+    let inflation = scale.radius_scale();
+    for (position, mut transform, radius) in &mut entities {
+        transform.translation = scale.to_render(&origin, position.0);
+        if let Some((radius, mesh)) = radius {
+            transform.scale = Vec3::splat((inflation * radius.0 / mesh.0) as f32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gameplay::{ScaleMode, ViewScaleConfig};
+    use bevy::math::DVec3;
+
+    fn sync_app(origin: DVec3, config: &ViewScaleConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(FloatingOrigin(origin));
+        app.insert_resource(ViewScale::from(config));
+        app.add_systems(Update, sync_render_transforms);
+        app
+    }
+
+    #[test]
+    fn transforms_follow_the_origin_at_neptune_distances() {
+        let neptune = DVec3::new(-2.1e12, 3.98e12, -1.1e9);
+        let mut app = sync_app(neptune, &ViewScaleConfig::default());
+
+        let triton = app
+            .world_mut()
+            .spawn((
+                WorldPosition(neptune + DVec3::new(3.547e8, 0.0, 0.0)),
+                Transform::from_rotation(Quat::from_rotation_x(0.5)),
+            ))
+            .id();
+        let probe = app
+            .world_mut()
+            .spawn((
+                WorldPosition(neptune + DVec3::new(0.75, -12.5, 100.25)),
+                Transform::default(),
+            ))
+            .id();
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        let probe = world.get::<Transform>(probe).unwrap().translation;
+        assert!(probe.as_dvec3().distance(DVec3::new(0.75, -12.5, 100.25)) < 1e-4);
+        let triton = world.get::<Transform>(triton).unwrap();
+        assert_eq!(triton.translation, Vec3::new(3.547e8, 0.0, 0.0));
+        assert_eq!(triton.rotation, Quat::from_rotation_x(0.5));
+    }
+
+    #[test]
+    fn scaled_views_remap_positions_and_inflate_radii() {
+        let config = ViewScaleConfig {
+            mode: ScaleMode::SquareRoot,
+            radius_inflation: 10.0,
+            ..default()
+        };
+        let mut app = sync_app(DVec3::new(1.0e12, 0.0, 0.0), &config);
+        let planet = app
+            .world_mut()
+            .spawn((
+                WorldPosition(DVec3::new(1.0e12, 0.0, 1.0e11)),
+                Transform::default(),
+                Radius(7.0e7),
+                MeshRadius(6.0e7),
+            ))
+            .id();
+        let light = app
+            .world_mut()
+            .spawn((WorldPosition::default(), Transform::default()))
+            .id();
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        let planet = world.get::<Transform>(planet).unwrap();
+        assert!((planet.translation.z / 1.0e10 - 1.0).abs() < 1e-6);
+        assert!((planet.scale.x / (10.0 * 7.0 / 6.0) - 1.0).abs() < 1e-6);
+        let transform = world.get::<Transform>(light).unwrap();
+        assert!((transform.translation.x / -3.162_277_7e10 - 1.0).abs() < 1e-6);
+        assert_eq!(transform.scale, Vec3::ONE);
+
+        // Simulation state is never touched.
+        assert_eq!(world.get::<WorldPosition>(light).unwrap().0, DVec3::ZERO);
+    }
+}
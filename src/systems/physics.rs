@@ -0,0 +1,619 @@
+//! # Physics Systems
+//!
+//! Fixed-step N-body gravity driven by `GameplayConfig.physics`, collisions
+//! driven by `GameplayConfig.collisions` and tidal disruption driven by
+//! `GameplayConfig.disruption`.
+
+use bevy::math::DVec3;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::components::{
+    Acceleration, Fragment, Mass, OnRails, OrbitParent, Radius, Spacecraft, Velocity, WorldPosition,
+};
+use crate::config::GameplayConfig;
+use crate::config::gameplay::CollisionPolicy;
+use crate::entities;
+use crate::messages::{BodyCollided, BodyDisrupted, CollisionOutcome};
+use crate::physics::collision::{self, Body};
+use crate::physics::kernel::SoaBodies;
+use crate::physics::{gravity, roche};
+use crate::resources::SimClock;
+
+/// Advances every body with a `Mass` by the `SimClock` delta of this step.
+///
+/// The delta is split into equal sub-steps no longer than
+/// `PhysicsConfig::max_substep`, capped at `PhysicsConfig::max_substeps`.
+/// The scheme is selected by `PhysicsConfig::integrator` and the force
+/// backend by `PhysicsConfig::solver`. Velocities are
+/// clamped to `PhysicsConfig::max_velocity` after the step, and `Acceleration`
+/// holds the last acceleration evaluated by the integrator.
+///
+/// On-rails bodies attract the integrated bodies from their current positions
+/// but are not moved here.
+pub fn step_bodies(
+    mut query: Query<
+        (&mut WorldPosition, &mut Velocity, &mut Acceleration, &Mass),
+        Without<OnRails>,
+    >,
+    rails: Query<(&WorldPosition, &Mass), With<OnRails>>,
+    clock: Res<SimClock>,
+    config: Res<GameplayConfig>,
+) {
+    let physics = &config.physics;
+    let delta = clock.delta();
+    if delta == 0.0 {
+        return;
+    }
+    let substeps = (delta.abs() / physics.max_substep)
+        .ceil()
+        .clamp(1.0, f64::from(physics.max_substeps));
+    let dt = delta / substeps;
+    let max_velocity = f64::from(physics.max_velocity);
+
+    let mut positions = Vec::with_capacity(query.iter().len());
+    let mut velocities = Vec::with_capacity(positions.capacity());
+    let mut masses = Vec::with_capacity(positions.capacity());
+    for (position, velocity, _, mass) in &query {
+        positions.push(position.0);
+        velocities.push(velocity.0);
+        masses.push(mass.0);
+    }
+
+    let count = positions.len();
+    let mut all_positions = positions.clone();
+    let mut all_masses = masses;
+    for (position, mass) in &rails {
+        all_positions.push(position.0);
+        all_masses.push(mass.0);
+    }
+    let mut all_accelerations = vec![DVec3::ZERO; all_positions.len()];
+    let mut bodies = SoaBodies::default();
+
+    let mut last_accelerations = vec![DVec3::ZERO; count];
+    let mut accel = |x: &[DVec3], out: &mut [DVec3]| {
+        all_positions[..count].copy_from_slice(x);
+        gravity::solve(
+            physics,
+            &all_positions,
+            &all_masses,
+            &mut bodies,
+            &mut all_accelerations,
+        );
+        out.copy_from_slice(&all_accelerations[..count]);
+        last_accelerations.copy_from_slice(out);
+    };
+    let integrator = physics.integrator.integrator();
+    for _ in 0..substeps as u32 {
+        integrator.step(&mut positions, &mut velocities, dt, &mut accel);
+    }
+
+    for ((mut position, mut velocity, mut acceleration, _), (x, (v, a))) in
+        query.iter_mut().zip(
+            positions
+                .into_iter()
+                .zip(velocities.into_iter().zip(last_accelerations)),
+        )
+    {
+        position.0 = x;
+        velocity.0 = v.clamp_length_max(max_velocity);
+        acceleration.0 = a;
+    }
+}
+
+/// Resolves bodies that overlap by radius after the step, following
+/// `CollisionConfig::policy`, and reports each pair with `BodyCollided`.
+///
+/// Merges keep the heavier body (or the on-rails one) and despawn the other;
+/// orbits and parents that pointed at the absorbed body move to the
+/// survivor. Pairs are resolved in a fixed order, one contact per body per
+/// step.
+#[allow(clippy::type_complexity)]
+pub fn resolve_collisions(
+    mut commands: Commands,
+    mut bodies: Query<(
+        Entity,
+        &mut WorldPosition,
+        &mut Velocity,
+        &mut Mass,
+        &mut Radius,
+        Has<OnRails>,
+    )>,
+    mut rails: Query<(Entity, &mut OnRails)>,
+    mut parents: Query<&mut OrbitParent>,
+    config: Res<GameplayConfig>,
+    mut collided: MessageWriter<BodyCollided>,
+) {
+    let collisions = &config.collisions;
+    if collisions.policy == CollisionPolicy::Ignore {
+        return;
+    }
+    let (min_radius, max_radius) = (
+        f64::from(config.planets.min_radius),
+        f64::from(config.planets.max_radius),
+    );
+
+    let (entities, mut states): (Vec<Entity>, Vec<Body>) = bodies
+        .iter()
+        .map(|(entity, position, velocity, mass, radius, kinematic)| {
+            (
+                entity,
+                Body {
+                    position: position.0,
+                    velocity: velocity.0,
+                    mass: mass.0,
+                    radius: radius.0,
+                    kinematic,
+                },
+            )
+        })
+        .unzip();
+
+    let mut touched = vec![false; states.len()];
+    let mut absorbed: HashMap<Entity, Entity> = HashMap::default();
+    for (i, j) in collision::contacts(&states) {
+        if touched[i] || touched[j] {
+            continue;
+        }
+        touched[i] = true;
+        touched[j] = true;
+
+        let (a, b) = (states[i], states[j]);
+        let position = if a.kinematic || b.kinematic {
+            if a.kinematic { a.position } else { b.position }
+        } else {
+            (a.position * a.mass + b.position * b.mass) / (a.mass + b.mass)
+        };
+        let (impact_speed, outcome) = match collisions.policy {
+            CollisionPolicy::Merge => {
+                let keep_first = a.kinematic || (!b.kinematic && a.mass >= b.mass);
+                let (survivor, loser) = if keep_first { (i, j) } else { (j, i) };
+                states[survivor] =
+                    collision::merge(&a, &b, collisions.density, min_radius, max_radius);
+                absorbed.insert(entities[loser], entities[survivor]);
+                (
+                    collision::closing_speed(&a, &b).max(0.0),
+                    CollisionOutcome::Merged {
+                        survivor: entities[survivor],
+                    },
+                )
+            }
+            CollisionPolicy::Bounce => {
+                let (mut a, mut b) = (a, b);
+                let speed = collision::bounce(&mut a, &mut b, collisions.restitution);
+                states[i] = a;
+                states[j] = b;
+                (speed, CollisionOutcome::Bounced)
+            }
+            CollisionPolicy::Ignore => unreachable!("ignored collisions return early"),
+        };
+
+        collided.write(BodyCollided {
+            first: entities[i],
+            second: entities[j],
+            outcome,
+            impact_speed,
+            position,
+        });
+    }
+
+    for (index, entity) in entities.iter().enumerate() {
+        if !touched[index] {
+            continue;
+        }
+        if absorbed.contains_key(entity) {
+            commands.entity(*entity).despawn();
+            continue;
+        }
+        let Ok((_, mut position, mut velocity, mut mass, mut radius, _)) = bodies.get_mut(*entity)
+        else {
+            continue;
+        };
+        let state = states[index];
+        position.0 = state.position;
+        velocity.0 = state.velocity;
+        mass.0 = state.mass;
+        radius.0 = state.radius;
+    }
+
+    if absorbed.is_empty() {
+        return;
+    }
+    for (entity, mut on_rails) in &mut rails {
+        if let Some(&survivor) = on_rails.primary.and_then(|p| absorbed.get(&p)) {
+            // A rails body that swallowed its own primary orbits the origin.
+            on_rails.primary = (survivor != entity).then_some(survivor);
+        }
+    }
+    for mut parent in &mut parents {
+        if let Some(survivor) = absorbed.get(&parent.0) {
+            parent.0 = *survivor;
+        }
+    }
+}
+
+/// Breaks up free bodies that are inside the Roche limit of a heavier body,
+/// replacing each with `Fragment`s and reporting it with `BodyDisrupted`.
+///
+/// On-rails bodies, fragments and spacecraft are never disrupted; a body
+/// touching its primary is left to `resolve_collisions`. Kick seeds mix
+/// `DisruptionConfig::seed` with the satellite's name, so a scenario
+/// breaks up the same way on every run.
+#[allow(clippy::type_complexity)]
+pub fn disrupt_satellites(
+    mut commands: Commands,
+    bodies: Query<(
+        Entity,
+        &Name,
+        &WorldPosition,
+        &Velocity,
+        &Mass,
+        &Radius,
+        Has<OnRails>,
+        Has<Fragment>,
+        Has<Spacecraft>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Option<&ChildOf>,
+    )>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    config: Res<GameplayConfig>,
+    mut disrupted: MessageWriter<BodyDisrupted>,
+) {
+    let disruption = &config.disruption;
+    if !disruption.enabled {
+        return;
+    }
+    let gravitational_constant = config.physics.gravitational_constant;
+
+    for (
+        entity,
+        name,
+        position,
+        velocity,
+        mass,
+        radius,
+        kinematic,
+        fragment,
+        spacecraft,
+        material,
+        root,
+    ) in &bodies
+    {
+        if kinematic || fragment || spacecraft {
+            continue;
+        }
+        let position = position.0;
+        let primary = bodies
+            .iter()
+            .filter(|other| other.0 != entity && other.4.0 > mass.0)
+            .filter_map(
+                |(other, _, other_position, _, other_mass, other_radius, ..)| {
+                    let distance = position.distance(other_position.0);
+                    let limit =
+                        roche::roche_limit(other_mass.0, mass.0, radius.0, disruption.formula);
+                    (distance < limit && distance > radius.0 + other_radius.0).then_some((
+                        other,
+                        distance,
+                        distance / limit,
+                    ))
+                },
+            )
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((primary, distance, _)) = primary else {
+            continue;
+        };
+        let count =
+            roche::fragment_count(mass.0, disruption.fragments, disruption.min_fragment_mass);
+        if count < 2 {
+            continue;
+        }
+
+        let escape_speed = (2.0 * gravitational_constant * mass.0 / radius.0).sqrt();
+        let satellite = Body {
+            position,
+            velocity: velocity.0,
+            mass: mass.0,
+            radius: radius.0,
+            kinematic: false,
+        };
+        let parts = roche::fragments(
+            &satellite,
+            count as usize,
+            disruption.velocity_spread * escape_speed,
+            roche::body_seed(disruption.seed, name.as_str()),
+        );
+        let fragments: Vec<Entity> = parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| {
+                entities::spawn_fragment(
+                    &mut commands,
+                    &mut meshes,
+                    material.map(|material| material.0.clone()),
+                    format!("{}-{}", name.as_str(), index + 1),
+                    part,
+                    primary,
+                    root.map(ChildOf::parent),
+                )
+            })
+            .collect();
+
+        info!(
+            "[Physics] '{}' broke up into {} fragments at {:.0} km",
+            name.as_str(),
+            count,
+            distance / 1000.0
+        );
+        commands.entity(entity).despawn();
+        disrupted.write(BodyDisrupted {
+            body: entity,
+            primary,
+            fragments,
+            distance,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gameplay::IntegratorKind;
+
+    fn setup_app(config: GameplayConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(config);
+        app.init_resource::<SimClock>();
+        app.add_systems(Update, step_bodies);
+        app
+    }
+
+    fn spawn_body(app: &mut App, position: DVec3, mass: f64) -> Entity {
+        app.world_mut()
+            .spawn((
+                WorldPosition(position),
+                Mass(mass),
+                Velocity::default(),
+                Acceleration::default(),
+            ))
+            .id()
+    }
+
+    fn step(app: &mut App, dt: f64) {
+        app.world_mut().resource_mut::<SimClock>().tick(dt);
+        app.world_mut().run_schedule(Update);
+    }
+
+    #[test]
+    fn bodies_fall_towards_each_other() {
+        for integrator in [IntegratorKind::SemiImplicitEuler, IntegratorKind::Yoshida4] {
+            let mut config = GameplayConfig::default();
+            config.physics.gravitational_constant = 1.0;
+            config.physics.integrator = integrator;
+            let mut app = setup_app(config);
+
+            let a = spawn_body(&mut app, DVec3::new(-1.0, 0.0, 0.0), 1.0);
+            let b = spawn_body(&mut app, DVec3::new(1.0, 0.0, 0.0), 1.0);
+            step(&mut app, 0.1);
+
+            let world = app.world();
+            assert!(world.get::<Velocity>(a).unwrap().0.x > 0.0);
+            assert!(world.get::<Velocity>(b).unwrap().0.x < 0.0);
+            assert!(world.get::<Acceleration>(a).unwrap().0.x > 0.0);
+            assert!(world.get::<WorldPosition>(a).unwrap().0.x > -1.0);
+        }
+    }
+
+    #[test]
+    fn velocity_is_clamped_to_max() {
+        let mut config = GameplayConfig::default();
+        config.physics.gravitational_constant = 1.0e6;
+        config.physics.max_velocity = 2.0;
+        let mut app = setup_app(config);
+
+        let a = spawn_body(&mut app, DVec3::ZERO, 1.0e3);
+        spawn_body(&mut app, DVec3::X, 1.0e3);
+        step(&mut app, 0.1);
+
+        let speed = app.world().get::<Velocity>(a).unwrap().0.length();
+        assert!((speed - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn long_steps_are_split_into_substeps() {
+        let run = |max_substep: f64| {
+            let mut config = GameplayConfig::default();
+            config.physics.gravitational_constant = 1.0;
+            config.physics.max_substep = max_substep;
+            let mut app = setup_app(config);
+            let a = spawn_body(&mut app, DVec3::new(-1.0, 0.0, 0.0), 1.0);
+            spawn_body(&mut app, DVec3::new(1.0, 0.0, 0.0), 1.0);
+            app.world_mut().resource_mut::<SimClock>().set_time_scale(10.0);
+            step(&mut app, 0.05);
+            app.world().get::<Velocity>(a).unwrap().0.x
+        };
+
+        let one_step = run(1.0);
+        let substepped = run(0.01);
+        // Velocity Verlet in one 0.5 s step vs. fifty 0.01 s sub-steps.
+        assert!((one_step - substepped).abs() > 1e-6);
+        assert!((substepped - run(0.005)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn paused_clock_freezes_bodies() {
+        let mut config = GameplayConfig::default();
+        config.physics.gravitational_constant = 1.0;
+        let mut app = setup_app(config);
+        let a = spawn_body(&mut app, DVec3::new(-1.0, 0.0, 0.0), 1.0);
+        spawn_body(&mut app, DVec3::new(1.0, 0.0, 0.0), 1.0);
+        app.world_mut().resource_mut::<SimClock>().set_paused(true);
+        step(&mut app, 0.1);
+
+        assert_eq!(app.world().get::<Velocity>(a).unwrap().0, DVec3::ZERO);
+    }
+
+    #[test]
+    fn steps_keep_sub_metre_precision_at_neptune_distances() {
+        let mut config = GameplayConfig::default();
+        config.physics.gravitational_constant = 0.0;
+        let mut app = setup_app(config);
+        let start = DVec3::new(4.495e12, 0.0, 0.0);
+        let probe = spawn_body(&mut app, start, 1.0);
+        app.world_mut().get_mut::<Velocity>(probe).unwrap().0 = DVec3::new(0.25, -0.125, 0.0);
+        for _ in 0..8 {
+            step(&mut app, 0.5);
+        }
+
+        let moved = app.world().get::<WorldPosition>(probe).unwrap().0 - start;
+        assert!(moved.distance(DVec3::new(1.0, -0.5, 0.0)) < 1e-3);
+    }
+
+    fn collision_app(policy: CollisionPolicy) -> App {
+        let mut config = GameplayConfig::default();
+        config.collisions.policy = policy;
+        config.collisions.restitution = 1.0;
+        config.planets.min_radius = 0.1;
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(config);
+        app.add_message::<BodyCollided>();
+        app.add_systems(Update, resolve_collisions);
+        app
+    }
+
+    fn spawn_ball(app: &mut App, x: f64, velocity: DVec3, mass: f64) -> Entity {
+        app.world_mut()
+            .spawn((
+                WorldPosition(DVec3::new(x, 0.0, 0.0)),
+                Velocity(velocity),
+                Mass(mass),
+                Radius(1.0),
+            ))
+            .id()
+    }
+
+    fn collisions(app: &App) -> Vec<BodyCollided> {
+        let messages = app.world().resource::<Messages<BodyCollided>>();
+        messages.get_cursor().read(messages).copied().collect()
+    }
+
+    #[test]
+    fn overlapping_bodies_merge() {
+        let mut app = collision_app(CollisionPolicy::Merge);
+        let heavy = spawn_ball(&mut app, 0.0, DVec3::new(1.0, 0.0, 0.0), 3.0);
+        let light = spawn_ball(&mut app, 1.5, DVec3::new(-2.0, 1.0, 0.0), 1.0);
+        let moon = app
+            .world_mut()
+            .spawn(OnRails {
+                primary: Some(light),
+                gravitational_parameter: 1.0,
+            })
+            .id();
+
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        assert!(world.get_entity(light).is_err());
+        assert_eq!(world.get::<Mass>(heavy).unwrap().0, 4.0);
+        // Momentum 3·(1, 0) + 1·(-2, 1) shared by 4 kg.
+        let velocity = world.get::<Velocity>(heavy).unwrap().0;
+        assert!((velocity * 4.0 - DVec3::new(1.0, 1.0, 0.0)).length() < 1e-12);
+        let radius = world.get::<Radius>(heavy).unwrap().0;
+        assert!((radius - 2f64.cbrt()).abs() < 1e-12);
+        assert_eq!(world.get::<OnRails>(moon).unwrap().primary, Some(heavy));
+
+        let events = collisions(&app);
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].outcome,
+            CollisionOutcome::Merged { survivor: heavy }
+        );
+        assert!((events[0].impact_speed - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bounce_policy_reflects_and_reports() {
+        let mut app = collision_app(CollisionPolicy::Bounce);
+        let a = spawn_ball(&mut app, 0.0, DVec3::X, 1.0);
+        let b = spawn_ball(&mut app, 1.5, DVec3::NEG_X, 1.0);
+
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        assert_eq!(world.get::<Velocity>(a).unwrap().0, DVec3::NEG_X);
+        assert_eq!(world.get::<Velocity>(b).unwrap().0, DVec3::X);
+        assert_eq!(world.get::<Mass>(a).unwrap().0, 1.0);
+        assert_eq!(collisions(&app)[0].outcome, CollisionOutcome::Bounced);
+    }
+
+    #[test]
+    fn ignore_policy_lets_bodies_pass() {
+        let mut app = collision_app(CollisionPolicy::Ignore);
+        let a = spawn_ball(&mut app, 0.0, DVec3::X, 1.0);
+        let b = spawn_ball(&mut app, 0.5, DVec3::NEG_X, 1.0);
+
+        app.world_mut().run_schedule(Update);
+
+        let world = app.world();
+        assert_eq!(world.get::<Velocity>(a).unwrap().0, DVec3::X);
+        assert!(world.get_entity(b).is_ok());
+        assert!(collisions(&app).is_empty());
+    }
+
+    #[test]
+    fn satellites_inside_the_roche_limit_break_up() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(GameplayConfig::default());
+        app.init_resource::<Assets<Mesh>>();
+        app.add_message::<BodyDisrupted>();
+        app.add_systems(Update, disrupt_satellites);
+
+        let planet = app
+            .world_mut()
+            .spawn((
+                Name::new("Planet"),
+                WorldPosition::default(),
+                Velocity(DVec3::ZERO),
+                Mass(6.0e24),
+                Radius(6.4e6),
+            ))
+            .id();
+        let velocity = DVec3::new(0.0, 6.6e3, 0.0);
+        let moon = app
+            .world_mut()
+            .spawn((
+                Name::new("Moon"),
+                WorldPosition(DVec3::new(9.0e6, 0.0, 0.0)),
+                Velocity(velocity),
+                Mass(7.3e22),
+                Radius(1.7e6),
+            ))
+            .id();
+
+        app.world_mut().run_schedule(Update);
+        app.world_mut().run_schedule(Update);
+
+        let messages = app.world().resource::<Messages<BodyDisrupted>>();
+        let disrupted: Vec<_> = messages.get_cursor().read(messages).cloned().collect();
+        assert_eq!(disrupted.len(), 1);
+        assert_eq!(disrupted[0].body, moon);
+        assert_eq!(disrupted[0].primary, planet);
+        assert_eq!(disrupted[0].fragments.len(), 16);
+
+        let world = app.world();
+        assert!(world.get_entity(moon).is_err());
+        assert!(world.get_entity(planet).is_ok());
+        let (mut mass, mut momentum) = (0.0, DVec3::ZERO);
+        for &fragment in &disrupted[0].fragments {
+            assert!(world.get::<Fragment>(fragment).is_some());
+            assert_eq!(world.get::<OrbitParent>(fragment).unwrap().0, planet);
+            let fragment_mass = world.get::<Mass>(fragment).unwrap().0;
+            mass += fragment_mass;
+            momentum += world.get::<Velocity>(fragment).unwrap().0 * fragment_mass;
+        }
+        assert!((mass / 7.3e22 - 1.0).abs() < 1e-12);
+        assert!((momentum / mass - velocity).length() < 1e-6);
+    }
+}
@@ -0,0 +1,48 @@
+//! # View Scale Systems
+//!
+//! Switches the rendered scale of the scene at runtime and advances the
+//! blend between scales. Driven by `GameplayConfig.view_scale`.
+
+use bevy::prelude::*;
+
+use crate::config::GameplayConfig;
+use crate::resources::{ViewScale, ViewScaleHotkeys};
+
+/// Cycles the distance mode and steps the radius inflation by
+/// `ViewScaleConfig::inflation_step`, between 1 and
+/// `ViewScaleConfig::max_radius_inflation`.
+pub fn handle_view_scale_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<ViewScaleHotkeys>,
+    config: Res<GameplayConfig>,
+    mut scale: ResMut<ViewScale>,
+) {
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.just_pressed(key));
+    let config = &config.view_scale;
+    let duration = f64::from(config.blend_duration);
+
+    if pressed(hotkeys.cycle_mode) {
+        let mode = scale.mode.next();
+        scale.set_mode(mode, duration);
+        info!("[Scale] Switching to {:?} distances", mode);
+    }
+    let step = if pressed(hotkeys.inflate_radii) {
+        config.inflation_step
+    } else if pressed(hotkeys.deflate_radii) {
+        config.inflation_step.recip()
+    } else {
+        return;
+    };
+    let inflation = (scale.inflation * step).clamp(1.0, config.max_radius_inflation);
+    if inflation != scale.inflation {
+        scale.set_inflation(inflation, duration);
+        info!("[Scale] Drawing radii at {}x", inflation);
+    }
+}
+
+/// Advances the scale blend in real time, so it runs while paused.
+pub fn advance_view_scale(mut scale: ResMut<ViewScale>, time: Res<Time<Real>>) {
+    if scale.is_blending() {
+        scale.update(time.delta_secs_f64());
+    }
+}
@@ -0,0 +1,104 @@
+//! # Scenario Systems
+//!
+//! Loads the configured scenario file and routes failures to the error screen.
+
+use bevy::prelude::*;
+
+use crate::config::GameplayConfig;
+use crate::procgen;
+use crate::resources::ActiveScenario;
+use crate::scenario::{self, MotionSpec, Scenario, ScenarioError};
+use launcher::config::AppPaths;
+use launcher::loading::resources::AssetLoadingState;
+use launcher::states::{AppState, ErrorState};
+use menu::settings::validation::Validate;
+
+/// Loads `GameplayConfig::scenario` when entering the loading screen.
+///
+/// Body textures are queued on the loading screen so the world appears fully
+/// textured. A missing or malformed file switches to `AppState::Error` and
+/// marks the loading as failed, so the loading screen does not fade into
+/// the game.
+pub fn load_active_scenario(
+    mut commands: Commands,
+    paths: Res<AppPaths>,
+    config: Res<GameplayConfig>,
+    asset_server: Res<AssetServer>,
+    mut loading_state: ResMut<AssetLoadingState>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut error_state: ResMut<ErrorState>,
+) {
+    let scenario = read_active_scenario(&paths, &config);
+    loading_state.failed = scenario.is_err();
+    match scenario {
+        Ok(scenario) => {
+            for texture in scenario.bodies.iter().filter_map(|b| b.texture.as_ref()) {
+                let handle: Handle<Image> = asset_server.load(texture.clone());
+                loading_state.required_assets.push(handle.untyped());
+            }
+            commands.insert_resource(ActiveScenario(scenario));
+        }
+        Err(e) => report_scenario_error(&e, &mut next_state, &mut error_state),
+    }
+}
+
+/// Drops the active scenario when leaving the game.
+pub fn unload_active_scenario(mut commands: Commands) {
+    commands.remove_resource::<ActiveScenario>();
+}
+
+/// Reads the scenario named in `config` from the assets directory.
+pub fn read_active_scenario(
+    paths: &AppPaths,
+    config: &GameplayConfig,
+) -> Result<Scenario, ScenarioError> {
+    let path = paths.assets_dir.join(&config.scenario);
+    let mut scenario = scenario::load_scenario(&path)?;
+
+    if let Some(generated) = scenario.generated.clone() {
+        let added = procgen::system::populate(&mut scenario, &config.planets);
+        info!(
+            "[Procgen] Generated {} planets around '{}' from seed {}",
+            added, generated.parent, generated.seed
+        );
+        scenario
+            .validate()
+            .map_err(|errors| ScenarioError::Invalid {
+                path: path.clone(),
+                errors,
+            })?;
+    }
+
+    let rails = scenario
+        .bodies
+        .iter()
+        .filter(|b| b.on_rails && matches!(b.motion, MotionSpec::Orbit(_)))
+        .count();
+    let ephemeris = scenario
+        .bodies
+        .iter()
+        .filter(|b| matches!(b.motion, MotionSpec::Ephemeris(_) | MotionSpec::Lunar))
+        .count();
+    info!(
+        "[Scenario] Loaded '{}' from {:?}: {} bodies ({} on rails, {} from ephemeris), {} lights",
+        scenario.name,
+        path,
+        scenario.bodies.len(),
+        rails,
+        ephemeris,
+        scenario.lights.len()
+    );
+    Ok(scenario)
+}
+
+/// Logs a scenario error and switches to the error screen.
+pub fn report_scenario_error(
+    error: &ScenarioError,
+    next_state: &mut NextState<AppState>,
+    error_state: &mut ErrorState,
+) {
+    let message = error.to_string();
+    error!("[Scenario] {}", message);
+    error_state.message = message;
+    next_state.set(AppState::Error);
+}
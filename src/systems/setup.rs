@@ -4,57 +4,70 @@
 
 use bevy::prelude::*;
 
-use crate::components::{GameWorldRoot, Rotates};
+use super::scenario::{read_active_scenario, report_scenario_error};
+use crate::astro::Ephemeris;
+use crate::components::GameWorldRoot;
+use crate::config::GameplayConfig;
+use crate::entities::{self, BodyAssets};
+use crate::resources::{ActiveScenario, SimClock};
+use launcher::config::AppPaths;
+use launcher::states::{AppState, ErrorState};
 use localization::Localization;
-use theme::Theme;
 
-/// Spawns the 3D game world with a placeholder planet, light, and camera.
+/// Spawns the 3D game world from the active scenario.
+///
+/// When the loading screen was skipped (e.g. `--state ingame`), the scenario
+/// is read here instead.
+#[allow(clippy::too_many_arguments)]
 pub fn setup_game_world(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    theme: Res<Theme>,
+    asset_server: Res<AssetServer>,
     localization: Res<Localization>,
+    config: Res<GameplayConfig>,
+    scenario: Option<Res<ActiveScenario>>,
+    paths: Res<AppPaths>,
+    ephemeris: Res<Ephemeris>,
+    clock: Res<SimClock>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut error_state: ResMut<ErrorState>,
 ) {
     info!("{}", localization.t("log-game-init"));
 
-    // Spawn a root entity to group world objects
-    commands
-        .spawn((Visibility::default(), Transform::default(), GameWorldRoot))
-        .with_children(|parent| {
-            // A placeholder planet (Sphere)
-            if let Ok(sphere_mesh) = Sphere::new(5.0).mesh().ico(5) {
-                parent.spawn((
-                    Mesh3d(meshes.add(sphere_mesh)),
-                    MeshMaterial3d(materials.add(StandardMaterial {
-                        base_color: theme.colors.accent,
-                        reflectance: 0.5,
-                        perceptual_roughness: 0.2,
-                        ..default()
-                    })),
-                    Transform::from_xyz(0.0, 0.0, 0.0),
-                    Rotates,
-                ));
-            } else {
-                warn!("{}", localization.t("log-game-sphere-fail"));
+    let scenario = match scenario {
+        Some(active) => active.0.clone(),
+        None => match read_active_scenario(&paths, &config) {
+            Ok(scenario) => {
+                commands.insert_resource(ActiveScenario(scenario.clone()));
+                scenario
+            }
+            Err(e) => {
+                report_scenario_error(&e, &mut next_state, &mut error_state);
+                return;
             }
+        },
+    };
 
-            // Let there be light!
-            parent.spawn((
-                PointLight {
-                    intensity: 1500000.0,
-                    shadows_enabled: true,
-                    ..default()
-                },
-                Transform::from_xyz(10.0, 10.0, 10.0),
-            ));
+    // Spawn a root entity to group world objects
+    let root = commands
+        .spawn((Visibility::default(), Transform::default(), GameWorldRoot))
+        .id();
 
-            // Setup the camera
-            parent.spawn((
-                Camera3d::default(),
-                Transform::from_xyz(0.0, 0.0, 20.0).looking_at(Vec3::ZERO, Vec3::Y),
-            ));
-        });
+    let mut assets = BodyAssets {
+        meshes: &mut meshes,
+        materials: &mut materials,
+        asset_server: &asset_server,
+    };
+    entities::spawn_scenario(
+        &mut commands,
+        &mut assets,
+        &scenario,
+        config.physics.gravitational_constant,
+        &ephemeris,
+        clock.epoch,
+        root,
+    );
 
     info!("{}", localization.t("log-game-enjoy"));
 }
@@ -0,0 +1,272 @@
+//! # Sky Culture Systems
+//!
+//! Loads the selected sky culture, maps its hotkeys and draws constellation
+//! lines, boundaries and localized names over the star field.
+
+use bevy::prelude::*;
+
+use crate::astro::{SkyCulture, StarCatalog};
+use crate::components::{CelestialSphere, ConstellationLabel, ConstellationLabels};
+use crate::config::GameplayConfig;
+use crate::resources::{ConstellationFigures, ConstellationGizmos, SkyHotkeys, SkyOverlay};
+use launcher::config::AppPaths;
+use localization::{Localization, LocalizedText};
+use theme::Theme;
+
+/// Largest step between boundary points (radians).
+const BOUNDARY_STEP: f64 = 0.02;
+
+/// Lines and labels sit just inside the star sphere so stars draw over them.
+const OVERLAY_RADIUS: f32 = 0.99;
+
+/// Reads culture `id` from the configured sky-culture directory.
+///
+/// A missing or malformed culture is not fatal; it is shown empty.
+pub fn read_sky_culture(paths: &AppPaths, config: &GameplayConfig, id: &str) -> SkyCulture {
+    let dir = paths.assets_dir.join(&config.sky.directory).join(id);
+    match SkyCulture::load(&dir) {
+        Ok(culture) => culture,
+        Err(e) => {
+            error!("[Sky] {}", e);
+            SkyCulture {
+                id: id.to_string(),
+                ..default()
+            }
+        }
+    }
+}
+
+/// Loads the culture selected in `SkyOverlay` and rebuilds its labels.
+///
+/// Runs every frame but only does work when the selected culture differs
+/// from the loaded one, or when the labels are missing after entering the
+/// game.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_sky_culture(
+    mut commands: Commands,
+    overlay: Res<SkyOverlay>,
+    culture: Option<Res<SkyCulture>>,
+    catalog: Option<Res<StarCatalog>>,
+    paths: Res<AppPaths>,
+    config: Res<GameplayConfig>,
+    localization: Res<Localization>,
+    theme: Res<Theme>,
+    labels: Query<Entity, With<ConstellationLabels>>,
+) {
+    let loaded = culture
+        .as_ref()
+        .is_some_and(|culture| culture.id == overlay.culture);
+    if loaded && !labels.is_empty() {
+        return;
+    }
+    let Some(catalog) = catalog else {
+        return;
+    };
+
+    let culture = match culture {
+        Some(culture) if loaded => culture.clone(),
+        _ => read_sky_culture(&paths, &config, &overlay.culture),
+    };
+    let (figures, dropped) = culture.figures(&catalog);
+    info!(
+        "[Sky] Sky culture: {} ({} figures, {} segments without catalog stars)",
+        localization.t(&format!("sky-culture-{}", culture.id)),
+        figures.len(),
+        dropped
+    );
+
+    for entity in &labels {
+        commands.entity(entity).despawn();
+    }
+    let root = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Pickable::IGNORE,
+            label_visibility(overlay.names),
+            ConstellationLabels,
+        ))
+        .id();
+    for figure in &figures {
+        commands.spawn((
+            Text::new(localization.t(&figure.name_key)),
+            TextFont {
+                font: theme.fonts.main.clone(),
+                font_size: theme.sizes.font_body * 0.8,
+                ..default()
+            },
+            TextColor(theme.colors.text_secondary),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+            LocalizedText(figure.name_key.clone()),
+            ConstellationLabel {
+                direction: figure.label_direction.as_vec3(),
+            },
+            ChildOf(root),
+        ));
+    }
+
+    commands.insert_resource(ConstellationFigures(figures));
+    commands.insert_resource(culture);
+}
+
+/// Applies sky-overlay hotkeys pressed this frame.
+pub fn handle_sky_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<SkyHotkeys>,
+    config: Res<GameplayConfig>,
+    mut overlay: ResMut<SkyOverlay>,
+    mut labels: Query<&mut Visibility, With<ConstellationLabels>>,
+) {
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.just_pressed(key));
+
+    if pressed(hotkeys.cycle_culture) {
+        overlay.cycle_culture(&config.sky.cultures);
+    }
+    if pressed(hotkeys.toggle_lines) {
+        overlay.lines = !overlay.lines;
+    }
+    if pressed(hotkeys.toggle_boundaries) {
+        overlay.boundaries = !overlay.boundaries;
+    }
+    if pressed(hotkeys.toggle_names) {
+        overlay.names = !overlay.names;
+        for mut visibility in &mut labels {
+            *visibility = label_visibility(overlay.names);
+        }
+    }
+}
+
+/// Draws constellation lines and boundaries on the celestial sphere.
+pub fn draw_constellations(
+    mut gizmos: Gizmos<ConstellationGizmos>,
+    overlay: Res<SkyOverlay>,
+    figures: Option<Res<ConstellationFigures>>,
+    culture: Option<Res<SkyCulture>>,
+    config: Res<GameplayConfig>,
+    sphere: Query<&Transform, With<CelestialSphere>>,
+) {
+    let Ok(sphere) = sphere.single() else {
+        return;
+    };
+    let radius = config.stars.sphere_radius * OVERLAY_RADIUS;
+    let place = |direction: Vec3| sphere.translation + sphere.rotation * direction * radius;
+
+    if overlay.lines
+        && let Some(figures) = figures
+    {
+        let [r, g, b, a] = config.sky.line_color;
+        let color = LinearRgba::new(r, g, b, a);
+        for figure in &figures.0 {
+            for [start, end] in &figure.lines {
+                gizmos.line(place(start.as_vec3()), place(end.as_vec3()), color);
+            }
+        }
+    }
+
+    if overlay.boundaries
+        && let Some(culture) = culture
+    {
+        let [r, g, b, a] = config.sky.boundary_color;
+        let color = LinearRgba::new(r, g, b, a);
+        for edge in &culture.boundaries {
+            let points = edge.points(BOUNDARY_STEP);
+            gizmos.linestrip(points.iter().map(|point| place(point.as_vec3())), color);
+        }
+    }
+}
+
+/// Pins constellation labels to their sky positions on screen.
+pub fn position_constellation_labels(
+    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    sphere: Query<&Transform, With<CelestialSphere>>,
+    config: Res<GameplayConfig>,
+    mut labels: Query<(&ConstellationLabel, &mut Node, &mut Visibility)>,
+) {
+    let (Ok((camera, camera_transform)), Ok(sphere)) = (camera.single(), sphere.single()) else {
+        return;
+    };
+    let radius = config.stars.sphere_radius * OVERLAY_RADIUS;
+
+    for (label, mut node, mut visibility) in &mut labels {
+        let position = sphere.translation + sphere.rotation * label.direction * radius;
+        match camera.world_to_viewport(camera_transform, position) {
+            Ok(screen) => {
+                node.left = Val::Px(screen.x);
+                node.top = Val::Px(screen.y);
+                *visibility = Visibility::Inherited;
+            }
+            Err(_) => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// Removes the label UI when leaving the game.
+pub fn despawn_constellation_labels(
+    mut commands: Commands,
+    labels: Query<Entity, With<ConstellationLabels>>,
+) {
+    for entity in &labels {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn label_visibility(shown: bool) -> Visibility {
+    if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gameplay::SkyCultureConfig;
+
+    #[test]
+    fn hotkeys_switch_culture_and_layers() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let config = GameplayConfig::default();
+        app.insert_resource(SkyHotkeys::from(&config.sky.bindings));
+        app.insert_resource(SkyOverlay::from(&config.sky));
+        app.insert_resource(config);
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.add_systems(Update, handle_sky_hotkeys);
+
+        let labels = app
+            .world_mut()
+            .spawn((ConstellationLabels, Visibility::Inherited))
+            .id();
+
+        let press = |app: &mut App, key: KeyCode| {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.reset_all();
+            input.press(key);
+            app.update();
+        };
+
+        press(&mut app, KeyCode::KeyC);
+        press(&mut app, KeyCode::KeyB);
+        press(&mut app, KeyCode::KeyJ);
+
+        let overlay = app.world().resource::<SkyOverlay>();
+        let defaults = SkyCultureConfig::default();
+        assert_eq!(overlay.culture, "chinese");
+        assert_eq!(overlay.lines, defaults.show_lines);
+        assert_eq!(overlay.boundaries, !defaults.show_boundaries);
+        assert_eq!(overlay.names, !defaults.show_names);
+        assert_eq!(
+            app.world().get::<Visibility>(labels),
+            Some(&label_visibility(!defaults.show_names))
+        );
+    }
+}
@@ -0,0 +1,603 @@
+//! # Spacecraft Systems
+//!
+//! Spawning spacecraft, editing their maneuver nodes, burning the nodes of
+//! armed plans in the physics step, and showing the plan as node markers
+//! on the predicted path and a HUD panel. Driven by
+//! `GameplayConfig.spacecraft`.
+
+use bevy::math::DVec3;
+use bevy::prelude::*;
+use fluent_bundle::FluentArgs;
+
+use crate::components::{
+    ManeuverPanel, ManeuverPlan, Mass, OrbitParent, Radius, Spacecraft, Velocity, WorldPosition,
+};
+use crate::config::GameplayConfig;
+use crate::constants::SECONDS_PER_DAY;
+use crate::entities::{self, InitialState};
+use crate::physics::maneuver::{ManeuverNode, burned_fraction};
+use crate::resources::{
+    ActiveSpacecraft, FloatingOrigin, Hud, OrbitCamera, OrbitGizmos, OrbitOverlay, PredictedPaths,
+    Selection, SimClock, SpacecraftHotkeys, ViewScale,
+};
+use crate::systems::hud::body_name;
+use crate::systems::orbit_display::point_at;
+use localization::Localization;
+use theme::Theme;
+
+/// Angle between the starting points of successive spacecraft, so that
+/// spacecraft around the same body never start on top of each other.
+const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
+/// Node markers are drawn this size relative to their distance from the
+/// camera.
+const MARKER_SIZE: f32 = 0.01;
+
+/// Spawns a spacecraft on a circular orbit around the selected body, or
+/// the focused one when nothing is selected, and makes it the active
+/// spacecraft. A spacecraft picked as the body is replaced by its parent.
+///
+/// The orbit lies in the reference plane at
+/// `SpacecraftConfig::orbit_radii` parent radii.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn launch_spacecraft(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<SpacecraftHotkeys>,
+    config: Res<GameplayConfig>,
+    localization: Res<Localization>,
+    selection: Res<Selection>,
+    orbit: Option<Res<OrbitCamera>>,
+    bodies: Query<(
+        &Name,
+        &WorldPosition,
+        &Velocity,
+        &Mass,
+        &Radius,
+        Option<&OrbitParent>,
+        Has<Spacecraft>,
+        Option<&ChildOf>,
+    )>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut active: ResMut<ActiveSpacecraft>,
+    mut launched: Local<u32>,
+) {
+    if !hotkeys.spawn.is_some_and(|key| keyboard.just_pressed(key)) {
+        return;
+    }
+    let picked = selection
+        .0
+        .or_else(|| orbit.and_then(|orbit| orbit.focus))
+        .and_then(|entity| {
+            let (.., parent, is_craft, _) = bodies.get(entity).ok()?;
+            if is_craft {
+                parent.map(|parent| parent.0)
+            } else {
+                Some(entity)
+            }
+        });
+    let Some((parent, (parent_name, position, velocity, mass, radius, _, _, root))) =
+        picked.and_then(|entity| Some((entity, bodies.get(entity).ok()?)))
+    else {
+        info!("[Spacecraft] Select or focus a body to launch a spacecraft around it");
+        return;
+    };
+
+    let craft = &config.spacecraft;
+    let distance = radius.0 * craft.orbit_radii;
+    let speed = (config.physics.gravitational_constant * (mass.0 + craft.mass) / distance).sqrt();
+    let (sin, cos) = (GOLDEN_ANGLE * f64::from(*launched)).sin_cos();
+    let state = InitialState {
+        position: position.0 + DVec3::new(cos, sin, 0.0) * distance,
+        velocity: velocity.0 + DVec3::new(-sin, cos, 0.0) * speed,
+    };
+    *launched += 1;
+
+    let mut args = FluentArgs::new();
+    args.set("index", *launched);
+    let name = localization.t_with_args("spacecraft-name", Some(&args));
+    info!(
+        "[Spacecraft] Launching {} around {}",
+        name,
+        parent_name.as_str()
+    );
+    active.0 = Some(entities::spawn_spacecraft(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        name,
+        craft,
+        state,
+        parent,
+        root.map(ChildOf::parent),
+    ));
+}
+
+/// Edits the maneuver plan of the spacecraft being planned: the selected
+/// one, else the active one. New nodes go `SpacecraftConfig::node_lead`
+/// after the last node or the clock; other edits apply to the selected
+/// node, which is never moved into the past.
+#[allow(clippy::type_complexity)]
+pub fn handle_maneuver_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    hotkeys: Res<SpacecraftHotkeys>,
+    config: Res<GameplayConfig>,
+    clock: Res<SimClock>,
+    selection: Res<Selection>,
+    active: Res<ActiveSpacecraft>,
+    mut crafts: Query<(&Name, &mut ManeuverPlan), With<Spacecraft>>,
+) {
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keyboard.just_pressed(key));
+    let Some(Ok((name, mut plan))) = active
+        .target(selection.0, |entity| crafts.contains(entity))
+        .map(|entity| crafts.get_mut(entity))
+    else {
+        return;
+    };
+    let craft = &config.spacecraft;
+    let now = clock.epoch;
+
+    if pressed(hotkeys.add_node) {
+        let after = plan.nodes().last().map_or(now, |node| node.epoch.max(now));
+        plan.insert(ManeuverNode {
+            epoch: after + craft.node_lead,
+            ..default()
+        });
+    }
+    if pressed(hotkeys.remove_node) {
+        plan.remove_selected();
+    }
+    if pressed(hotkeys.next_node) {
+        plan.select_next();
+    }
+
+    let step = craft.delta_v_step;
+    let mut delta_v = DVec3::ZERO;
+    for (key, change) in [
+        (hotkeys.prograde, DVec3::X),
+        (hotkeys.retrograde, DVec3::NEG_X),
+        (hotkeys.normal, DVec3::Y),
+        (hotkeys.anti_normal, DVec3::NEG_Y),
+        (hotkeys.radial_out, DVec3::Z),
+        (hotkeys.radial_in, DVec3::NEG_Z),
+    ] {
+        if pressed(key) {
+            delta_v += change * step;
+        }
+    }
+    let mut delay = 0.0;
+    if pressed(hotkeys.later) {
+        delay += craft.node_time_step;
+    }
+    if pressed(hotkeys.earlier) {
+        delay -= craft.node_time_step;
+    }
+    if delta_v != DVec3::ZERO || delay != 0.0 {
+        plan.edit_selected(|node| {
+            node.prograde += delta_v.x;
+            node.normal += delta_v.y;
+            node.radial += delta_v.z;
+            node.epoch = (node.epoch + delay).max(now);
+        });
+    }
+
+    if pressed(hotkeys.toggle_execution) {
+        plan.armed = !plan.armed;
+        if plan.armed {
+            info!("[Spacecraft] Armed the maneuver plan of {}", name);
+        } else {
+            info!("[Spacecraft] Disarmed the maneuver plan of {}", name);
+        }
+    }
+}
+
+/// Burns the nodes of armed plans as the clock passes them, after the
+/// bodies have been stepped.
+///
+/// Impulsive nodes change the velocity at once in the step that reaches
+/// them; finite ones burn the share of their window each step covers. The
+/// direction is taken against the parent when the burn starts and held
+/// until it ends. Burns stop when the budget runs out. Nodes of unarmed
+/// plans are dropped when their time passes, and nothing burns while the
+/// clock is paused or reversed.
+#[allow(clippy::type_complexity)]
+pub fn execute_maneuvers(
+    clock: Res<SimClock>,
+    config: Res<GameplayConfig>,
+    mut crafts: Query<(
+        &Name,
+        &WorldPosition,
+        &mut Velocity,
+        &mut Spacecraft,
+        &mut ManeuverPlan,
+        Option<&OrbitParent>,
+    )>,
+    parents: Query<(&WorldPosition, &Velocity), Without<Spacecraft>>,
+) {
+    let delta = clock.delta();
+    if delta <= 0.0 {
+        return;
+    }
+    let (from, to) = (clock.epoch - delta, clock.epoch);
+    let settings = &config.spacecraft;
+
+    for (name, position, mut velocity, mut craft, mut plan, parent) in &mut crafts {
+        let (origin, frame_velocity) = parent
+            .and_then(|parent| parents.get(parent.0).ok())
+            .map_or((DVec3::ZERO, DVec3::ZERO), |(position, velocity)| {
+                (position.0, velocity.0)
+            });
+        while let Some(&node) = plan.nodes().first() {
+            if !plan.armed {
+                if node.epoch > to {
+                    break;
+                }
+                info!("[Spacecraft] {} passed a node of its unarmed plan", name);
+                plan.pop_first();
+                continue;
+            }
+
+            let window = node.window(settings.burn_mode, settings.max_acceleration);
+            if window.0 > to {
+                break;
+            }
+            let fraction = burned_fraction(window, from, to);
+            if fraction > 0.0 {
+                let direction = *plan.direction.get_or_insert_with(|| {
+                    node.delta_v_vector(position.0 - origin, velocity.0 - frame_velocity)
+                        .normalize_or_zero()
+                });
+                let burned = (node.delta_v() * fraction).min(craft.remaining());
+                velocity.0 += direction * burned;
+                craft.delta_v_spent += burned;
+                plan.progress += fraction;
+            }
+            if window.1 > to {
+                break;
+            }
+            info!(
+                "[Spacecraft] {} finished a burn; {:.1} m/s of delta-v left",
+                name,
+                craft.remaining()
+            );
+            plan.pop_first();
+        }
+    }
+}
+
+/// Marks each spacecraft's upcoming nodes on its predicted path, in the
+/// same frame as `draw_orbits`. The selected node of each plan is drawn
+/// larger.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_maneuver_nodes(
+    mut gizmos: Gizmos<OrbitGizmos>,
+    overlay: Res<OrbitOverlay>,
+    predicted: Res<PredictedPaths>,
+    config: Res<GameplayConfig>,
+    origin: Res<FloatingOrigin>,
+    scale: Res<ViewScale>,
+    plans: Query<&ManeuverPlan>,
+    bodies: Query<(Entity, &Name, &WorldPosition)>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+) {
+    if !overlay.predictions || predicted.nodes.is_empty() {
+        return;
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let eye = camera.translation();
+    let reference = overlay.reference.as_deref().and_then(|name| {
+        bodies
+            .iter()
+            .find(|(_, body_name, _)| body_name.as_str() == name)
+    });
+    let reference_path = reference.and_then(|(entity, ..)| predicted.paths.get(&entity));
+    let [r, g, b, a] = config.spacecraft.color;
+    let color = Color::from(LinearRgba::new(r, g, b, a));
+
+    for (&entity, markers) in &predicted.nodes {
+        let Ok(plan) = plans.get(entity) else {
+            continue;
+        };
+        for (index, marker) in markers.iter().enumerate() {
+            let position = match (reference_path, reference) {
+                (Some(path), Some((_, _, anchor))) => {
+                    marker.position - point_at(path, marker.sample) + anchor.0
+                }
+                _ => marker.position,
+            };
+            let point = scale.to_render(&origin, position);
+            let mut size = point.distance(eye) * MARKER_SIZE;
+            if plan.selected_index() == Some(index) {
+                size *= 2.0;
+            }
+            gizmos.sphere(Isometry3d::from_translation(point), size, color);
+        }
+    }
+}
+
+/// Spawns the maneuver panel in the bottom-right corner, hidden until
+/// there is a spacecraft to plan for.
+pub fn spawn_maneuver_panel(mut commands: Commands, theme: Res<Theme>) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font: theme.fonts.main.clone(),
+            font_size: theme.sizes.font_body,
+            ..default()
+        },
+        TextColor(theme.colors.text_primary),
+        Node {
+            position_type: PositionType::Absolute,
+            right: theme.sizes.margin,
+            bottom: theme.sizes.margin,
+            padding: UiRect::all(theme.sizes.padding),
+            ..default()
+        },
+        BackgroundColor(theme.colors.surface),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        ManeuverPanel,
+    ));
+}
+
+/// Shows the delta-v budget and maneuver plan of the spacecraft being
+/// planned while the HUD is visible.
+pub fn update_maneuver_panel(
+    hud: Res<Hud>,
+    selection: Res<Selection>,
+    active: Res<ActiveSpacecraft>,
+    clock: Res<SimClock>,
+    localization: Res<Localization>,
+    crafts: Query<(&Name, &Spacecraft, &ManeuverPlan)>,
+    mut panel: Query<(&mut Text, &mut Visibility), With<ManeuverPanel>>,
+) {
+    let Ok((mut text, mut visibility)) = panel.single_mut() else {
+        return;
+    };
+    let craft = active
+        .target(selection.0, |entity| crafts.contains(entity))
+        .and_then(|entity| crafts.get(entity).ok())
+        .filter(|_| hud.visible);
+    visibility.set_if_neq(if craft.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    });
+    let Some((name, craft, plan)) = craft else {
+        return;
+    };
+    let label = plan_text(
+        &localization,
+        &body_name(&localization, name.as_str()),
+        craft,
+        plan,
+        clock.epoch,
+    );
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+/// Panel text for `plan`: budget, planned delta-v, whether the plan is
+/// armed and one line per node, counted down from `now`.
+fn plan_text(
+    localization: &Localization,
+    name: &str,
+    craft: &Spacecraft,
+    plan: &ManeuverPlan,
+    now: f64,
+) -> String {
+    let mut args = FluentArgs::new();
+    args.set("craft", name);
+    args.set("remaining", format!("{:.0}", craft.remaining()));
+    args.set("budget", format!("{:.0}", craft.delta_v_budget));
+    let planned = plan.planned_delta_v();
+    args.set("planned", format!("{planned:.0}"));
+    let mut lines = vec![
+        localization.t_with_args("maneuver-title", Some(&args)),
+        localization.t_with_args("maneuver-budget", Some(&args)),
+    ];
+    lines.push(if planned > craft.remaining() {
+        localization.t_with_args("maneuver-over-budget", Some(&args))
+    } else {
+        localization.t_with_args("maneuver-planned", Some(&args))
+    });
+    lines.push(localization.t(if plan.armed {
+        "maneuver-armed"
+    } else {
+        "maneuver-disarmed"
+    }));
+
+    if plan.nodes().is_empty() {
+        lines.push(localization.t("maneuver-empty"));
+    }
+    for (index, node) in plan.nodes().iter().enumerate() {
+        let mut args = FluentArgs::new();
+        args.set("index", index + 1);
+        args.set("countdown", countdown(node.epoch - now));
+        args.set("prograde", format!("{:.0}", node.prograde));
+        args.set("normal", format!("{:.0}", node.normal));
+        args.set("radial", format!("{:.0}", node.radial));
+        let marker = if plan.selected_index() == Some(index) {
+            "▸ "
+        } else {
+            "  "
+        };
+        let mut line = format!(
+            "{marker}{}",
+            localization.t_with_args("maneuver-node", Some(&args))
+        );
+        if index == 0 && plan.direction.is_some() {
+            line.push_str(" · ");
+            line.push_str(&localization.t("maneuver-burning"));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// `T−` countdown to a time `seconds` away, or `T+` once it has passed, as
+/// hours, minutes and seconds with whole days in front.
+pub fn countdown(seconds: f64) -> String {
+    let sign = if seconds < 0.0 { '+' } else { '−' };
+    let total = seconds.abs().round();
+    let days = (total / SECONDS_PER_DAY).floor();
+    let rest = total - days * SECONDS_PER_DAY;
+    let (hours, minutes, seconds) = (
+        (rest / 3600.0).floor(),
+        (rest % 3600.0 / 60.0).floor(),
+        rest % 60.0,
+    );
+    if days > 0.0 {
+        format!("T{sign}{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("T{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// Forgets the active spacecraft when leaving the game.
+pub fn clear_active_spacecraft(mut active: ResMut<ActiveSpacecraft>) {
+    active.0 = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::gameplay::BurnMode;
+
+    #[test]
+    fn countdowns_show_days_and_clock_time() {
+        assert_eq!(countdown(3725.0), "T−01:02:05");
+        assert_eq!(countdown(-59.6), "T+00:01:00");
+        assert_eq!(countdown(2.0 * SECONDS_PER_DAY + 7.0), "T−2d 00:00:07");
+    }
+
+    fn burn_app(mode: BurnMode) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        let mut config = GameplayConfig::default();
+        config.spacecraft.burn_mode = mode;
+        config.spacecraft.max_acceleration = 2.0;
+        app.insert_resource(config);
+        app.insert_resource(SimClock::new(0.0, 1.0));
+        app.add_systems(Update, execute_maneuvers);
+
+        let planet = app
+            .world_mut()
+            .spawn((
+                WorldPosition::default(),
+                Velocity(DVec3::new(0.0, 0.0, 5.0)),
+            ))
+            .id();
+        let mut plan = ManeuverPlan::default();
+        for epoch in [100.0, 200.0] {
+            plan.insert(ManeuverNode {
+                epoch,
+                prograde: 30.0,
+                radial: 40.0,
+                ..default()
+            });
+        }
+        let craft = app
+            .world_mut()
+            .spawn((
+                Name::new("Probe"),
+                WorldPosition(DVec3::new(7.0e6, 0.0, 0.0)),
+                Velocity(DVec3::new(0.0, 7.5e3, 5.0)),
+                Spacecraft::new(80.0),
+                plan,
+                OrbitParent(planet),
+            ))
+            .id();
+        (app, craft)
+    }
+
+    /// Runs one step `delta` seconds long.
+    fn step(app: &mut App, delta: f64) {
+        app.world_mut().resource_mut::<SimClock>().tick(delta);
+        app.world_mut().run_schedule(Update);
+    }
+
+    fn arm(app: &mut App, craft: Entity) {
+        app.world_mut()
+            .get_mut::<ManeuverPlan>(craft)
+            .unwrap()
+            .armed = true;
+    }
+
+    #[test]
+    fn armed_plans_burn_along_the_parent_orbit_within_budget() {
+        let (mut app, craft) = burn_app(BurnMode::Impulsive);
+        step(&mut app, 150.0);
+        let world = app.world();
+        assert_eq!(world.get::<ManeuverPlan>(craft).unwrap().nodes().len(), 1);
+        assert_eq!(world.get::<Spacecraft>(craft).unwrap().delta_v_spent, 0.0);
+
+        let (mut app, craft) = burn_app(BurnMode::Impulsive);
+        arm(&mut app, craft);
+        step(&mut app, 99.0);
+        assert_eq!(
+            app.world().get::<Spacecraft>(craft).unwrap().delta_v_spent,
+            0.0
+        );
+        step(&mut app, 51.0);
+        let velocity = app.world().get::<Velocity>(craft).unwrap().0;
+        assert!(velocity.distance(DVec3::new(40.0, 7530.0, 5.0)) < 1e-9);
+
+        // The second node only has 30 m/s of budget left.
+        step(&mut app, 100.0);
+        let world = app.world();
+        assert_eq!(world.get::<Spacecraft>(craft).unwrap().remaining(), 0.0);
+        assert!(world.get::<ManeuverPlan>(craft).unwrap().nodes().is_empty());
+        let change = world.get::<Velocity>(craft).unwrap().0 - velocity;
+        assert!((change.length() - 30.0).abs() < 1e-9);
+        assert_eq!(change.z, 0.0);
+    }
+
+    #[test]
+    fn finite_burns_spread_over_their_window() {
+        let (mut app, craft) = burn_app(BurnMode::Finite);
+        arm(&mut app, craft);
+        // A 50 m/s node at 2 m/s² burns from 87.5 s to 112.5 s.
+        step(&mut app, 80.0);
+        assert!(
+            app.world()
+                .get::<ManeuverPlan>(craft)
+                .unwrap()
+                .direction
+                .is_none()
+        );
+        step(&mut app, 20.0);
+        let plan = app.world().get::<ManeuverPlan>(craft).unwrap();
+        assert_eq!(plan.progress, 0.5);
+        assert!(plan.direction.is_some());
+        assert_eq!(
+            app.world().get::<Spacecraft>(craft).unwrap().delta_v_spent,
+            25.0
+        );
+
+        step(&mut app, 20.0);
+        let world = app.world();
+        assert_eq!(world.get::<Spacecraft>(craft).unwrap().delta_v_spent, 50.0);
+        let plan = world.get::<ManeuverPlan>(craft).unwrap();
+        assert_eq!((plan.nodes().len(), plan.progress), (1, 0.0));
+    }
+
+    #[test]
+    fn nodes_moved_onto_the_clock_still_burn() {
+        let (mut app, craft) = burn_app(BurnMode::Impulsive);
+        arm(&mut app, craft);
+        step(&mut app, 50.0);
+        // "Earlier" clamps the node to the current time.
+        let mut plan = app.world_mut().get_mut::<ManeuverPlan>(craft).unwrap();
+        plan.select_next();
+        plan.edit_selected(|node| node.epoch = 50.0);
+        step(&mut app, 10.0);
+        let world = app.world();
+        assert_eq!(world.get::<Spacecraft>(craft).unwrap().delta_v_spent, 50.0);
+        assert_eq!(world.get::<ManeuverPlan>(craft).unwrap().nodes().len(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! # Star Field Systems
+//!
+//! Loads the star catalog named in the asset manifest and keeps the
+//! celestial sphere centred on the camera.
+
+use bevy::prelude::*;
+
+use crate::astro::StarCatalog;
+use crate::components::{CelestialSphere, GameWorldRoot};
+use crate::config::GameplayConfig;
+use crate::entities::{BodyAssets, stars};
+use launcher::config::AppPaths;
+use launcher::loading::assets::AssetManifest;
+
+/// Loads the configured star catalog when entering the loading screen.
+///
+/// The catalog is kept for the rest of the session. A missing or malformed
+/// catalog is not fatal; the sky is simply left empty.
+pub fn load_star_catalog(
+    mut commands: Commands,
+    paths: Res<AppPaths>,
+    manifest: Res<AssetManifest>,
+    config: Res<GameplayConfig>,
+    catalog: Option<Res<StarCatalog>>,
+) {
+    if catalog.is_none() {
+        commands.insert_resource(read_star_catalog(&paths, &manifest, &config));
+    }
+}
+
+/// Reads the catalog keyed by `StarConfig::catalog` from the assets directory.
+pub fn read_star_catalog(
+    paths: &AppPaths,
+    manifest: &AssetManifest,
+    config: &GameplayConfig,
+) -> StarCatalog {
+    let Some(file) = manifest.catalog(&config.stars.catalog) else {
+        warn!(
+            "[Stars] No '{}' entry in the manifest catalogs",
+            config.stars.catalog
+        );
+        return StarCatalog::default();
+    };
+
+    let path = paths.assets_dir.join(file);
+    match StarCatalog::load(&path) {
+        Ok(catalog) => {
+            info!(
+                "[Stars] Loaded {} stars from {:?}",
+                catalog.stars.len(),
+                path
+            );
+            catalog
+        }
+        Err(e) => {
+            error!("[Stars] {}", e);
+            StarCatalog::default()
+        }
+    }
+}
+
+/// Spawns the star field under the game world root.
+///
+/// When the loading screen was skipped, the catalog is read here instead.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_star_field(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+    config: Res<GameplayConfig>,
+    catalog: Option<Res<StarCatalog>>,
+    paths: Res<AppPaths>,
+    manifest: Res<AssetManifest>,
+    root: Query<Entity, With<GameWorldRoot>>,
+) {
+    let Ok(root) = root.single() else {
+        return;
+    };
+
+    let loaded;
+    let catalog = match &catalog {
+        Some(catalog) => catalog.as_ref(),
+        None => {
+            loaded = read_star_catalog(&paths, &manifest, &config);
+            commands.insert_resource(loaded.clone());
+            &loaded
+        }
+    };
+
+    let mut assets = BodyAssets {
+        meshes: &mut meshes,
+        materials: &mut materials,
+        asset_server: &asset_server,
+    };
+    stars::spawn_star_field(&mut commands, &mut assets, catalog, &config.stars, root);
+}
+
+/// Moves the celestial sphere with the camera so stars stay at infinity.
+pub fn center_celestial_sphere(
+    camera: Query<&Transform, (With<Camera3d>, Without<CelestialSphere>)>,
+    mut sphere: Query<&mut Transform, With<CelestialSphere>>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    for mut transform in &mut sphere {
+        transform.translation = camera.translation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sphere_follows_camera() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_systems(Update, center_celestial_sphere);
+
+        app.world_mut()
+            .spawn((Camera3d::default(), Transform::from_xyz(1.0, 2.0, 3.0)));
+        let sphere = app
+            .world_mut()
+            .spawn((CelestialSphere, Transform::default()))
+            .id();
+        app.update();
+
+        assert_eq!(
+            app.world().get::<Transform>(sphere).unwrap().translation,
+            Vec3::new(1.0, 2.0, 3.0)
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! # Terrain Systems
+//!
+//! Gives generated planets quadtree terrain driven by `GameplayConfig.terrain`,
+//! refines it around the camera and builds chunk meshes on the async compute
+//! pool so that no frame waits for noise sampling.
+
+use std::sync::Arc;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::Mix;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, futures::check_ready};
+
+use crate::components::{ChunkBuild, Radius, Surface, TerrainChunk, TerrainLod};
+use crate::config::GameplayConfig;
+use crate::procgen::terrain::{self, ChunkMesh, TerrainShape};
+
+/// Adds `TerrainLod` to bodies with a solid `Surface`.
+pub fn attach_terrain(
+    mut commands: Commands,
+    bodies: Query<(Entity, &Surface, &Radius), Without<TerrainLod>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    config: Res<GameplayConfig>,
+) {
+    if !config.terrain.enabled {
+        return;
+    }
+    for (entity, surface, radius) in &bodies {
+        let Some(shape) = TerrainShape::new(surface.class, surface.seed, radius.0, &config.terrain)
+        else {
+            continue;
+        };
+        let material = materials.add(StandardMaterial {
+            perceptual_roughness: 0.8,
+            double_sided: true,
+            cull_mode: None,
+            ..default()
+        });
+        commands.entity(entity).insert(TerrainLod {
+            shape: Arc::new(shape),
+            material,
+            chunks: HashMap::new(),
+        });
+    }
+}
+
+/// Starts building the chunks the camera needs and retires the rest.
+///
+/// New chunks stay hidden until every chunk of their body is built, then
+/// the whole set is swapped in at once so no holes open while the camera
+/// moves. The body's own sphere mesh is dropped at the first swap.
+#[allow(clippy::type_complexity)]
+pub fn update_terrain_lod(
+    mut commands: Commands,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    mut bodies: Query<(Entity, &GlobalTransform, &mut TerrainLod, Has<Mesh3d>)>,
+    mut chunks: Query<(&mut Visibility, Has<ChunkBuild>), With<TerrainChunk>>,
+    config: Res<GameplayConfig>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let pool = AsyncComputeTaskPool::get();
+    let resolution = config.terrain.resolution;
+
+    for (body, transform, mut lod, has_sphere) in &mut bodies {
+        let local = transform
+            .affine()
+            .inverse()
+            .transform_point3(camera.translation())
+            .as_dvec3();
+        let wanted = terrain::select_chunks(local, lod.shape.radius, &config.terrain);
+
+        let mut building = false;
+        for &id in &wanted {
+            if lod.chunks.contains_key(&id) {
+                continue;
+            }
+            let shape = Arc::clone(&lod.shape);
+            let task = pool.spawn(async move { terrain::build_chunk(id, resolution, &shape) });
+            let chunk = commands
+                .spawn((
+                    TerrainChunk(id),
+                    Transform::default(),
+                    Visibility::Hidden,
+                    MeshMaterial3d(lod.material.clone()),
+                    ChunkBuild(task),
+                    Pickable::default(),
+                    ChildOf(body),
+                ))
+                .id();
+            lod.chunks.insert(id, chunk);
+            building = true;
+        }
+        if building
+            || lod
+                .chunks
+                .values()
+                .any(|&chunk| chunks.get(chunk).is_ok_and(|(_, pending)| pending))
+        {
+            continue;
+        }
+
+        lod.chunks.retain(|id, chunk| {
+            let keep = wanted.binary_search(id).is_ok();
+            if keep {
+                if let Ok((mut visibility, _)) = chunks.get_mut(*chunk) {
+                    visibility.set_if_neq(Visibility::Inherited);
+                }
+            } else {
+                commands.entity(*chunk).despawn();
+            }
+            keep
+        });
+        if has_sphere {
+            commands.entity(body).remove::<Mesh3d>();
+        }
+    }
+}
+
+/// Uploads finished chunk meshes, colored from their body's palette.
+pub fn finish_terrain_chunks(
+    mut commands: Commands,
+    mut builds: Query<(Entity, &mut ChunkBuild, &mut Transform, &ChildOf)>,
+    surfaces: Query<&Surface>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, mut build, mut transform, parent) in &mut builds {
+        let Some(chunk) = check_ready(&mut build.0) else {
+            continue;
+        };
+        let palette = surfaces
+            .get(parent.parent())
+            .map(|surface| surface.palette.as_slice())
+            .unwrap_or_default();
+        transform.translation = chunk.center.as_vec3();
+        commands
+            .entity(entity)
+            .remove::<ChunkBuild>()
+            .insert(Mesh3d(meshes.add(chunk_mesh(chunk, palette))));
+    }
+}
+
+/// Converts chunk vertex arrays to a mesh. Vertex colors run from the
+/// second palette color in the lowlands through the base color at mean
+/// radius to the accent on peaks.
+pub fn chunk_mesh(chunk: ChunkMesh, palette: &[Color]) -> Mesh {
+    let color = |index: usize| palette.get(index).or(palette.first()).copied();
+    let (base, low, high) = (
+        color(0).unwrap_or(Color::WHITE),
+        color(1).unwrap_or(Color::WHITE),
+        color(2).unwrap_or(Color::WHITE),
+    );
+    let colors: Vec<[f32; 4]> = chunk
+        .elevations
+        .iter()
+        .map(|&elevation| {
+            let mixed = if elevation < 0.0 {
+                base.mix(&low, -elevation)
+            } else {
+                base.mix(&high, elevation)
+            };
+            mixed.to_linear().to_f32_array()
+        })
+        .collect();
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, chunk.positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, chunk.normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(chunk.indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::procgen::PlanetClass;
+    use crate::procgen::terrain::vertices_per_chunk;
+
+    fn terrain_app() -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(GameplayConfig::default());
+        app.init_resource::<Assets<Mesh>>();
+        app.init_resource::<Assets<StandardMaterial>>();
+        app.add_systems(
+            Update,
+            (attach_terrain, update_terrain_lod, finish_terrain_chunks).chain(),
+        );
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera3d::default(),
+                GlobalTransform::from_translation(Vec3::new(6.4e8, 0.0, 0.0)),
+            ))
+            .id();
+        let planet = app
+            .world_mut()
+            .spawn((
+                Surface {
+                    class: PlanetClass::Rocky,
+                    albedo: 0.3,
+                    palette: vec![Color::WHITE, Color::BLACK, Color::WHITE],
+                    seed: 3,
+                },
+                Radius(6.4e6),
+                GlobalTransform::IDENTITY,
+                Mesh3d::default(),
+            ))
+            .id();
+        (app, camera, planet)
+    }
+
+    /// Runs frames until every chunk is built and swapped in.
+    fn settle(app: &mut App, planet: Entity) -> Vec<Entity> {
+        for _ in 0..1000 {
+            app.world_mut().run_schedule(Update);
+            let world = app.world_mut();
+            let pending = world
+                .query_filtered::<(), With<ChunkBuild>>()
+                .iter(world)
+                .count();
+            let hidden = world
+                .query::<(&TerrainChunk, &Visibility)>()
+                .iter(world)
+                .filter(|(_, visibility)| **visibility == Visibility::Hidden)
+                .count();
+            if pending == 0 && hidden == 0 && world.get::<Mesh3d>(planet).is_none() {
+                return world
+                    .get::<TerrainLod>(planet)
+                    .unwrap()
+                    .chunks
+                    .values()
+                    .copied()
+                    .collect();
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("terrain never settled");
+    }
+
+    #[test]
+    fn terrain_refines_as_the_camera_approaches() {
+        let (mut app, camera, planet) = terrain_app();
+        let config = app.world().resource::<GameplayConfig>().terrain.clone();
+        let per_chunk = vertices_per_chunk(config.resolution);
+
+        let far = settle(&mut app, planet);
+        assert_eq!(far.len(), 6);
+
+        *app.world_mut().get_mut::<GlobalTransform>(camera).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(6.41e6, 0.0, 0.0));
+        let near = settle(&mut app, planet);
+        assert!(near.len() > 6);
+        assert!(near.len() * per_chunk <= config.vertex_budget as usize);
+
+        let world = app.world();
+        let meshes = world.resource::<Assets<Mesh>>();
+        for chunk in &near {
+            assert_eq!(world.get::<ChildOf>(*chunk).unwrap().parent(), planet);
+            let mesh = meshes.get(&world.get::<Mesh3d>(*chunk).unwrap().0).unwrap();
+            assert_eq!(mesh.count_vertices(), per_chunk);
+        }
+        for chunk in &far {
+            assert!(world.get_entity(*chunk).is_err());
+        }
+    }
+}